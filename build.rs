@@ -3,6 +3,31 @@ use std::path::PathBuf;
 use std::process::Command;
 
 fn main() {
+    // 1-3. G.729 (opsiyonel): bcg729 kaynaklardan statik derlenir
+    if env::var_os("CARGO_FEATURE_G729").is_some() {
+        build_bcg729();
+    }
+
+    // 4. Opus (opsiyonel): Sistemdeki libopus'a dinamik bağlanır
+    if env::var_os("CARGO_FEATURE_OPUS").is_some() {
+        if let Ok(dir) = env::var("OPUS_LIB_DIR") {
+            println!("cargo:rustc-link-search=native={}", dir);
+        }
+        println!("cargo:rustc-link-lib=opus");
+        println!("cargo:rerun-if-env-changed=OPUS_LIB_DIR");
+    }
+
+    // 5. iLBC (opsiyonel): Sistemdeki libilbc'ye dinamik bağlanır
+    if env::var_os("CARGO_FEATURE_ILBC").is_some() {
+        if let Ok(dir) = env::var("ILBC_LIB_DIR") {
+            println!("cargo:rustc-link-search=native={}", dir);
+        }
+        println!("cargo:rustc-link-lib=ilbc");
+        println!("cargo:rerun-if-env-changed=ILBC_LIB_DIR");
+    }
+}
+
+fn build_bcg729() {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let root = PathBuf::from(manifest_dir);
     let bcg729_path = root.join("deps/bcg729");
@@ -22,8 +22,12 @@ fn inspect_codec(codec_type: CodecType, name: &str, values: &[i16]) {
     );
     println!("{}", "-".repeat(50));
 
-    let mut encoder = CodecFactory::create_encoder(codec_type);
-    let mut decoder = CodecFactory::create_decoder(codec_type);
+    let mut encoder = CodecFactory::builtin()
+        .create_encoder(codec_type)
+        .expect("Encoder başlatılamadı.");
+    let mut decoder = CodecFactory::builtin()
+        .create_decoder(codec_type)
+        .expect("Decoder başlatılamadı.");
 
     for &val in values {
         let input_slice = [val];
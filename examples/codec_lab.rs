@@ -30,7 +30,7 @@ fn main() {
     let args: Vec<String> = env::args().collect();
     if args.len() != 3 {
         eprintln!("Kullanım: cargo run --example codec_lab -- <input.wav> <codec>");
-        eprintln!("Desteklenen codec'ler: pcma, pcmu, g729"); // G722 kaldırıldı
+        eprintln!("Desteklenen codec'ler: pcma, pcmu, g729, g722, g726, gsm, l16");
         std::process::exit(1);
     }
 
@@ -41,7 +41,13 @@ fn main() {
         "pcma" => CodecType::PCMA,
         "pcmu" => CodecType::PCMU,
         "g729" => CodecType::G729,
-        // "g722" => CodecType::G722, // Kaldırıldı
+        "g722" => CodecType::G722,
+        "g726" => CodecType::G726_32,
+        "gsm" => CodecType::GSM,
+        "l16" => CodecType::L16 {
+            rate: 16000,
+            channels: 1,
+        },
         _ => {
             eprintln!("Hata: Geçersiz veya desteklenmeyen codec '{}'", codec_str);
             std::process::exit(1);
@@ -63,10 +69,11 @@ fn main() {
         std::process::exit(1);
     }
 
-    // G.729, PCMA, PCMU hepsi 8000Hz ister
-    if spec.sample_rate != 8000 {
+    // G.729, PCMA, PCMU 8000Hz; G.722 ve L16 (lab) 16000Hz ister
+    if spec.sample_rate != codec_type.sample_rate() {
         eprintln!(
-            "Uyarı: Bu kodek için 8000 Hz örnekleme hızı bekleniyor. Girdi: {} Hz",
+            "Uyarı: Bu kodek için {} Hz örnekleme hızı bekleniyor. Girdi: {} Hz",
+            codec_type.sample_rate(),
             spec.sample_rate
         );
     }
@@ -76,8 +83,12 @@ fn main() {
         .collect::<Result<_, _>>()
         .unwrap();
 
-    let mut encoder = CodecFactory::create_encoder(codec_type);
-    let mut decoder = CodecFactory::create_decoder(codec_type);
+    let mut encoder = CodecFactory::builtin()
+        .create_encoder(codec_type)
+        .expect("Encoder başlatılamadı.");
+    let mut decoder = CodecFactory::builtin()
+        .create_decoder(codec_type)
+        .expect("Decoder başlatılamadı.");
 
     let encoded_payload = encoder.encode(&original_samples);
     let decoded_samples = decoder.decode(&encoded_payload);
@@ -96,7 +107,7 @@ fn main() {
     let output_filename = format!("output_{}.wav", codec_str);
     let out_spec = hound::WavSpec {
         channels: 1,
-        sample_rate: 8000, // Artık sadece 8k kodekler var
+        sample_rate: codec_type.sample_rate(),
         bits_per_sample: 16,
         sample_format: hound::SampleFormat::Int,
     };
@@ -1,5 +1,4 @@
 // examples/noise_extractor.rs
-use hound;
 use std::env;
 
 fn main() {
@@ -26,7 +26,7 @@ fi
 # 1. UNIT TESTLER (Matematiksel Doğruluk)
 # ---------------------------------------------------------
 echo -e "\n🔬 FAZ 1: Matematiksel Bütünlük Testleri (cargo test)"
-cargo test --tests -- --nocapture
+cargo test --features g729 --tests -- --nocapture
 TEST_EXIT_CODE=$?
 
 if [ $TEST_EXIT_CODE -ne 0 ]; then
@@ -43,7 +43,7 @@ echo -e "\n🎙️ FAZ 2: İşitsel Doğrulama (WAV Üretimi)"
 CODECS=("pcma" "pcmu" "g729")
 
 for CODEC in "${CODECS[@]}"; do
-    cargo run --example codec_lab -- "$NB_ASSET" "$CODEC" > "$OUTPUT_DIR/${CODEC}_log.txt" 2>&1
+    cargo run --features g729 --example codec_lab -- "$NB_ASSET" "$CODEC" > "$OUTPUT_DIR/${CODEC}_log.txt" 2>&1
     
     if [ -f "output_${CODEC}.wav" ]; then
         mv "output_${CODEC}.wav" "$OUTPUT_DIR/output_${CODEC}.wav"
@@ -0,0 +1,83 @@
+// sentiric-rtp-core/src/codecs/clearmode.rs
+
+//! Clearmode (RFC 4040): 64 kbit/s şeffaf veri kanalı (`a=rtpmap:<pt> CLEARMODE/8000`).
+//! ISDN veri çağrıları ve güvenli terminaller için oktetler hiçbir değişikliğe uğramadan
+//! taşınır; ses değildir. `Encoder`/`Decoder` arayüzünde her örnek bir okteti taşır
+//! (0-255, düşük 8 bit). Bu akışlar `AudioResampler`'dan geçirilmemeli veya
+//! `AudioResampler::set_bypass` ile şeffaf moda alınmalıdır.
+
+use super::{CodecType, Decoder, Encoder};
+
+/// Yaygın dinamik PT.
+pub const CLEARMODE_PAYLOAD_TYPE: u8 = 125;
+
+// --- ENCODER ---
+#[derive(Default)]
+pub struct ClearmodeEncoder;
+
+impl ClearmodeEncoder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Oktetler olduğu gibi payload olur.
+    pub fn encode_octets(&mut self, octets: &[u8]) -> Vec<u8> {
+        octets.to_vec()
+    }
+}
+
+impl Encoder for ClearmodeEncoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::Clearmode
+    }
+
+    /// Her örneğin düşük 8 biti bir oktettir.
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        pcm_samples.iter().map(|&s| s as u8).collect()
+    }
+}
+
+// --- DECODER ---
+#[derive(Default)]
+pub struct ClearmodeDecoder;
+
+impl ClearmodeDecoder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Payload olduğu gibi oktet olarak döner.
+    pub fn decode_octets(&mut self, payload: &[u8]) -> Vec<u8> {
+        payload.to_vec()
+    }
+}
+
+impl Decoder for ClearmodeDecoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::Clearmode
+    }
+
+    /// Her oktet 0-255 aralığında bir örnek olur (işaret genişletmesi yapılmaz).
+    /// Kayıp telafisi yoktur: Veri kanalında sentetik oktet üretmek veriyi bozar.
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        payload.iter().map(|&b| b as i16).collect()
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clearmode_is_bit_transparent() {
+        let octets: Vec<u8> = (0..=255).collect();
+        let mut decoder = ClearmodeDecoder::new();
+        let samples = decoder.decode(&octets);
+        assert!(samples.iter().all(|s| (0..=255).contains(s)));
+        assert_eq!(ClearmodeEncoder::new().encode(&samples), octets);
+        assert_eq!(ClearmodeEncoder::new().encode_octets(&octets), octets);
+        assert_eq!(decoder.decode_octets(&octets), octets);
+        assert!(decoder.conceal(160).is_empty());
+    }
+}
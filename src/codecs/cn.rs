@@ -0,0 +1,312 @@
+// sentiric-rtp-core/src/codecs/cn.rs
+
+//! RFC 3389 Konfor Gürültüsü (Comfort Noise, PT 13).
+//! Sessizlik bastırma sırasında karşı taraf sadece arka plan gürültüsünün seviyesini
+//! ve spektral zarfını (yansıma katsayıları) gönderir; alıcı benzer gürültüyü sentezler.
+//! Payload: [gürültü seviyesi (-dBov, 0-127)] [yansıma katsayısı 1] ... [katsayı N]
+
+use super::{CodecType, Decoder, Encoder};
+
+/// Encoder'ın ürettiği ve decoder'ın kabul ettiği en yüksek model derecesi.
+pub const CN_MAX_ORDER: usize = 10;
+/// Varsayılan sentez bloğu: 20ms @ 8kHz.
+pub const CN_FRAME_SAMPLES: usize = 160;
+
+/// 0 dBov referansı: Tam ölçekli kare dalganın ortalama enerjisi.
+const FULL_SCALE_ENERGY: f64 = 32767.0 * 32767.0;
+/// Düzgün dağılımlı [-32768, 32767] gürültünün varyansı.
+const UNIFORM_NOISE_VARIANCE: f64 = 32768.0 * 32768.0 / 3.0;
+
+/// Ortalama enerjiyi RFC 3389 gürültü seviyesine (-dBov) çevirir.
+fn energy_to_level(energy: f64) -> u8 {
+    if energy <= 0.0 {
+        return 127;
+    }
+    let dbov = 10.0 * (energy / FULL_SCALE_ENERGY).log10();
+    (-dbov).floor().clamp(0.0, 127.0) as u8
+}
+
+fn level_to_energy(level: u8) -> f64 {
+    FULL_SCALE_ENERGY * 10f64.powf(-((level & 0x7F) as f64) / 10.0)
+}
+
+/// RFC 3389 Bölüm 3.2: Katsayılar [-1, 1] aralığında 8 bit ile doğrusal nicelenir.
+/// Hat üzerindeki işaret kuralı A(z) = 1 + Σ aᵢz⁻ⁱ'dir (pozitif korelasyon -> negatif k);
+/// içeride tahminci kuralı (x̂ = Σ aᵢx[n-i]) kullanıldığından işaret burada çevrilir.
+/// Nicemleme ve geri çözme aynı ölçeği kullanır; gidiş-dönüş katsayıyı küçültmez.
+const REFLECTION_SCALE: f64 = 127.0;
+
+fn quantize_reflection(k: f64) -> u8 {
+    (-k * REFLECTION_SCALE + 127.0).round().clamp(0.0, 254.0) as u8
+}
+
+fn dequantize_reflection(q: u8) -> f64 {
+    -((q as f64 - 127.0) / REFLECTION_SCALE).clamp(-0.999, 0.999)
+}
+
+/// Levinson-Durbin: Otokorelasyondan yansıma katsayıları.
+fn reflection_coefficients(samples: &[i16], order: usize) -> Vec<f64> {
+    let mut autocorr = vec![0.0f64; order + 1];
+    for (lag, r) in autocorr.iter_mut().enumerate() {
+        *r = samples
+            .iter()
+            .zip(samples.iter().skip(lag))
+            .map(|(&a, &b)| a as f64 * b as f64)
+            .sum();
+    }
+    let mut refl = vec![0.0; order];
+    if autocorr[0] <= 0.0 {
+        return refl;
+    }
+    // Hafif beyaz gürültü düzeltmesi (sayısal kararlılık)
+    autocorr[0] *= 1.0001;
+
+    let mut lpc = vec![0.0f64; order + 1];
+    let mut error = autocorr[0];
+    for i in 0..order {
+        let mut acc = autocorr[i + 1];
+        for j in 0..i {
+            acc -= lpc[j + 1] * autocorr[i - j];
+        }
+        let k = acc / error;
+        refl[i] = k;
+        let prev = lpc.clone();
+        lpc[i + 1] = k;
+        for j in 0..i {
+            lpc[j + 1] = prev[j + 1] - k * prev[i - j];
+        }
+        error *= 1.0 - k * k;
+        if error <= 0.0 {
+            break;
+        }
+    }
+    refl
+}
+
+/// Yansıma katsayılarından doğrudan form LPC katsayıları (step-up).
+fn reflection_to_lpc(refl: &[f64]) -> Vec<f64> {
+    let mut lpc = vec![0.0f64; refl.len()];
+    for (i, &k) in refl.iter().enumerate() {
+        let prev = lpc.clone();
+        lpc[i] = k;
+        for j in 0..i {
+            lpc[j] = prev[j] - k * prev[i - 1 - j];
+        }
+    }
+    lpc
+}
+
+// --- ENCODER ---
+pub struct ComfortNoiseEncoder {
+    order: usize,
+}
+
+impl Default for ComfortNoiseEncoder {
+    fn default() -> Self {
+        Self::new(CN_MAX_ORDER)
+    }
+}
+
+impl ComfortNoiseEncoder {
+    /// order: Spektral model derecesi (0 = sadece seviye, en fazla `CN_MAX_ORDER`).
+    pub fn new(order: usize) -> Self {
+        Self {
+            order: order.min(CN_MAX_ORDER),
+        }
+    }
+}
+
+impl Encoder for ComfortNoiseEncoder {
+    /// Girdi, sessizlik dönemine ait (VAD'ın konuşma yok dediği) PCM olmalıdır.
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        if pcm_samples.is_empty() {
+            return Vec::new();
+        }
+        let energy = pcm_samples
+            .iter()
+            .map(|&s| s as f64 * s as f64)
+            .sum::<f64>()
+            / pcm_samples.len() as f64;
+        let mut payload = Vec::with_capacity(1 + self.order);
+        payload.push(energy_to_level(energy));
+        payload.extend(
+            reflection_coefficients(pcm_samples, self.order)
+                .into_iter()
+                .map(quantize_reflection),
+        );
+        payload
+    }
+
+    fn get_type(&self) -> CodecType {
+        CodecType::ComfortNoise
+    }
+}
+
+// --- DECODER ---
+pub struct ComfortNoiseDecoder {
+    /// Sentezde kullanılan (yumuşatılmış) enerji ve hedefi.
+    energy: f64,
+    target_energy: f64,
+    refl: Vec<f64>,
+    target_refl: Vec<f64>,
+    lpc: Vec<f64>,
+    history: [f64; CN_MAX_ORDER],
+    seed: u32,
+    frame_samples: usize,
+    active: bool,
+}
+
+impl Default for ComfortNoiseDecoder {
+    fn default() -> Self {
+        Self::new(CN_FRAME_SAMPLES)
+    }
+}
+
+impl ComfortNoiseDecoder {
+    /// frame_samples: Her CN paketi için üretilecek örnek sayısı (ptime).
+    pub fn new(frame_samples: usize) -> Self {
+        Self {
+            energy: 0.0,
+            target_energy: 0.0,
+            refl: Vec::new(),
+            target_refl: Vec::new(),
+            lpc: Vec::new(),
+            history: [0.0; CN_MAX_ORDER],
+            seed: 0x1234_5678,
+            frame_samples,
+            active: false,
+        }
+    }
+
+    /// Bir CN paketi alındı mı? (Alınmadıysa üretilecek gürültü yoktur.)
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Son alınan parametrelerle `samples` kadar gürültü üretir. CN paketleri seyrek
+    /// gönderildiği için, bir sonraki paket gelene kadar her ptime'da çağrılmalıdır.
+    pub fn generate(&mut self, samples: usize) -> Vec<i16> {
+        if !self.active {
+            return Vec::new();
+        }
+        // Ani seviye/spektrum sıçramalarını önlemek için hedefe yumuşak geçiş
+        self.energy = (self.energy + self.target_energy) / 2.0;
+        for (k, target) in self.refl.iter_mut().zip(&self.target_refl) {
+            *k = (*k + target) / 2.0;
+        }
+        self.lpc = reflection_to_lpc(&self.refl);
+
+        // Sentez filtresi 1/A(z) enerjiyi Π(1 - k²) oranında artırır; uyarım ona göre ölçeklenir.
+        let residual = self.refl.iter().fold(self.energy, |e, k| e * (1.0 - k * k));
+        let scale = (residual / UNIFORM_NOISE_VARIANCE).sqrt();
+
+        let order = self.lpc.len();
+        let mut output = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            // xorshift32
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 17;
+            self.seed ^= self.seed << 5;
+            let excitation = ((self.seed & 0xFFFF) as i32 - 0x8000) as f64 * scale;
+            let prediction: f64 = self.lpc.iter().zip(&self.history).map(|(a, y)| a * y).sum();
+            let sample = excitation + prediction;
+            if order > 0 {
+                self.history.copy_within(0..order - 1, 1);
+                self.history[0] = sample;
+            }
+            output.push(sample.round().clamp(i16::MIN as f64, i16::MAX as f64) as i16);
+        }
+        output
+    }
+}
+
+impl Decoder for ComfortNoiseDecoder {
+    /// Parametreleri günceller ve bir ptime'lık gürültü üretir.
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        let Some((&level, coeffs)) = payload.split_first() else {
+            return Vec::new();
+        };
+        let order = coeffs.len().min(CN_MAX_ORDER);
+        self.target_energy = level_to_energy(level);
+        self.target_refl = coeffs[..order]
+            .iter()
+            .map(|&q| dequantize_reflection(q))
+            .collect();
+        if !self.active || self.refl.len() != order {
+            // İlk paket veya model derecesi değişti: Doğrudan uygula
+            self.energy = self.target_energy;
+            self.refl = self.target_refl.clone();
+            self.history = [0.0; CN_MAX_ORDER];
+        }
+        self.active = true;
+        self.generate(self.frame_samples)
+    }
+
+    fn get_type(&self) -> CodecType {
+        CodecType::ComfortNoise
+    }
+
+    /// Payload boyutundan bağımsız olarak bir ptime'lık gürültü üretilir.
+    fn decoded_size(&self, _payload_len: usize) -> usize {
+        self.frame_samples
+    }
+
+    /// Paket gelmeyen aralıklar son parametrelerle doldurulur.
+    fn conceal(&mut self, samples: usize) -> Vec<i16> {
+        self.generate(samples)
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_of(pcm: &[i16]) -> u8 {
+        energy_to_level(pcm.iter().map(|&s| s as f64 * s as f64).sum::<f64>() / pcm.len() as f64)
+    }
+
+    #[test]
+    fn test_cn_roundtrip_matches_noise_level() {
+        // Alçak geçiren filtreden geçmiş gürültü (arka plan uğultusu)
+        let mut seed = 7u32;
+        let mut prev = 0.0;
+        let background: Vec<i16> = (0..1600)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                let white = ((seed >> 16) as i32 - 0x4000) as f64 * 0.05;
+                prev = 0.8 * prev + white;
+                prev as i16
+            })
+            .collect();
+
+        let payload = ComfortNoiseEncoder::default().encode(&background[..160]);
+        assert_eq!(payload.len(), 1 + CN_MAX_ORDER);
+        // Birinci yansıma katsayısı güçlü pozitif korelasyonu yansıtmalı (hat üzerinde negatif)
+        assert!(payload[1] < 64);
+        assert!(dequantize_reflection(payload[1]) > 0.5);
+        // Nicemleme gidiş-dönüşü ölçeği korur (yalnızca yarım adım hata)
+        for k in [-0.9, -0.5, 0.0, 0.25, 0.75] {
+            let back = dequantize_reflection(quantize_reflection(k));
+            assert!(
+                (back - k).abs() <= 0.5 / REFLECTION_SCALE,
+                "{} -> {}",
+                k,
+                back
+            );
+        }
+
+        let mut decoder = ComfortNoiseDecoder::default();
+        assert!(decoder.conceal(160).is_empty());
+        let mut noise = decoder.decode(&payload);
+        assert_eq!(noise.len(), CN_FRAME_SAMPLES);
+        for _ in 0..9 {
+            noise.extend(decoder.conceal(160));
+        }
+        let diff = level_of(&noise) as i32 - level_of(&background) as i32;
+        assert!(diff.abs() <= 3, "CN seviye farkı {} dB", diff);
+
+        // Sadece seviye içeren (0. derece) paket de geçerlidir
+        assert_eq!(decoder.decode(&[127]).len(), CN_FRAME_SAMPLES);
+    }
+}
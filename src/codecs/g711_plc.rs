@@ -0,0 +1,285 @@
+// sentiric-rtp-core/src/codecs/g711_plc.rs
+
+//! ITU-T G.711 Appendix I paket kaybı telafisi (PLC), 8 kHz.
+//! Kayıp başladığında geçmiş tampondan perde (pitch) periyodu bulunur ve son periyot
+//! tekrar edilir. Kayıp uzadıkça kullanılan periyot sayısı 1 -> 2 -> 3'e çıkar (vızıltıyı
+//! azaltır), ilk 10ms'den sonra ses 10ms başına %20 sönümlenir ve 60ms'de susar.
+//! Gerçek ses geri geldiğinde sentetik sinyalle overlap-add yapılır.
+//! Referans algoritmadan farklı olarak iyi çerçevelerde gecikme eklenmez; kayıp başındaki
+//! geçiş, geçmişin ters çevrilmiş son çeyrek periyoduyla yumuşatılır.
+
+const PITCH_MIN: usize = 40; // 200 Hz
+const PITCH_MAX: usize = 120; // 66.6 Hz
+const OVERLAP_MAX: usize = PITCH_MAX >> 2;
+const HISTORY_LEN: usize = PITCH_MAX * 3 + OVERLAP_MAX;
+const CORR_LEN: usize = 160;
+const CORR_MIN_POWER: f32 = 250.0;
+/// 10ms @ 8kHz
+const FRAME_SIZE: usize = 80;
+/// Kurtarmada overlap süresi, kaybedilen her ek 10ms için bu kadar uzar.
+const END_OVERLAP_INCR: usize = 32;
+/// İlk 10ms'den sonra örnek başına sönümleme (10ms'de %20).
+const ATTEN_INCR: f32 = 0.2 / FRAME_SIZE as f32;
+/// Sönümlemenin sıfıra ulaştığı kayıp süresi (60ms).
+const MAX_CONCEAL: usize = FRAME_SIZE + (1.0 / ATTEN_INCR) as usize;
+
+pub struct G711Plc {
+    /// Son HISTORY_LEN örnek (en yenisi sonda).
+    history: [f32; HISTORY_LEN],
+    /// Kayıp başındaki geçmişin kopyası; periyot genişletmede kullanılır.
+    snapshot: [f32; HISTORY_LEN],
+    /// Tekrar edilen döngü (uçları birleştirilmiş 1-3 perde periyodu).
+    cycle: [f32; PITCH_MAX * 3],
+    cycle_len: usize,
+    pitch: usize,
+    offset: usize,
+    /// Mevcut kayıpta telafi edilen örnek sayısı (0 = kayıp yok).
+    missing: usize,
+    concealed_total: u64,
+}
+
+impl Default for G711Plc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl G711Plc {
+    pub fn new() -> Self {
+        Self {
+            history: [0.0; HISTORY_LEN],
+            snapshot: [0.0; HISTORY_LEN],
+            cycle: [0.0; PITCH_MAX * 3],
+            cycle_len: PITCH_MIN,
+            pitch: PITCH_MIN,
+            offset: 0,
+            missing: 0,
+            concealed_total: 0,
+        }
+    }
+
+    /// Başlangıçtan beri telafi edilen örnek sayısı.
+    pub fn concealed_samples(&self) -> u64 {
+        self.concealed_total
+    }
+
+    /// Şu an bir kayıp dönemi içinde miyiz?
+    pub fn in_erasure(&self) -> bool {
+        self.missing > 0
+    }
+
+    /// Normalize çapraz korelasyonla perde periyodu (PITCH_MIN..=PITCH_MAX).
+    fn find_pitch(&self) -> usize {
+        let end = HISTORY_LEN;
+        let target = &self.history[end - CORR_LEN..end];
+        let mut best = PITCH_MAX;
+        let mut best_score = f32::MIN;
+        for lag in PITCH_MIN..=PITCH_MAX {
+            let candidate = &self.history[end - CORR_LEN - lag..end - lag];
+            let (mut corr, mut energy) = (0.0f32, 0.0f32);
+            for (t, c) in target.iter().zip(candidate) {
+                corr += t * c;
+                energy += c * c;
+            }
+            let score = if energy > CORR_MIN_POWER {
+                corr / energy.sqrt()
+            } else {
+                0.0
+            };
+            if score > best_score {
+                best_score = score;
+                best = lag;
+            }
+        }
+        best
+    }
+
+    /// Snapshot'ın son `len` örneğinden döngü kurar; son çeyrek periyot, döngü başlangıcından
+    /// önceki bölgeyle karıştırılır ki sondan başa geçiş kesintisiz olsun.
+    fn build_cycle(&mut self, len: usize) {
+        let overlap = self.pitch >> 2;
+        let start = HISTORY_LEN - len;
+        self.cycle[..len].copy_from_slice(&self.snapshot[start..]);
+        let step = 1.0 / overlap as f32;
+        for j in 0..overlap {
+            let w = (j + 1) as f32 * step;
+            let i = len - overlap + j;
+            self.cycle[i] = self.cycle[i] * (1.0 - w) + self.snapshot[start - overlap + j] * w;
+        }
+        self.cycle_len = len;
+    }
+
+    fn next_synthetic(&mut self) -> f32 {
+        let sample = self.cycle[self.offset];
+        self.offset += 1;
+        if self.offset >= self.cycle_len {
+            self.offset = 0;
+        }
+        sample
+    }
+
+    fn gain(&self) -> f32 {
+        if self.missing <= FRAME_SIZE {
+            1.0
+        } else {
+            (1.0 - ATTEN_INCR * (self.missing - FRAME_SIZE) as f32).max(0.0)
+        }
+    }
+
+    fn save_history(&mut self, samples: &[i16]) {
+        let n = samples.len().min(HISTORY_LEN);
+        self.history.copy_within(n.., 0);
+        for (dst, &s) in self.history[HISTORY_LEN - n..]
+            .iter_mut()
+            .zip(&samples[samples.len() - n..])
+        {
+            *dst = s as f32;
+        }
+    }
+
+    /// Kaybolan `samples` örnek yerine sentetik ses üretir.
+    pub fn conceal(&mut self, samples: usize) -> Vec<i16> {
+        let mut output = Vec::with_capacity(samples);
+        let mut i = 0;
+
+        if self.missing == 0 {
+            self.pitch = self.find_pitch();
+            self.snapshot = self.history;
+            self.build_cycle(self.pitch);
+            self.offset = 0;
+
+            // Kayıp başı: Geçmişin ters çevrilmiş kuyruğu ile sentetik sinyal karıştırılır
+            let overlap = (self.pitch >> 2).min(samples);
+            let step = 1.0 / (self.pitch >> 2) as f32;
+            while i < overlap {
+                let w = (i + 1) as f32 * step;
+                let old = self.history[HISTORY_LEN - 1 - i];
+                let new = self.next_synthetic();
+                output.push(old * (1.0 - w) + new * w);
+                i += 1;
+            }
+            self.missing += overlap;
+        }
+
+        while i < samples {
+            // 10ms ve 20ms sınırlarında döngü bir periyot genişletilir (en fazla 3 periyot)
+            if self.missing > 0
+                && self.missing.is_multiple_of(FRAME_SIZE)
+                && self.missing <= 2 * FRAME_SIZE
+                && self.cycle_len < self.pitch * 3
+            {
+                let old_offset = self.offset;
+                let old_cycle_len = self.cycle_len;
+                let old_cycle = self.cycle;
+                self.build_cycle(old_cycle_len + self.pitch);
+                // Aynı örnek yeni döngüde bir periyot ileridedir; faz korunur
+                self.offset = (old_offset + self.pitch) % self.cycle_len;
+                let overlap = self.pitch >> 2;
+                let step = 1.0 / overlap as f32;
+                let mut j = 0;
+                while j < overlap && i < samples {
+                    let w = (j + 1) as f32 * step;
+                    let old = old_cycle[(old_offset + j) % old_cycle_len];
+                    let new = self.next_synthetic();
+                    output.push((old * (1.0 - w) + new * w) * self.gain());
+                    self.missing += 1;
+                    i += 1;
+                    j += 1;
+                }
+                continue;
+            }
+            let sample = if self.missing >= MAX_CONCEAL {
+                0.0
+            } else {
+                self.next_synthetic() * self.gain()
+            };
+            output.push(sample);
+            self.missing += 1;
+            i += 1;
+        }
+
+        let output: Vec<i16> = output
+            .into_iter()
+            .map(|s| s.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+            .collect();
+        self.concealed_total += samples as u64;
+        self.save_history(&output);
+        output
+    }
+
+    /// Doğru alınmış çerçeveyi işler. Kayıp sonrası ilk çerçevede sentetik sinyalden
+    /// gerçek sese overlap-add ile geçilir (kayıp uzadıkça overlap uzar).
+    pub fn process_good(&mut self, pcm: &mut [i16]) {
+        if self.missing > 0 {
+            let lost_frames = self.missing.div_ceil(FRAME_SIZE);
+            let overlap = ((self.pitch >> 2) + END_OVERLAP_INCR * (lost_frames - 1))
+                .min(FRAME_SIZE)
+                .min(pcm.len());
+            let step = 1.0 / overlap.max(1) as f32;
+            for (j, sample) in pcm.iter_mut().take(overlap).enumerate() {
+                let w = (j + 1) as f32 * step;
+                let synthetic = if self.missing >= MAX_CONCEAL {
+                    0.0
+                } else {
+                    self.next_synthetic() * self.gain()
+                };
+                self.missing += 1;
+                let mixed = synthetic * (1.0 - w) + *sample as f32 * w;
+                *sample = mixed.round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            }
+            self.missing = 0;
+        }
+        self.save_history(pcm);
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn voiced(n: usize, start: usize) -> Vec<i16> {
+        // 100 Hz perdeli basit "sesli" sinyal (periyot 80 örnek)
+        (start..start + n)
+            .map(|i| {
+                let t = i as f32 * 2.0 * std::f32::consts::PI / 80.0;
+                ((t.sin() + 0.5 * (2.0 * t).sin()) * 8000.0) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_plc_replicates_pitch_and_fades_out() {
+        let mut plc = G711Plc::new();
+        for frame in 0..5 {
+            let mut pcm = voiced(FRAME_SIZE, frame * FRAME_SIZE);
+            plc.process_good(&mut pcm);
+        }
+        assert_eq!(plc.find_pitch(), 80);
+
+        // İlk 10ms: Periyot tekrarı orijinal sinyali yakından izlemeli
+        let concealed = plc.conceal(FRAME_SIZE);
+        let expected = voiced(FRAME_SIZE, 5 * FRAME_SIZE);
+        let err: f64 = concealed
+            .iter()
+            .zip(&expected)
+            .skip(OVERLAP_MAX)
+            .map(|(a, b)| (*a as f64 - *b as f64).abs())
+            .sum::<f64>()
+            / (FRAME_SIZE - OVERLAP_MAX) as f64;
+        assert!(err < 200.0, "ortalama hata {}", err);
+
+        // 60ms sonra tamamen sessiz
+        let tail = plc.conceal(6 * FRAME_SIZE);
+        assert!(tail[tail.len() - FRAME_SIZE..].iter().all(|&s| s == 0));
+        assert!(plc.in_erasure());
+        assert_eq!(plc.concealed_samples(), 7 * FRAME_SIZE as u64);
+
+        // Kurtarma: Overlap bittikten sonra gerçek ses aynen geçer
+        let original = voiced(FRAME_SIZE, 0);
+        let mut pcm = original.clone();
+        plc.process_good(&mut pcm);
+        assert!(!plc.in_erasure());
+        assert_eq!(pcm[FRAME_SIZE - 1], original[FRAME_SIZE - 1]);
+    }
+}
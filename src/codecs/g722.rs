@@ -1,96 +1,390 @@
 // sentiric-rtp-core/src/codecs/g722.rs
 
-// ============================================================================
-// !!! SIMULATION ONLY - NOT FOR PRODUCTION TRANSCODING !!!
-// This implementation simulates 16kHz wideband audio by upsampling G.711.
-// It is NOT a standard G.722 SB-ADPCM implementation.
-// Use this only for passing RTP packets or basic testing.
-// ============================================================================
+//! ITU-T G.722 (SB-ADPCM) 64 kbit/s geniş bant kodek.
+//! 16 kHz PCM, QMF ile iki alt banda (0-4 kHz / 4-8 kHz) bölünür; alt bant 6 bit,
+//! üst bant 2 bit ADPCM ile kodlanır. Her byte iki 16 kHz örneği (bir 8 kHz QMF adımını) taşır.
+//!
+//! RFC 3551 Bölüm 4.5.2: Tarihsel bir hata nedeniyle RTP saati 8000 Hz'dir, ses ise 16 kHz
+//! örneklenir. Bkz. `CodecType::rtp_clock_rate`.
 
-use super::{Encoder, Decoder, CodecType};
-use super::pcmu::PcmuEncoder;
-use super::codec_data::ULAW_TO_LINEAR_LUT;
+use super::{CodecType, Decoder, Encoder};
 
-pub struct G722;
+const QMF_COEFFS: [i32; 12] = [3, -11, 12, 32, -210, 951, 3876, -805, 362, -156, 53, -11];
 
-impl G722 {
-    pub fn new() -> Self {
-        G722 {}
+/// Alt bant niceleyici karar seviyeleri (Q6).
+const Q6: [i32; 32] = [
+    0, 35, 72, 110, 150, 190, 233, 276, 323, 370, 422, 473, 530, 587, 650, 714, 786, 858, 940,
+    1023, 1121, 1219, 1339, 1458, 1612, 1765, 1980, 2195, 2557, 2919, 0, 0,
+];
+const ILN: [i32; 32] = [
+    0, 63, 62, 31, 30, 29, 28, 27, 26, 25, 24, 23, 22, 21, 20, 19, 18, 17, 16, 15, 14, 13, 12, 11,
+    10, 9, 8, 7, 6, 5, 4, 0,
+];
+const ILP: [i32; 32] = [
+    0, 61, 60, 59, 58, 57, 56, 55, 54, 53, 52, 51, 50, 49, 48, 47, 46, 45, 44, 43, 42, 41, 40, 39,
+    38, 37, 36, 35, 34, 33, 32, 0,
+];
+const WL: [i32; 8] = [-60, -30, 58, 172, 334, 538, 1198, 3042];
+const RL42: [i32; 16] = [0, 7, 6, 5, 4, 3, 2, 1, 7, 6, 5, 4, 3, 2, 1, 0];
+const ILB: [i32; 32] = [
+    2048, 2093, 2139, 2186, 2233, 2282, 2332, 2383, 2435, 2489, 2543, 2599, 2656, 2714, 2774, 2834,
+    2896, 2960, 3025, 3091, 3158, 3228, 3298, 3371, 3444, 3520, 3597, 3676, 3756, 3838, 3922, 4008,
+];
+const QM4: [i32; 16] = [
+    0, -20456, -12896, -8968, -6288, -4240, -2584, -1200, 20456, 12896, 8968, 6288, 4240, 2584,
+    1200, 0,
+];
+const QM6: [i32; 64] = [
+    -136, -136, -136, -136, -24808, -21904, -19008, -16704, -14984, -13512, -12280, -11192, -10232,
+    -9360, -8576, -7856, -7192, -6576, -6000, -5456, -4944, -4464, -4008, -3576, -3168, -2776,
+    -2400, -2032, -1688, -1360, -1040, -728, 24808, 21904, 19008, 16704, 14984, 13512, 12280,
+    11192, 10232, 9360, 8576, 7856, 7192, 6576, 6000, 5456, 4944, 4464, 4008, 3576, 3168, 2776,
+    2400, 2032, 1688, 1360, 1040, 728, 432, 136, -432, -136,
+];
+const IHN: [i32; 3] = [0, 1, 0];
+const IHP: [i32; 3] = [0, 3, 2];
+const WH: [i32; 3] = [0, -214, 798];
+const RH2: [i32; 4] = [2, 1, 2, 1];
+const QM2: [i32; 4] = [-7408, -1616, 7408, 1616];
+
+#[inline]
+fn saturate(amp: i32) -> i32 {
+    amp.clamp(i16::MIN as i32, i16::MAX as i32)
+}
+
+/// Tek bir alt bandın uyarlamalı tahminci (predictor) durumu.
+#[derive(Clone, Default)]
+struct Band {
+    s: i32,
+    sp: i32,
+    sz: i32,
+    r: [i32; 3],
+    a: [i32; 3],
+    ap: [i32; 3],
+    p: [i32; 3],
+    d: [i32; 7],
+    b: [i32; 7],
+    bp: [i32; 7],
+    sg: [i32; 7],
+    nb: i32,
+    det: i32,
+}
+
+impl Band {
+    fn new(det: i32) -> Self {
+        Self {
+            det,
+            ..Self::default()
+        }
+    }
+
+    /// Blok 3L/3H: LOGSCL + SCALEL (ölçek faktörü uyarlaması).
+    fn adapt_scale(&mut self, weight: i32, nb_max: i32, shift: i32) {
+        self.nb = (((self.nb * 127) >> 7) + weight).clamp(0, nb_max);
+        let wd1 = ((self.nb >> 6) & 31) as usize;
+        let wd2 = shift - (self.nb >> 11);
+        let wd3 = if wd2 < 0 {
+            ILB[wd1] << -wd2
+        } else {
+            ILB[wd1] >> wd2
+        };
+        self.det = wd3 << 2;
+    }
+
+    /// Blok 4: Yeniden yapılandırma ve tahminci katsayılarının güncellenmesi.
+    fn update(&mut self, d: i32) {
+        // RECONS / PARREC
+        self.d[0] = d;
+        self.r[0] = saturate(self.s + d);
+        self.p[0] = saturate(self.sz + d);
+
+        // UPPOL2
+        for i in 0..3 {
+            self.sg[i] = self.p[i] >> 15;
+        }
+        let wd1 = saturate(self.a[1] << 2);
+        let wd2 = if self.sg[0] == self.sg[1] { -wd1 } else { wd1 }.min(32767);
+        let mut wd3 = (wd2 >> 7) + if self.sg[0] == self.sg[2] { 128 } else { -128 };
+        wd3 += (self.a[2] * 32512) >> 15;
+        self.ap[2] = wd3.clamp(-12288, 12288);
+
+        // UPPOL1
+        self.sg[0] = self.p[0] >> 15;
+        self.sg[1] = self.p[1] >> 15;
+        let wd1 = if self.sg[0] == self.sg[1] { 192 } else { -192 };
+        let wd2 = (self.a[1] * 32640) >> 15;
+        let limit = saturate(15360 - self.ap[2]);
+        self.ap[1] = saturate(wd1 + wd2).clamp(-limit, limit);
+
+        // UPZERO
+        let wd1 = if d == 0 { 0 } else { 128 };
+        self.sg[0] = d >> 15;
+        for i in 1..7 {
+            self.sg[i] = self.d[i] >> 15;
+            let wd2 = if self.sg[i] == self.sg[0] { wd1 } else { -wd1 };
+            let wd3 = (self.b[i] * 32640) >> 15;
+            self.bp[i] = saturate(wd2 + wd3);
+        }
+
+        // DELAYA
+        for i in (1..7).rev() {
+            self.d[i] = self.d[i - 1];
+            self.b[i] = self.bp[i];
+        }
+        for i in (1..3).rev() {
+            self.r[i] = self.r[i - 1];
+            self.p[i] = self.p[i - 1];
+            self.a[i] = self.ap[i];
+        }
+
+        // FILTEP
+        let wd1 = (self.a[1] * saturate(self.r[1] + self.r[1])) >> 15;
+        let wd2 = (self.a[2] * saturate(self.r[2] + self.r[2])) >> 15;
+        self.sp = saturate(wd1 + wd2);
+
+        // FILTEZ
+        let mut sz = 0;
+        for i in (1..7).rev() {
+            sz += (self.b[i] * saturate(self.d[i] + self.d[i])) >> 15;
+        }
+        self.sz = saturate(sz);
+
+        // PREDIC
+        self.s = saturate(self.sp + self.sz);
     }
+}
 
-    // --- Simülasyon Yardımcıları ---
+pub struct G722Encoder {
+    low: Band,
+    high: Band,
+    qmf: [i32; 24],
+    /// Tek sayıda örnekle çağrıldığında bir sonraki çağrıya devreden örnek.
+    pending: Option<i16>,
+}
 
-    // Encode: Linear PCM -> Simulated 4-bit Nibble
-    // Bu, G.711'in 8-bit çıktısını alıp 4-bit'e sıkıştıran daha iyi bir quantizer.
-    fn encode_nibble(sample: i16) -> u8 {
-        let ulaw_val = PcmuEncoder::linear_to_ulaw(sample);
-        // 8-bit u-law değerini daha düzgün bir 4-bit'e dönüştür
-        // Örnek: İlk 4 biti al ve yuvarlama için 0x08 (yarım bit) ekle.
-        ((ulaw_val >> 4) & 0x07) as u8 // En üst 3 bit + yarım bit (daha kaliteli quantize)
+impl Default for G722Encoder {
+    fn default() -> Self {
+        Self::new()
     }
+}
+
+impl G722Encoder {
+    pub fn new() -> Self {
+        Self {
+            low: Band::new(32),
+            high: Band::new(8),
+            qmf: [0; 24],
+            pending: None,
+        }
+    }
+
+    fn encode_pair(&mut self, s0: i16, s1: i16) -> u8 {
+        // Verici QMF: Her iki örnekten bir alt ve bir üst bant örneği
+        self.qmf.copy_within(2.., 0);
+        self.qmf[22] = s0 as i32;
+        self.qmf[23] = s1 as i32;
+        let mut sum_even = 0;
+        let mut sum_odd = 0;
+        for i in 0..12 {
+            sum_odd += self.qmf[2 * i] * QMF_COEFFS[i];
+            sum_even += self.qmf[2 * i + 1] * QMF_COEFFS[11 - i];
+        }
+        let xlow = (sum_even + sum_odd) >> 14;
+        let xhigh = (sum_even - sum_odd) >> 14;
+
+        // Alt bant: SUBTRA, QUANTL, INVQAL
+        let el = saturate(xlow - self.low.s);
+        let wd = if el >= 0 { el } else { -(el + 1) };
+        let mut i = 1;
+        while i < 30 && wd >= (Q6[i] * self.low.det) >> 12 {
+            i += 1;
+        }
+        let ilow = if el < 0 { ILN[i] } else { ILP[i] };
+        let ril = (ilow >> 2) as usize;
+        let dlow = (self.low.det * QM4[ril]) >> 15;
+        self.low.adapt_scale(WL[RL42[ril] as usize], 18432, 8);
+        self.low.update(dlow);
 
-    // Decode: Simulated 4-bit Nibble -> Linear PCM
-    fn decode_nibble(nibble: u8) -> i16 {
-        // 4-bit değeri alıp, 8-bit u-law'a geri dönüştür.
-        // Boş kalan alt bitleri doldurmak önemlidir.
-        let ulaw_val = (nibble << 4) | 0x08; // En alttaki 4 biti 0x08 ile doldur (orta nokta)
-        ULAW_TO_LINEAR_LUT[ulaw_val as usize]
+        // Üst bant: SUBTRA, QUANTH, INVQAH
+        let eh = saturate(xhigh - self.high.s);
+        let wd = if eh >= 0 { eh } else { -(eh + 1) };
+        let mih = if wd >= (564 * self.high.det) >> 12 {
+            2
+        } else {
+            1
+        };
+        let ihigh = if eh < 0 { IHN[mih] } else { IHP[mih] } as usize;
+        let dhigh = (self.high.det * QM2[ihigh]) >> 15;
+        self.high.adapt_scale(WH[RH2[ihigh] as usize], 22528, 10);
+        self.high.update(dhigh);
+
+        ((ihigh as i32) << 6 | ilow) as u8
     }
 }
 
-impl Encoder for G722 {
+impl Encoder for G722Encoder {
+    /// Girdi 16 kHz PCM olmalıdır; 20 ms (320 örnek) -> 160 byte.
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(pcm_samples.len() / 2 + 1);
+        let mut samples = pcm_samples;
+        if let Some(first) = self.pending.take() {
+            match samples.split_first() {
+                Some((&second, rest)) => {
+                    output.push(self.encode_pair(first, second));
+                    samples = rest;
+                }
+                None => self.pending = Some(first),
+            }
+        }
+        let mut pairs = samples.chunks_exact(2);
+        for pair in &mut pairs {
+            output.push(self.encode_pair(pair[0], pair[1]));
+        }
+        if let [last] = pairs.remainder() {
+            self.pending = Some(*last);
+        }
+        output
+    }
+
     fn get_type(&self) -> CodecType {
         CodecType::G722
     }
 
-    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
-        // G.722, 16kHz input bekler.
-        // 8kHz input alıyorsak, basitçe örnekleri tekrarlayarak 16kHz simülasyonu yapalım.
-        // Bu "upsampling" kalitesizdir ama simülasyon amacına uyar.
-        let mut wideband_samples = Vec::with_capacity(pcm_samples.len() * 2);
-        for &sample in pcm_samples {
-            wideband_samples.push(sample);
-            wideband_samples.push(sample); // Her örneği iki kez tekrarla
+    /// Bekleyen tek örnek sessizlikle çiftlenip kodlanır (akışın son örneği kaybolmaz).
+    fn flush(&mut self) -> Vec<u8> {
+        match self.pending.take() {
+            Some(last) => vec![self.encode_pair(last, 0)],
+            None => Vec::new(),
+        }
+    }
+}
+
+pub struct G722Decoder {
+    low: Band,
+    high: Band,
+    qmf: [i32; 24],
+}
+
+impl Default for G722Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl G722Decoder {
+    pub fn new() -> Self {
+        Self {
+            low: Band::new(32),
+            high: Band::new(8),
+            qmf: [0; 24],
         }
+    }
 
-        let mut output = Vec::with_capacity(wideband_samples.len() / 2); // 2 adet 4-bit nibble = 1 byte
+    fn decode_byte(&mut self, code: u8, output: &mut Vec<i16>) {
+        let ilow = (code & 0x3F) as usize;
+        let ihigh = ((code >> 6) & 0x03) as usize;
 
-        for chunk in wideband_samples.chunks(2) {
-            // chunk[0] = s1 (first sample), chunk[1] = s2 (second sample)
-            let s1 = chunk[0];
-            let s2 = chunk[1];
+        // Alt bant: INVQBL (6 bit) ile çıkış, INVQAL (4 bit) ile tahminci güncellemesi
+        let rlow = (self.low.s + ((self.low.det * QM6[ilow]) >> 15)).clamp(-16384, 16383);
+        let ril = ilow >> 2;
+        let dlow = (self.low.det * QM4[ril]) >> 15;
+        self.low.adapt_scale(WL[RL42[ril] as usize], 18432, 8);
+        self.low.update(dlow);
 
-            let n1 = Self::encode_nibble(s1);
-            let n2 = Self::encode_nibble(s2);
+        // Üst bant
+        let dhigh = (self.high.det * QM2[ihigh]) >> 15;
+        let rhigh = (dhigh + self.high.s).clamp(-16384, 16383);
+        self.high.adapt_scale(WH[RH2[ihigh] as usize], 22528, 10);
+        self.high.update(dhigh);
 
-            // G.722 byte formatı: [MSB (Sample 2'nin nibble'ı) | LSB (Sample 1'in nibble'ı)]
-            let byte = (n2 << 4) | n1;
-            output.push(byte);
+        // Alıcı QMF
+        self.qmf.copy_within(2.., 0);
+        self.qmf[22] = rlow + rhigh;
+        self.qmf[23] = rlow - rhigh;
+        let mut xout1 = 0;
+        let mut xout2 = 0;
+        for i in 0..12 {
+            xout2 += self.qmf[2 * i] * QMF_COEFFS[i];
+            xout1 += self.qmf[2 * i + 1] * QMF_COEFFS[11 - i];
         }
+        output.push(saturate(xout1 >> 11) as i16);
+        output.push(saturate(xout2 >> 11) as i16);
+    }
+}
 
+impl Decoder for G722Decoder {
+    /// Çıktı 16 kHz PCM'dir; her byte iki örnek üretir.
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        let mut output = Vec::with_capacity(payload.len() * 2);
+        for &code in payload {
+            self.decode_byte(code, &mut output);
+        }
         output
     }
-}
 
-impl Decoder for G722 {
     fn get_type(&self) -> CodecType {
         CodecType::G722
     }
+}
 
-    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
-        let mut output = Vec::with_capacity(payload.len() * 2); // Her byte'dan 2 sample çıkar
-
-        for byte in payload {
-            // Byte'ı iki nibble'a ayır
-            let n1 = byte & 0x0F;        // Sample 1'in nibble'ı
-            let n2 = (byte >> 4) & 0x0F; // Sample 2'nin nibble'ı
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            let s1 = Self::decode_nibble(n1);
-            let s2 = Self::decode_nibble(n2);
+    #[test]
+    fn test_g722_odd_chunks_match_single_call() {
+        let pcm: Vec<i16> = (0..640)
+            .map(|i| ((i as f64 * 0.19).sin() * 12000.0) as i16)
+            .collect();
+        let whole = G722Encoder::new().encode(&pcm);
+        assert_eq!(whole.len(), 320);
 
-            output.push(s1);
-            output.push(s2);
+        let mut encoder = G722Encoder::new();
+        let mut split = Vec::new();
+        for chunk in pcm.chunks(157) {
+            split.extend(encoder.encode(chunk));
         }
-        output
+        assert_eq!(split, whole);
+        assert!(encoder.flush().is_empty());
+        assert_eq!(G722Decoder::new().decode(&whole).len(), 640);
+
+        // Tek sayıda örnekle biten akışın son örneği flush ile kodlanır
+        let mut encoder = G722Encoder::new();
+        assert_eq!(encoder.encode(&pcm[..639]).len(), 319);
+        let mut padded = pcm[..639].to_vec();
+        padded.push(0);
+        let tail = encoder.flush();
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0], G722Encoder::new().encode(&padded)[319]);
+        assert!(encoder.flush().is_empty());
     }
-}
\ No newline at end of file
+
+    /// Beklenen değerler spandsp `g722_encode`/`g722_decode` (64 kbit/s) referansının
+    /// C dökümüyle aynı girdiden üretilmiştir; tablo veya blok 4 hataları burada yakalanır.
+    #[test]
+    fn test_g722_reference_vectors() {
+        // Sıfırlanmış durumda sessizlik: ilow = 58, ihigh = 3
+        assert_eq!(G722Encoder::new().encode(&[0; 8]), [0xFA; 4]);
+
+        let pcm: Vec<i16> = (0..64)
+            .map(|n: i32| {
+                (((n * 2731) % 16384 - 8192) * if (n / 8) % 2 == 1 { 3 } else { -2 }) as i16
+            })
+            .collect();
+        const CODES: [u8; 32] = [
+            0x8C, 0x20, 0x84, 0x20, 0x84, 0x20, 0x20, 0x91, 0x31, 0xB4, 0x2E, 0x98, 0x07, 0x93,
+            0x37, 0xA7, 0x1A, 0x87, 0x87, 0x2B, 0x8F, 0x0E, 0x9D, 0x37, 0xAA, 0x2A, 0x9A, 0x47,
+            0xEE, 0xA5, 0x6A, 0x96,
+        ];
+        const DECODED: [i16; 64] = [
+            -1, 0, 0, -1, -1, 1, 0, -7, -2, 22, -3, -83, -3, 181, -31, -334, 78, 478, 33, -657,
+            -975, 445, 6049, 10967, 5598, -5839, -6777, 2979, 7249, 2901, 1447, 4916, 5684, 606,
+            -8248, -15685, -14367, -6026, -1022, -587, 3889, 11117, 10289, 629, -7796, -12698,
+            -18751, -20027, -9626, 6299, 10092, -816, -10699, -10212, -4769, -2042, -2500, -1109,
+            5174, 10157, 7856, 4736, 7337, 4432,
+        ];
+        assert_eq!(G722Encoder::new().encode(&pcm), CODES);
+        assert_eq!(G722Decoder::new().decode(&CODES), DECODED);
+    }
+}
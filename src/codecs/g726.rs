@@ -0,0 +1,872 @@
+// sentiric-rtp-core/src/codecs/g726.rs
+
+//! ITU-T G.726 ADPCM (16/24/32/40 kbit/s), 8 kHz.
+//! Algoritma, ITU referans test vektörleriyle bit-exact olan Sun Microsystems referans
+//! uygulamasını (g72x.c) tam sayı aritmetiği ve 16-bit taşma davranışıyla birlikte izler.
+//! G.711 giriş/çıkışı için senkron tandem düzeltmesi (G.726 Bölüm 4.2.7) desteklenir;
+//! ITU test vektörleri `encode_law_sample` / `decode_sample_to_law` ile doğrulanabilir.
+//!
+//! Paketleme: RFC 3551 Bölüm 4.5.4 (`G726-32`) ilk kod kelimesini octet'in en az anlamlı
+//! bitlerine, ITU-T I.366.2 AAL2 (`AAL2-G726-32`) ise en anlamlı bitlerine yerleştirir.
+
+use super::codec_data::{ALAW_TO_LINEAR_LUT, ULAW_TO_LINEAR_LUT};
+use super::{CodecType, Decoder, Encoder};
+
+/// Hız başına kuantalayıcı ve adaptasyon tabloları.
+struct RateTables {
+    /// Kuantalayıcı karar seviyeleri (normalize log genlik).
+    quant: &'static [i32],
+    /// Kuantalayıcı durum sayısı (16 kbit/s dışında tektir; sıfır kodu kullanılmaz).
+    states: i32,
+    /// Kod kelimesi -> yeniden yapılandırılmış normalize log genlik.
+    dqln: &'static [i32],
+    /// Kod kelimesi -> ölçek faktörü çarpanının logu (WI, 2^-5 ölçekli değil).
+    wi: &'static [i32],
+    /// Kod kelimesi -> adaptasyon hızı kontrolü girdisi (FI).
+    fi: &'static [i32],
+}
+
+const TABLES_16: RateTables = RateTables {
+    quant: &[261],
+    states: 4,
+    dqln: &[116, 365, 365, 116],
+    wi: &[-704, 14048, 14048, -704],
+    fi: &[0, 0xE00, 0xE00, 0],
+};
+
+const TABLES_24: RateTables = RateTables {
+    quant: &[8, 218, 331],
+    states: 7,
+    dqln: &[-2048, 135, 273, 373, 373, 273, 135, -2048],
+    wi: &[-128, 960, 4384, 18624, 18624, 4384, 960, -128],
+    fi: &[0, 0x200, 0x400, 0xE00, 0xE00, 0x400, 0x200, 0],
+};
+
+const TABLES_32: RateTables = RateTables {
+    quant: &[-124, 80, 178, 246, 300, 349, 400],
+    states: 15,
+    dqln: &[
+        -2048, 4, 135, 213, 273, 323, 373, 425, 425, 373, 323, 273, 213, 135, 4, -2048,
+    ],
+    wi: &[
+        -384, 576, 1312, 2048, 3584, 6336, 11360, 35904, 35904, 11360, 6336, 3584, 2048, 1312, 576,
+        -384,
+    ],
+    fi: &[
+        0, 0, 0, 0x200, 0x200, 0x200, 0x600, 0xE00, 0xE00, 0x600, 0x200, 0x200, 0x200, 0, 0, 0,
+    ],
+};
+
+const TABLES_40: RateTables = RateTables {
+    quant: &[
+        -122, -16, 68, 139, 198, 250, 298, 339, 378, 413, 445, 475, 502, 526, 549,
+    ],
+    states: 31,
+    dqln: &[
+        -2048, -66, 28, 104, 169, 224, 274, 318, 358, 395, 429, 459, 488, 514, 539, 566, 566, 539,
+        514, 488, 459, 429, 395, 358, 318, 274, 224, 169, 104, 28, -66, -2048,
+    ],
+    wi: &[
+        448, 448, 768, 1248, 1280, 1312, 1856, 3200, 4512, 5728, 7008, 8960, 11456, 14080, 16928,
+        22272, 22272, 16928, 14080, 11456, 8960, 7008, 5728, 4512, 3200, 1856, 1312, 1280, 1248,
+        768, 448, 448,
+    ],
+    fi: &[
+        0, 0, 0, 0, 0, 0x200, 0x200, 0x200, 0x200, 0x200, 0x400, 0x600, 0x800, 0xA00, 0xC00, 0xC00,
+        0xC00, 0xC00, 0xA00, 0x800, 0x600, 0x400, 0x200, 0x200, 0x200, 0x200, 0x200, 0, 0, 0, 0, 0,
+    ],
+};
+
+/// G.726 bit hızı (örnek başına 2-5 bit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum G726Rate {
+    Kbps16,
+    Kbps24,
+    Kbps32,
+    Kbps40,
+}
+
+impl G726Rate {
+    pub fn bits_per_sample(self) -> u32 {
+        match self {
+            G726Rate::Kbps16 => 2,
+            G726Rate::Kbps24 => 3,
+            G726Rate::Kbps32 => 4,
+            G726Rate::Kbps40 => 5,
+        }
+    }
+
+    pub fn codec_type(self) -> CodecType {
+        match self {
+            G726Rate::Kbps16 => CodecType::G726_16,
+            G726Rate::Kbps24 => CodecType::G726_24,
+            G726Rate::Kbps32 => CodecType::G726_32,
+            G726Rate::Kbps40 => CodecType::G726_40,
+        }
+    }
+
+    pub fn from_codec_type(codec: CodecType) -> Option<Self> {
+        match codec {
+            CodecType::G726_16 => Some(G726Rate::Kbps16),
+            CodecType::G726_24 => Some(G726Rate::Kbps24),
+            CodecType::G726_32 => Some(G726Rate::Kbps32),
+            CodecType::G726_40 => Some(G726Rate::Kbps40),
+            _ => None,
+        }
+    }
+
+    fn tables(self) -> &'static RateTables {
+        match self {
+            G726Rate::Kbps16 => &TABLES_16,
+            G726Rate::Kbps24 => &TABLES_24,
+            G726Rate::Kbps32 => &TABLES_32,
+            G726Rate::Kbps40 => &TABLES_40,
+        }
+    }
+
+    /// Kod kelimesinin işaret biti.
+    fn sign_bit(self) -> i32 {
+        1 << (self.bits_per_sample() - 1)
+    }
+}
+
+/// Kod kelimelerinin octet içindeki sıralaması.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum G726Packing {
+    /// RFC 3551: İlk örnek en az anlamlı bitlerde (`G726-32/8000`).
+    #[default]
+    Rfc3551,
+    /// ITU-T I.366.2 / RFC 3551 Bölüm 4.5.4 notu: İlk örnek en anlamlı bitlerde (`AAL2-G726-32/8000`).
+    Aal2,
+}
+
+impl G726Packing {
+    /// SDP kodlama adından (`a=rtpmap`) paketleme düzenini çıkarır.
+    pub fn from_encoding_name(name: &str) -> Self {
+        if name
+            .get(..5)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("AAL2-"))
+        {
+            G726Packing::Aal2
+        } else {
+            G726Packing::Rfc3551
+        }
+    }
+
+    /// Paketleme düzenine göre SDP kodlama adı (örn. `AAL2-G726-32`).
+    pub fn encoding_name(self, rate: G726Rate) -> &'static str {
+        match (self, rate) {
+            (G726Packing::Rfc3551, G726Rate::Kbps16) => "G726-16",
+            (G726Packing::Rfc3551, G726Rate::Kbps24) => "G726-24",
+            (G726Packing::Rfc3551, G726Rate::Kbps32) => "G726-32",
+            (G726Packing::Rfc3551, G726Rate::Kbps40) => "G726-40",
+            (G726Packing::Aal2, G726Rate::Kbps16) => "AAL2-G726-16",
+            (G726Packing::Aal2, G726Rate::Kbps24) => "AAL2-G726-24",
+            (G726Packing::Aal2, G726Rate::Kbps32) => "AAL2-G726-32",
+            (G726Packing::Aal2, G726Rate::Kbps40) => "AAL2-G726-40",
+        }
+    }
+}
+
+/// C `short` atamasındaki 16-bit taşmayı taklit eder (bit-exactness için gerekli).
+fn s16(x: i32) -> i32 {
+    x as i16 as i32
+}
+
+/// Pozitif bir değerin bit uzunluğu (en fazla 15); referanstaki `quan(val, power2, 15)`.
+fn log2_bits(val: i32) -> i32 {
+    (32 - val.max(0).leading_zeros() as i32).min(15)
+}
+
+/// İki kayan nokta benzeri değeri çarpar (FMULT).
+fn fmult(an: i32, srn: i32) -> i32 {
+    let anmag = if an > 0 { an } else { (-an) & 0x1FFF };
+    let anexp = log2_bits(anmag) - 6;
+    let anmant = if anmag == 0 {
+        32
+    } else if anexp >= 0 {
+        anmag >> anexp
+    } else {
+        anmag << -anexp
+    };
+    let wanexp = anexp + ((srn >> 6) & 0xF) - 13;
+    let wanmant = (anmant * (srn & 0x3F) + 0x30) >> 4;
+    let retval = if wanexp >= 0 {
+        (wanmant << wanexp) & 0x7FFF
+    } else {
+        wanmant >> -wanexp
+    };
+    if (an ^ srn) < 0 {
+        -retval
+    } else {
+        retval
+    }
+}
+
+/// Fark sinyalini ölçek faktörüne göre log alanında kuantalar (kod kelimesi döner).
+fn quantize(d: i32, y: i32, tables: &RateTables) -> i32 {
+    let dqm = s16(d.abs());
+    let exp = log2_bits(dqm >> 1);
+    let mant = ((dqm << 7) >> exp) & 0x7F;
+    let dl = (exp << 7) + mant;
+    let dln = dl - (y >> 2);
+
+    let size = (tables.states - 1) >> 1;
+    let i = tables.quant.iter().take_while(|&&q| dln >= q).count() as i32;
+    if d < 0 {
+        // 1'e tümleyen
+        (size << 1) + 1 - i
+    } else if i == 0 && tables.states & 1 == 1 {
+        // Tek sayıda durumda sıfır kodu geçersizdir
+        tables.states
+    } else {
+        i
+    }
+}
+
+/// Normalize log genlikten fark sinyali (işaret-büyüklük biçiminde 16-bit).
+fn reconstruct(negative: bool, dqln: i32, y: i32) -> i32 {
+    let dql = dqln + (y >> 2);
+    if dql < 0 {
+        return if negative { -0x8000 } else { 0 };
+    }
+    let dex = (dql >> 7) & 15;
+    let dqt = 128 + (dql & 127);
+    let dq = (dqt << 7) >> (14 - dex);
+    if negative {
+        dq - 0x8000
+    } else {
+        dq
+    }
+}
+
+/// Büyüklüğü 4-bit üs + 6-bit mantis biçimine çevirir (FLOAT A/B).
+fn to_float(mag: i32, negative: bool) -> i32 {
+    let exp = log2_bits(mag);
+    let value = (exp << 6) + ((mag << 6) >> exp);
+    if negative {
+        value - 0x400
+    } else {
+        value
+    }
+}
+
+// --- SEGMENT YARDIMCILARI (Sun g711.c, tandem düzeltmesi için) ---
+/// 16-bit aralıkta segment; 8 = aralık dışı.
+fn segment(val: i32) -> i32 {
+    (0..8).find(|&s| val < (0x100 << s)).unwrap_or(8)
+}
+
+fn linear_to_alaw(pcm: i32) -> u8 {
+    let (mask, val) = if pcm >= 0 {
+        (0xD5, pcm)
+    } else {
+        (0x55, -pcm - 8)
+    };
+    let seg = segment(val);
+    if seg >= 8 {
+        return (0x7F ^ mask) as u8;
+    }
+    let shift = if seg < 2 { 4 } else { seg + 3 };
+    (((seg << 4) | ((val >> shift) & 0xF)) ^ mask) as u8
+}
+
+fn linear_to_ulaw(pcm: i32) -> u8 {
+    const BIAS: i32 = 0x84;
+    let (mask, val) = if pcm < 0 {
+        (0x7F, BIAS - pcm)
+    } else {
+        (0xFF, pcm + BIAS)
+    };
+    let seg = segment(val);
+    if seg >= 8 {
+        return (0x7F ^ mask) as u8;
+    }
+    (((seg << 4) | ((val >> (seg + 3)) & 0xF)) ^ mask) as u8
+}
+
+/// G.711 sıkıştırma kuralı (ITU test vektörleri A-law ve µ-law olarak verilir).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum G711Law {
+    ALaw,
+    MuLaw,
+}
+
+impl G711Law {
+    /// Kodu 14-bit doğrusal değere açar.
+    fn expand(self, code: u8) -> i32 {
+        let linear = match self {
+            G711Law::ALaw => ALAW_TO_LINEAR_LUT[code as usize],
+            G711Law::MuLaw => ULAW_TO_LINEAR_LUT[code as usize],
+        };
+        linear as i32 >> 2
+    }
+}
+
+/// Encoder ve decoder'ın ortak tahminci/adaptasyon durumu.
+struct G726State {
+    rate: G726Rate,
+    /// Yavaş (kararlı durum) ölçek faktörü.
+    yl: i32,
+    /// Hızlı (kararsız durum) ölçek faktörü.
+    yu: i32,
+    dms: i32,
+    dml: i32,
+    ap: i32,
+    /// Kutup (a) ve sıfır (b) tahminci katsayıları.
+    a: [i32; 2],
+    b: [i32; 6],
+    pk: [i32; 2],
+    /// Geçmiş fark ve yeniden yapılandırılmış sinyal (kayan nokta biçimi).
+    dq: [i32; 6],
+    sr: [i32; 2],
+    td: bool,
+}
+
+/// Bir örnek için tahmin sonuçları.
+struct Prediction {
+    se: i32,
+    sez: i32,
+    y: i32,
+}
+
+impl G726State {
+    fn new(rate: G726Rate) -> Self {
+        Self {
+            rate,
+            yl: 34816,
+            yu: 544,
+            dms: 0,
+            dml: 0,
+            ap: 0,
+            a: [0; 2],
+            b: [0; 6],
+            pk: [0; 2],
+            dq: [32; 6],
+            sr: [32; 2],
+            td: false,
+        }
+    }
+
+    fn predict(&self) -> Prediction {
+        let sezi = s16(self
+            .b
+            .iter()
+            .zip(&self.dq)
+            .map(|(&b, &dq)| fmult(b >> 2, dq))
+            .sum());
+        let pole = fmult(self.a[1] >> 2, self.sr[1]) + fmult(self.a[0] >> 2, self.sr[0]);
+        Prediction {
+            se: s16((sezi + pole) >> 1),
+            sez: sezi >> 1,
+            y: self.step_size(),
+        }
+    }
+
+    fn step_size(&self) -> i32 {
+        if self.ap >= 256 {
+            return self.yu;
+        }
+        let mut y = self.yl >> 6;
+        let dif = self.yu - y;
+        let al = self.ap >> 2;
+        if dif > 0 {
+            y += (dif * al) >> 6;
+        } else if dif < 0 {
+            y += (dif * al + 0x3F) >> 6;
+        }
+        y
+    }
+
+    /// Kod kelimesinden sinyali yeniden kurar, durumu günceller ve `sr` (14-bit) döndürür.
+    fn apply(&mut self, code: i32, p: &Prediction) -> i32 {
+        let tables = self.rate.tables();
+        let i = code as usize;
+        let dq = reconstruct(code & self.rate.sign_bit() != 0, tables.dqln[i], p.y);
+        let sr = s16(if dq < 0 {
+            p.se - (dq & 0x7FFF)
+        } else {
+            p.se + dq
+        });
+        let dqsez = s16(sr + p.sez - p.se);
+        self.update(p.y, tables.wi[i], tables.fi[i], dq, sr, dqsez);
+        sr
+    }
+
+    fn update(&mut self, y: i32, wi: i32, fi: i32, dq: i32, sr: i32, dqsez: i32) {
+        let pk0 = (dqsez < 0) as i32;
+        let mag = dq & 0x7FFF;
+
+        // TRANS: Ton geçişi (modem sinyali) tespiti
+        let ylint = self.yl >> 15;
+        let ylfrac = (self.yl >> 10) & 0x1F;
+        let thr = if ylint > 9 {
+            31 << 10
+        } else {
+            (32 + ylfrac) << ylint
+        };
+        let dqthr = (thr + (thr >> 1)) >> 1;
+        let tr = self.td && mag > dqthr;
+
+        // FUNCTW & FILTD & LIMB: Hızlı ölçek faktörü
+        self.yu = (y + ((wi - y) >> 5)).clamp(544, 5120);
+        // FILTE: Yavaş ölçek faktörü
+        self.yl += self.yu + ((-self.yl) >> 6);
+
+        let mut a2p = 0;
+        if tr {
+            self.a = [0; 2];
+            self.b = [0; 6];
+        } else {
+            let pks1 = pk0 ^ self.pk[0];
+
+            // UPA2
+            a2p = self.a[1] - (self.a[1] >> 7);
+            if dqsez != 0 {
+                let fa1 = if pks1 != 0 { self.a[0] } else { -self.a[0] };
+                if fa1 < -8191 {
+                    a2p -= 0x100;
+                } else if fa1 > 8191 {
+                    a2p += 0xFF;
+                } else {
+                    a2p += fa1 >> 5;
+                }
+                // LIMC
+                if pk0 ^ self.pk[1] != 0 {
+                    if a2p <= -12160 {
+                        a2p = -12288;
+                    } else if a2p >= 12416 {
+                        a2p = 12288;
+                    } else {
+                        a2p -= 0x80;
+                    }
+                } else if a2p <= -12416 {
+                    a2p = -12288;
+                } else if a2p >= 12160 {
+                    a2p = 12288;
+                } else {
+                    a2p += 0x80;
+                }
+            }
+            self.a[1] = a2p;
+
+            // UPA1 & LIMD
+            self.a[0] -= self.a[0] >> 8;
+            if dqsez != 0 {
+                self.a[0] += if pks1 == 0 { 192 } else { -192 };
+            }
+            let a1ul = 15360 - a2p;
+            self.a[0] = self.a[0].clamp(-a1ul, a1ul);
+
+            // UPB: 40 kbit/s daha yavaş sızdırır
+            let leak = if self.rate == G726Rate::Kbps40 { 9 } else { 8 };
+            for (b, &dq_old) in self.b.iter_mut().zip(&self.dq) {
+                *b -= *b >> leak;
+                if mag != 0 {
+                    *b += if (dq ^ dq_old) >= 0 { 128 } else { -128 };
+                }
+                *b = s16(*b);
+            }
+        }
+
+        // FLOAT A / FLOAT B
+        self.dq.copy_within(0..5, 1);
+        self.dq[0] = if mag == 0 {
+            if dq >= 0 {
+                0x20
+            } else {
+                s16(0xFC20)
+            }
+        } else {
+            to_float(mag, dq < 0)
+        };
+        self.sr[1] = self.sr[0];
+        self.sr[0] = if sr == 0 {
+            0x20
+        } else if sr > 0 {
+            to_float(sr, false)
+        } else if sr > -32768 {
+            to_float(-sr, true)
+        } else {
+            s16(0xFC20)
+        };
+
+        self.pk[1] = self.pk[0];
+        self.pk[0] = pk0;
+
+        // TONE
+        self.td = !tr && a2p < -11776;
+
+        // Adaptasyon hızı kontrolü (FILTA, FILTB, SUBTC)
+        self.dms += (fi - self.dms) >> 5;
+        self.dml += ((fi << 2) - self.dml) >> 7;
+        if tr {
+            self.ap = 256;
+        } else if y < 1536 || self.td || ((self.dms << 2) - self.dml).abs() >= (self.dml >> 3) {
+            self.ap += (0x200 - self.ap) >> 4;
+        } else {
+            self.ap += (-self.ap) >> 4;
+        }
+    }
+
+    /// 14-bit doğrusal örneği kodlar.
+    fn encode(&mut self, sl: i32) -> i32 {
+        let p = self.predict();
+        let d = s16(sl - p.se);
+        let code = quantize(d, p.y, self.rate.tables());
+        self.apply(code, &p);
+        code
+    }
+
+    /// Senkron tandem düzeltmesi: Çıkış G.711 kodu, yeniden kodlandığında aynı ADPCM kodunu
+    /// verecek şekilde bir adım aşağı/yukarı kaydırılır (G.726 Bölüm 4.2.7).
+    fn tandem_adjust(&self, law: G711Law, sr: i32, code: i32, p: &Prediction) -> u8 {
+        let sp = match law {
+            G711Law::ALaw => linear_to_alaw((if sr <= -32768 { -1 } else { sr } >> 1) << 3),
+            G711Law::MuLaw => linear_to_ulaw(if sr <= -32768 { 0 } else { sr } << 2),
+        };
+        let dx = s16(law.expand(sp) - p.se);
+        let id = quantize(dx, p.y, self.rate.tables());
+        if id == code {
+            return sp;
+        }
+        let sign = self.rate.sign_bit();
+        // İşaretli kodları sıralı (biased) büyüklüğe çevir
+        let lower = (id ^ sign) > (code ^ sign);
+        match (law, lower) {
+            (G711Law::ALaw, true) if sp & 0x80 != 0 => {
+                if sp == 0xD5 {
+                    0x55
+                } else {
+                    ((sp ^ 0x55) - 1) ^ 0x55
+                }
+            }
+            (G711Law::ALaw, true) => {
+                if sp == 0x2A {
+                    0x2A
+                } else {
+                    ((sp ^ 0x55) + 1) ^ 0x55
+                }
+            }
+            (G711Law::ALaw, false) if sp & 0x80 != 0 => {
+                if sp == 0xAA {
+                    0xAA
+                } else {
+                    ((sp ^ 0x55) + 1) ^ 0x55
+                }
+            }
+            (G711Law::ALaw, false) => {
+                if sp == 0x55 {
+                    0xD5
+                } else {
+                    ((sp ^ 0x55) - 1) ^ 0x55
+                }
+            }
+            (G711Law::MuLaw, true) if sp & 0x80 != 0 => {
+                if sp == 0xFF {
+                    0x7E
+                } else {
+                    sp + 1
+                }
+            }
+            (G711Law::MuLaw, true) => sp.saturating_sub(1),
+            (G711Law::MuLaw, false) if sp & 0x80 != 0 => {
+                if sp == 0x80 {
+                    0x80
+                } else {
+                    sp - 1
+                }
+            }
+            (G711Law::MuLaw, false) => {
+                if sp == 0x7F {
+                    0xFE
+                } else {
+                    sp + 1
+                }
+            }
+        }
+    }
+}
+
+// --- ENCODER ---
+pub struct G726Encoder {
+    state: G726State,
+    packing: G726Packing,
+    /// Bir sonraki octet'e taşınan, henüz tamamlanmamış bitler.
+    pending: u32,
+    pending_bits: u32,
+}
+
+impl Default for G726Encoder {
+    fn default() -> Self {
+        Self::new(G726Rate::Kbps32, G726Packing::Rfc3551)
+    }
+}
+
+impl G726Encoder {
+    pub fn new(rate: G726Rate, packing: G726Packing) -> Self {
+        Self {
+            state: G726State::new(rate),
+            packing,
+            pending: 0,
+            pending_bits: 0,
+        }
+    }
+
+    pub fn rate(&self) -> G726Rate {
+        self.state.rate
+    }
+
+    pub fn packing(&self) -> G726Packing {
+        self.packing
+    }
+
+    /// Tek bir 16-bit doğrusal örneği kodlar ve paketlenmemiş kod kelimesini döndürür.
+    pub fn encode_sample(&mut self, sample: i16) -> u8 {
+        self.state.encode(sample as i32 >> 2) as u8
+    }
+
+    /// Tek bir G.711 örneğini kodlar (ITU test vektörlerinin giriş biçimi).
+    pub fn encode_law_sample(&mut self, law: G711Law, code: u8) -> u8 {
+        self.state.encode(law.expand(code)) as u8
+    }
+
+    fn push_code(&mut self, code: u8, out: &mut Vec<u8>) {
+        let bits = self.state.rate.bits_per_sample();
+        match self.packing {
+            G726Packing::Rfc3551 => {
+                self.pending |= (code as u32) << self.pending_bits;
+                self.pending_bits += bits;
+                while self.pending_bits >= 8 {
+                    out.push(self.pending as u8);
+                    self.pending >>= 8;
+                    self.pending_bits -= 8;
+                }
+            }
+            G726Packing::Aal2 => {
+                self.pending = (self.pending << bits) | code as u32;
+                self.pending_bits += bits;
+                while self.pending_bits >= 8 {
+                    self.pending_bits -= 8;
+                    out.push((self.pending >> self.pending_bits) as u8);
+                }
+                self.pending &= (1 << self.pending_bits) - 1;
+            }
+        }
+    }
+}
+
+impl Encoder for G726Encoder {
+    fn get_type(&self) -> CodecType {
+        self.state.rate.codec_type()
+    }
+
+    /// Octet'e tamamlanmayan son kod kelimeleri bir sonraki çağrıya taşınır
+    /// (24/40 kbit/s'de 8'in katı örnek ile her paket tam octet olur).
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        let bits = self.state.rate.bits_per_sample() as usize;
+        let mut output = Vec::with_capacity((pcm_samples.len() * bits).div_ceil(8));
+        for &sample in pcm_samples {
+            let code = self.encode_sample(sample);
+            self.push_code(code, &mut output);
+        }
+        output
+    }
+}
+
+// --- DECODER ---
+pub struct G726Decoder {
+    state: G726State,
+    packing: G726Packing,
+}
+
+impl Default for G726Decoder {
+    fn default() -> Self {
+        Self::new(G726Rate::Kbps32, G726Packing::Rfc3551)
+    }
+}
+
+impl G726Decoder {
+    pub fn new(rate: G726Rate, packing: G726Packing) -> Self {
+        Self {
+            state: G726State::new(rate),
+            packing,
+        }
+    }
+
+    pub fn rate(&self) -> G726Rate {
+        self.state.rate
+    }
+
+    pub fn packing(&self) -> G726Packing {
+        self.packing
+    }
+
+    /// Tek bir kod kelimesini 16-bit doğrusal örneğe çözer.
+    pub fn decode_sample(&mut self, code: u8) -> i16 {
+        let code = code as i32 & ((1 << self.state.rate.bits_per_sample()) - 1);
+        let p = self.state.predict();
+        let sr = self.state.apply(code, &p);
+        (sr << 2).clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    /// Tek bir kod kelimesini tandem düzeltmeli G.711 koduna çözer (ITU test vektörlerinin
+    /// çıkış biçimi).
+    pub fn decode_sample_to_law(&mut self, law: G711Law, code: u8) -> u8 {
+        let code = code as i32 & ((1 << self.state.rate.bits_per_sample()) - 1);
+        let p = self.state.predict();
+        let sr = self.state.apply(code, &p);
+        self.state.tandem_adjust(law, sr, code, &p)
+    }
+
+    /// Payload'daki kod kelimelerini paketleme düzenine göre ayırır.
+    fn unpack<'a>(&self, payload: &'a [u8]) -> impl Iterator<Item = u8> + 'a {
+        let bits = self.state.rate.bits_per_sample();
+        let count = payload.len() * 8 / bits as usize;
+        let packing = self.packing;
+        (0..count).map(move |n| {
+            let start = n as u32 * bits;
+            let mut code = 0u32;
+            for bit in 0..bits {
+                let pos = start + bit;
+                let byte = payload[(pos / 8) as usize] as u32;
+                match packing {
+                    // LSB-first: Kelimenin bit'leri de octet içinde düşükten yükseğe
+                    G726Packing::Rfc3551 => code |= ((byte >> (pos % 8)) & 1) << bit,
+                    // MSB-first
+                    G726Packing::Aal2 => code = (code << 1) | ((byte >> (7 - pos % 8)) & 1),
+                }
+            }
+            code as u8
+        })
+    }
+}
+
+impl Decoder for G726Decoder {
+    fn get_type(&self) -> CodecType {
+        self.state.rate.codec_type()
+    }
+
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        self.unpack(payload)
+            .map(|code| self.decode_sample(code))
+            .collect()
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATES: [G726Rate; 4] = [
+        G726Rate::Kbps16,
+        G726Rate::Kbps24,
+        G726Rate::Kbps32,
+        G726Rate::Kbps40,
+    ];
+
+    fn speech_like(n: usize) -> Vec<i16> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64 / 8000.0;
+                let env = 0.6 + 0.4 * (2.0 * std::f64::consts::PI * 3.0 * t).sin();
+                (env * (9000.0 * (2.0 * std::f64::consts::PI * 300.0 * t).sin()
+                    + 4000.0 * (2.0 * std::f64::consts::PI * 1150.0 * t).sin()))
+                    as i16
+            })
+            .collect()
+    }
+
+    fn snr(original: &[i16], decoded: &[i16]) -> f64 {
+        let (mut sig, mut noise) = (0.0, 0.0);
+        for (&a, &b) in original.iter().zip(decoded) {
+            sig += a as f64 * a as f64;
+            noise += (a as f64 - b as f64).powi(2);
+        }
+        10.0 * (sig / noise.max(1.0)).log10()
+    }
+
+    #[test]
+    fn test_g726_packing_orders() {
+        // RFC 3551 Bölüm 4.5.4: 32 kbit/s'de ilk örnek octet'in düşük nibble'ında
+        for (packing, expected) in [(G726Packing::Rfc3551, 0x21), (G726Packing::Aal2, 0x12)] {
+            let mut encoder = G726Encoder::new(G726Rate::Kbps32, packing);
+            let mut out = Vec::new();
+            encoder.push_code(1, &mut out);
+            encoder.push_code(2, &mut out);
+            assert_eq!(out, vec![expected]);
+            let decoder = G726Decoder::new(G726Rate::Kbps32, packing);
+            assert_eq!(decoder.unpack(&out).collect::<Vec<_>>(), vec![1, 2]);
+        }
+
+        // 24 kbit/s: 8 kod = 3 octet; kodlar her iki düzende de geri açılmalı
+        let codes = [1u8, 7, 2, 6, 3, 5, 4, 0];
+        for packing in [G726Packing::Rfc3551, G726Packing::Aal2] {
+            let mut encoder = G726Encoder::new(G726Rate::Kbps24, packing);
+            let mut out = Vec::new();
+            for &c in &codes {
+                encoder.push_code(c, &mut out);
+            }
+            assert_eq!(out.len(), 3);
+            let decoder = G726Decoder::new(G726Rate::Kbps24, packing);
+            assert_eq!(decoder.unpack(&out).collect::<Vec<_>>(), codes);
+        }
+        assert_eq!(
+            G726Packing::from_encoding_name("aal2-G726-32"),
+            G726Packing::Aal2
+        );
+        assert_eq!(
+            G726Packing::from_encoding_name("G726-32"),
+            G726Packing::Rfc3551
+        );
+        assert_eq!(
+            G726Packing::Aal2.encoding_name(G726Rate::Kbps24),
+            "AAL2-G726-24"
+        );
+    }
+
+    #[test]
+    fn test_g726_roundtrip_all_rates() {
+        let pcm = speech_like(1600);
+        for (rate, min_snr) in RATES.into_iter().zip([8.0, 15.0, 20.0, 25.0]) {
+            let mut encoder = G726Encoder::new(rate, G726Packing::Rfc3551);
+            let mut decoder = G726Decoder::new(rate, G726Packing::Rfc3551);
+            let mut decoded = Vec::new();
+            for frame in pcm.chunks(160) {
+                let payload = encoder.encode(frame);
+                assert_eq!(payload.len(), 20 * rate.bits_per_sample() as usize);
+                decoded.extend(decoder.decode(&payload));
+            }
+            assert_eq!(decoded.len(), pcm.len());
+            // Adaptasyon süresi (ilk 20ms) hariç tutulur
+            let quality = snr(&pcm[160..], &decoded[160..]);
+            assert!(quality > min_snr, "{:?}: SNR {:.1} dB", rate, quality);
+        }
+    }
+
+    #[test]
+    fn test_g726_synchronous_tandem() {
+        // G.711 -> G.726 -> G.711 -> G.726 zinciri aynı ADPCM kodlarını üretmelidir
+        let pcm = speech_like(800);
+        for law in [G711Law::ALaw, G711Law::MuLaw] {
+            let input: Vec<u8> = pcm
+                .iter()
+                .map(|&s| match law {
+                    G711Law::ALaw => linear_to_alaw(s as i32),
+                    G711Law::MuLaw => linear_to_ulaw(s as i32),
+                })
+                .collect();
+            for rate in RATES {
+                let mut first = G726Encoder::new(rate, G726Packing::Rfc3551);
+                let mut decoder = G726Decoder::new(rate, G726Packing::Rfc3551);
+                let mut second = G726Encoder::new(rate, G726Packing::Rfc3551);
+                for &sample in &input {
+                    let code = first.encode_law_sample(law, sample);
+                    let relayed = decoder.decode_sample_to_law(law, code);
+                    assert_eq!(second.encode_law_sample(law, relayed), code, "{:?}", rate);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // sentiric-rtp-core/src/codecs/g729.rs
 
-use super::{CodecType, Decoder, Encoder};
+use super::{CodecError, CodecType, Decoder, Encoder};
 use std::ffi::c_void;
 
 extern "C" {
@@ -27,26 +27,106 @@ extern "C" {
     );
 }
 
+/// Bir G.729 konuşma çerçevesi: 10ms, 80 örnek, 10 byte.
+pub const FRAME_SAMPLES: usize = 80;
+pub const FRAME_BYTES: usize = 10;
+/// G.729 Annex B SID (Silence Insertion Descriptor) çerçevesi boyutu.
+pub const SID_BYTES: usize = 2;
+
+/// SDP `a=fmtp:18 annexb=...` değerine göre Annex B (VAD/DTX/CNG) etkin mi?
+/// RFC 4856: Parametre yoksa varsayılan `annexb=yes`'tir.
+pub fn annexb_enabled(fmtp: Option<&str>) -> bool {
+    let Some(fmtp) = fmtp else {
+        return true;
+    };
+    !fmtp.split(';').any(|param| {
+        param.split_once('=').is_some_and(|(k, v)| {
+            k.trim().eq_ignore_ascii_case("annexb") && v.trim().eq_ignore_ascii_case("no")
+        })
+    })
+}
+
+/// RFC 3551 Bölüm 4.5.6: Payload sıfır veya daha fazla 10 byte'lık çerçeve ve en sonda
+/// en fazla bir adet 2 byte'lık SID çerçevesinden oluşur.
+/// Dönen değer: (konuşma çerçeveleri, varsa SID). Başka bir artık boyut geçersizdir ve atlanır.
+pub fn split_payload(payload: &[u8]) -> (std::slice::ChunksExact<'_, u8>, Option<&[u8]>) {
+    let frames = payload.chunks_exact(FRAME_BYTES);
+    let sid = Some(frames.remainder()).filter(|r| r.len() == SID_BYTES);
+    (frames, sid)
+}
+
 // --- ENCODER ---
 pub struct G729Encoder {
     context: *mut c_void,
+    vad: bool,
+    /// Önceki çağrılardan kalan, henüz kodlanmamış girdi: 80 örneğe tamamlanmamış artık
+    /// (örn. 256'lık TTS blokları) veya paket sınırından sonra kalan çerçeveler.
+    pending: Vec<i16>,
+    /// Son yayılan çerçeveden bu yana iletilmeyen (DTX) çerçeve sayısı.
+    untransmitted: usize,
+    /// Son dönen paketin önünde atlanan, iletilmeyen çerçeve sayısı.
+    skipped: usize,
 }
 
-impl Default for G729Encoder {
-    fn default() -> Self {
-        Self::new()
+impl G729Encoder {
+    /// Annex B kapalı (annexb=no): Her 10ms için 10 byte üretilir.
+    pub fn new() -> Result<Self, CodecError> {
+        Self::with_vad(false)
     }
-}
 
-impl G729Encoder {
-    pub fn new() -> Self {
+    /// vad: Annex B VAD/DTX. Açıkken sessizlikte 2 byte'lık SID üretilir veya hiç çerçeve üretilmez.
+    /// bcg729 kanalı ayrılamazsa `CodecError::Init` döner.
+    pub fn with_vad(vad: bool) -> Result<Self, CodecError> {
+        let ctx = unsafe { initBcg729EncoderChannel(vad as u8) };
+        if ctx.is_null() {
+            return Err(CodecError::Init {
+                codec: CodecType::G729,
+                code: 0,
+            });
+        }
+        Ok(G729Encoder {
+            context: ctx,
+            vad,
+            pending: Vec::with_capacity(2 * FRAME_SAMPLES),
+            untransmitted: 0,
+            skipped: 0,
+        })
+    }
+
+    /// SDP `fmtp` satırına göre (`annexb=yes/no`) yapılandırılmış encoder.
+    pub fn from_fmtp(fmtp: Option<&str>) -> Result<Self, CodecError> {
+        Self::with_vad(annexb_enabled(fmtp))
+    }
+
+    pub fn vad_enabled(&self) -> bool {
+        self.vad
+    }
+
+    /// Ara bellekte bekleyen, henüz kodlanmamış örnek sayısı. Paket bir boşlukta veya SID'de
+    /// bittiyse 80'den fazla olabilir; kalan çerçeveler sonraki çağrıda kodlanır.
+    pub fn pending_samples(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Son dönen paketin önünde atlanan (DTX ile iletilmeyen) 10ms çerçeve sayısı.
+    /// Paketin RTP zaman damgası, önceki paketin sonundan bu kadar çerçeve ileridedir.
+    pub fn skipped_frames(&self) -> usize {
+        self.skipped
+    }
+
+    /// Tek bir 10ms çerçeveyi kodlar; dönen uzunluk 10 (konuşma), 2 (SID) veya 0 (iletilmez).
+    fn encode_frame(&mut self, frame: &[i16; FRAME_SAMPLES]) -> ([u8; FRAME_BYTES], usize) {
+        let mut bitstream = [0u8; FRAME_BYTES];
+        let mut out_len: u8 = 0;
         unsafe {
-            let ctx = initBcg729EncoderChannel(0);
-            if ctx.is_null() {
-                panic!("G.729 Encoder Init Failed");
-            }
-            G729Encoder { context: ctx }
+            bcg729Encoder(
+                self.context,
+                frame.as_ptr(),
+                bitstream.as_mut_ptr(),
+                &mut out_len,
+            );
         }
+        (bitstream, out_len as usize)
     }
 }
 
@@ -67,48 +147,106 @@ impl Encoder for G729Encoder {
         CodecType::G729
     }
 
+    /// Girdi bir RTP paketi olarak kodlanır. DTX açıkken çıktı RFC 3551 düzenine uyar:
+    /// önce ardışık konuşma çerçeveleri, en sonda en fazla bir SID. Paket, konuşmadan sonra
+    /// gelen ilk iletilmeyen çerçevede veya SID'de biter; kalan girdi sonraki çağrıya
+    /// taşınır (`pending_samples`). Paketin önündeki iletilmeyen çerçeveler atlanır
+    /// (`skipped_frames`); tamamen sessiz girdide çıktı boştur ve paket gönderilmemelidir.
+    /// 80 örneğe tamamlanmayan son örnekler de taşınır, akış sonunda `flush` ile kodlanır.
+    /// RTP zaman damgası `skipped_frames` ve çıktıdaki çerçevelere (SID dahil) göre ilerletilmelidir.
     fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
-        let mut output = Vec::with_capacity(pcm_samples.len() / 8);
-        for chunk in pcm_samples.chunks(80) {
-            if chunk.len() != 80 {
+        let mut output = vec![0u8; self.max_encoded_size(pcm_samples.len())];
+        let written = self.encode_into(pcm_samples, &mut output).unwrap_or(0);
+        output.truncate(written);
+        output
+    }
+
+    /// Bekleyen örneklerle birlikte tamamlanan her 80 örnek için en fazla 10 byte
+    /// (SID her zaman bir konuşma çerçevesinin yerini alır).
+    fn max_encoded_size(&self, samples: usize) -> usize {
+        (self.pending.len() + samples) / FRAME_SAMPLES * FRAME_BYTES
+    }
+
+    /// `encode` ile aynı düzen ve aynı ara bellek. Tampon yetmezse hiçbir örnek tüketilmez.
+    fn encode_into(&mut self, pcm_samples: &[i16], out: &mut [u8]) -> Result<usize, CodecError> {
+        let needed = self.max_encoded_size(pcm_samples.len());
+        if out.len() < needed {
+            return Err(CodecError::BufferTooSmall {
+                needed,
+                available: out.len(),
+            });
+        }
+
+        self.pending.extend_from_slice(pcm_samples);
+        let mut written = 0;
+        let mut consumed = 0;
+        while let Some(chunk) = self.pending.get(consumed..consumed + FRAME_SAMPLES) {
+            let mut frame = [0i16; FRAME_SAMPLES];
+            frame.copy_from_slice(chunk);
+            consumed += FRAME_SAMPLES;
+
+            let (bitstream, out_len) = self.encode_frame(&frame);
+            if !matches!(out_len, FRAME_BYTES | SID_BYTES) {
+                // İletilmeyen çerçeve: boşluk kapatılmaz, paket burada biter
+                self.untransmitted += 1;
+                if written > 0 {
+                    break;
+                }
                 continue;
             }
-            let mut bitstream = [0u8; 10];
-            let mut out_len: u8 = 0;
-            unsafe {
-                bcg729Encoder(
-                    self.context,
-                    chunk.as_ptr(),
-                    bitstream.as_mut_ptr(),
-                    &mut out_len,
-                );
+            if written == 0 {
+                self.skipped = std::mem::take(&mut self.untransmitted);
+            }
+            out[written..written + out_len].copy_from_slice(&bitstream[..out_len]);
+            written += out_len;
+            // SID paketin son çerçevesidir; ardından gelen konuşma yeni pakete kalır
+            if out_len == SID_BYTES {
+                break;
             }
-            output.extend_from_slice(&bitstream[..out_len as usize]);
         }
-        output
+        self.pending.drain(..consumed);
+        Ok(written)
+    }
+
+    /// Bekleyen son örnekleri sessizlikle 80'in katına tamamlayıp `encode` ile aynı paket
+    /// düzeninde kodlar. Paket sınırı nedeniyle örnek kalırsa (`pending_samples`) tekrar
+    /// çağrılmalıdır.
+    fn flush(&mut self) -> Vec<u8> {
+        let padded = self.pending.len().next_multiple_of(FRAME_SAMPLES);
+        self.pending.resize(padded, 0);
+        self.encode(&[])
     }
 }
 
 // --- DECODER ---
 pub struct G729Decoder {
     context: *mut c_void,
-}
-
-impl Default for G729Decoder {
-    fn default() -> Self {
-        Self::new()
-    }
+    /// Son alınan çerçeve SID miydi? (DTX dönemi: iletilmeyen çerçeveler CNG ile doldurulur)
+    in_dtx: bool,
+    /// Başlangıçtan beri telafi edilen (kayıp) 10ms çerçeve sayısı.
+    concealed_frames: u64,
+    /// `decode_stream` çağrılarından kalan, 10 byte'a tamamlanmamış bit akışı.
+    pending: [u8; FRAME_BYTES],
+    pending_len: usize,
 }
 
 impl G729Decoder {
-    pub fn new() -> Self {
-        unsafe {
-            let ctx = initBcg729DecoderChannel();
-            if ctx.is_null() {
-                panic!("G.729 Decoder Init Failed");
-            }
-            G729Decoder { context: ctx }
+    /// bcg729 kanalı ayrılamazsa `CodecError::Init` döner.
+    pub fn new() -> Result<Self, CodecError> {
+        let ctx = unsafe { initBcg729DecoderChannel() };
+        if ctx.is_null() {
+            return Err(CodecError::Init {
+                codec: CodecType::G729,
+                code: 0,
+            });
         }
+        Ok(G729Decoder {
+            context: ctx,
+            in_dtx: false,
+            concealed_frames: 0,
+            pending: [0; FRAME_BYTES],
+            pending_len: 0,
+        })
     }
 }
 
@@ -124,31 +262,356 @@ impl Drop for G729Decoder {
 
 unsafe impl Send for G729Decoder {}
 
+impl G729Decoder {
+    /// Tek bir 10ms çerçeveyi `out`'un ilk 80 örneğine çözer.
+    fn decode_frame_into(
+        &mut self,
+        bitstream: Option<&[u8]>,
+        erasure: bool,
+        sid: bool,
+        out: &mut [i16],
+    ) {
+        if out.len() < FRAME_SAMPLES {
+            return;
+        }
+        let (ptr, len) = bitstream.map_or((std::ptr::null(), 0), |b| (b.as_ptr(), b.len() as u8));
+        unsafe {
+            bcg729Decoder(
+                self.context,
+                ptr,
+                len,
+                erasure as u8,
+                sid as u8,
+                0,
+                out.as_mut_ptr(),
+            );
+        }
+    }
+
+    fn decode_frame(
+        &mut self,
+        bitstream: Option<&[u8]>,
+        erasure: bool,
+        sid: bool,
+        output: &mut Vec<i16>,
+    ) {
+        let mut pcm_buf = [0i16; FRAME_SAMPLES];
+        self.decode_frame_into(bitstream, erasure, sid, &mut pcm_buf);
+        output.extend_from_slice(&pcm_buf);
+    }
+
+    /// DTX sırasında paket gelmeyen her 10ms için çağrılır: Son SID parametreleriyle
+    /// konfor gürültüsü (CNG) üretir. SID alınmadıysa (DTX dışında) boş döner.
+    pub fn comfort_noise(&mut self) -> Vec<i16> {
+        let mut output = Vec::new();
+        if self.in_dtx {
+            self.decode_frame(None, false, true, &mut output);
+        }
+        output
+    }
+
+    /// `frames` adet kayıp 10ms çerçeveyi bcg729'un kendi silme (frame erasure) telafisiyle
+    /// üretir: Son çerçevenin LSP/pitch parametreleri tekrarlanıp giderek sönümlenir.
+    /// DTX dönemindeyse kayıp çerçeveler konfor gürültüsüyle doldurulur.
+    /// Kayıptan sonra bekleyen yarım çerçeve yeni veriyle birleştirilemez ve atılır.
+    pub fn conceal_frames(&mut self, frames: usize) -> Vec<i16> {
+        self.pending_len = 0;
+        let mut output = Vec::with_capacity(frames * FRAME_SAMPLES);
+        for _ in 0..frames {
+            if self.in_dtx {
+                self.decode_frame(None, false, true, &mut output);
+            } else {
+                self.decode_frame(None, true, false, &mut output);
+            }
+        }
+        self.concealed_frames += frames as u64;
+        output
+    }
+
+    /// Başlangıçtan beri telafi edilen çerçeve sayısı (kayıp istatistiği).
+    pub fn concealed_frames(&self) -> u64 {
+        self.concealed_frames
+    }
+
+    /// Son çözülen payload bir SID ile bitti mi (karşı taraf sessizlik dönemine girdi mi)?
+    pub fn in_dtx(&self) -> bool {
+        self.in_dtx
+    }
+}
+
 impl Decoder for G729Decoder {
     fn get_type(&self) -> CodecType {
         CodecType::G729
     }
 
+    /// 10 byte'lık konuşma çerçeveleri ve sondaki SID birlikte çözülür; SID konfor gürültüsü üretir.
+    /// Her payload bağımsızdır: Önceki çağrılardan byte taşınmaz, geçersiz artık atlanır.
+    /// Parça parça okunan ham akışlar için `decode_stream` kullanılır.
     fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
-        let mut output = Vec::with_capacity(payload.len() * 8);
-        for chunk in payload.chunks(10) {
-            if chunk.len() < 2 {
-                continue;
-            }
-            let mut pcm_buf = [0i16; 80];
-            unsafe {
-                bcg729Decoder(
-                    self.context,
-                    chunk.as_ptr(),
-                    chunk.len() as u8,
-                    0,
-                    0,
-                    0,
-                    pcm_buf.as_mut_ptr(),
-                );
+        let mut output = vec![0i16; self.decoded_size(payload.len())];
+        let written = self.decode_into(payload, &mut output).unwrap_or(0);
+        output.truncate(written);
+        output
+    }
+
+    /// Her 10 byte'lık çerçeve ve sondaki SID için 80 örnek.
+    fn decoded_size(&self, payload_len: usize) -> usize {
+        let sid = payload_len % FRAME_BYTES == SID_BYTES;
+        (payload_len / FRAME_BYTES + sid as usize) * FRAME_SAMPLES
+    }
+
+    fn decode_into(&mut self, payload: &[u8], out: &mut [i16]) -> Result<usize, CodecError> {
+        let needed = self.decoded_size(payload.len());
+        if out.len() < needed {
+            return Err(CodecError::BufferTooSmall {
+                needed,
+                available: out.len(),
+            });
+        }
+        let (frames, sid) = split_payload(payload);
+        let mut slots = out.chunks_exact_mut(FRAME_SAMPLES);
+        for (frame, slot) in frames.zip(&mut slots) {
+            self.decode_frame_into(Some(frame), false, false, slot);
+            self.in_dtx = false;
+        }
+        if let (Some(sid), Some(slot)) = (sid, slots.next()) {
+            self.decode_frame_into(Some(sid), false, true, slot);
+            self.in_dtx = true;
+        }
+        Ok(needed)
+    }
+
+    /// `decode_stream` ile biriken yarım çerçeve için `flush_stream`.
+    fn flush(&mut self) -> Vec<i16> {
+        self.flush_stream()
+    }
+
+    /// `samples` örneği kapsayan sayıda (yukarı yuvarlanmış) 10ms çerçeve telafi edilir.
+    fn conceal(&mut self, samples: usize) -> Vec<i16> {
+        self.conceal_frames(samples.div_ceil(FRAME_SAMPLES))
+    }
+}
+
+impl G729Decoder {
+    /// RTP dışı ham bit akışını (dosya, soket) rastgele boyutlu parçalarla çözer.
+    /// 10 byte'a tamamlanmayan artık bir sonraki çağrıya taşınır. Akışta çerçeve
+    /// sınırı bilinmediğinden hiçbir byte SID olarak yorumlanmaz.
+    pub fn decode_stream(&mut self, bytes: &[u8]) -> Vec<i16> {
+        let total = self.pending_len + bytes.len();
+        let mut output = Vec::with_capacity(total / FRAME_BYTES * FRAME_SAMPLES);
+        let mut frame = self.pending;
+        let mut filled = self.pending_len;
+        let mut input = bytes;
+        loop {
+            let take = (FRAME_BYTES - filled).min(input.len());
+            frame[filled..filled + take].copy_from_slice(&input[..take]);
+            filled += take;
+            input = &input[take..];
+            if filled < FRAME_BYTES {
+                break;
             }
-            output.extend_from_slice(&pcm_buf);
+            filled = 0;
+            self.decode_frame(Some(&frame), false, false, &mut output);
+            self.in_dtx = false;
         }
+        self.pending = frame;
+        self.pending_len = filled;
         output
     }
+
+    /// `decode_stream` sonunda bekleyen yarım çerçeve çözülemez; yerine bir çerçevelik
+    /// kayıp telafisi üretilir (zaman çizelgesi korunur). Bekleyen byte yoksa boş döner.
+    pub fn flush_stream(&mut self) -> Vec<i16> {
+        if self.pending_len == 0 {
+            return Vec::new();
+        }
+        self.conceal_frames(1)
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_buffers_match_vec_api() {
+        let pcm: Vec<i16> = (0..2 * FRAME_SAMPLES)
+            .map(|i| ((i as f64 * 0.2).sin() * 6000.0) as i16)
+            .collect();
+        let mut out = [0u8; 2 * FRAME_BYTES];
+        let written = G729Encoder::new()
+            .unwrap()
+            .encode_into(&pcm, &mut out)
+            .unwrap();
+        assert_eq!(
+            &out[..written],
+            G729Encoder::new().unwrap().encode(&pcm).as_slice()
+        );
+
+        // 80'in katı olmayan girdinin artığı bir sonraki çağrıya taşınır
+        let mut encoder = G729Encoder::new().unwrap();
+        assert_eq!(encoder.encode_into(&pcm[..100], &mut out), Ok(FRAME_BYTES));
+        assert_eq!(encoder.pending_samples(), 20);
+        assert!(matches!(
+            encoder.encode_into(&pcm, &mut out[..5]),
+            Err(CodecError::BufferTooSmall { needed: 20, .. })
+        ));
+
+        let mut decoder = G729Decoder::new().unwrap();
+        assert_eq!(decoder.decoded_size(22), 3 * FRAME_SAMPLES);
+        let mut pcm_out = [0i16; 2 * FRAME_SAMPLES];
+        assert_eq!(
+            decoder.decode_into(&out, &mut pcm_out),
+            Ok(2 * FRAME_SAMPLES)
+        );
+        assert!(decoder.decode_into(&out, &mut pcm_out[..80]).is_err());
+    }
+
+    #[test]
+    fn test_annexb_fmtp() {
+        assert!(annexb_enabled(None));
+        assert!(annexb_enabled(Some("annexb=yes")));
+        assert!(!annexb_enabled(Some("annexb=no")));
+        assert!(!annexb_enabled(Some("bitrate=8; AnnexB = NO")));
+    }
+
+    #[test]
+    fn test_split_mixed_payload() {
+        let payload = [1u8; 22];
+        let (frames, sid) = split_payload(&payload);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(sid, Some(&payload[20..]));
+
+        let (frames, sid) = split_payload(&payload[..2]);
+        assert_eq!((frames.len(), sid.is_some()), (0, true));
+
+        // 10'un katı olmayan ve SID olmayan artık atlanır
+        let (frames, sid) = split_payload(&payload[..13]);
+        assert_eq!((frames.len(), sid), (1, None));
+    }
+
+    #[test]
+    fn test_conceal_reports_frames() {
+        let mut encoder = G729Encoder::new().unwrap();
+        let mut decoder = G729Decoder::new().unwrap();
+        let pcm: Vec<i16> = (0..160)
+            .map(|i| ((i as f64 * 0.3).sin() * 6000.0) as i16)
+            .collect();
+        decoder.decode(&encoder.encode(&pcm));
+
+        assert_eq!(decoder.conceal_frames(3).len(), 3 * FRAME_SAMPLES);
+        // Trait üzerinden: 20ms (160 örnek) = 2 çerçeve
+        assert_eq!(Decoder::conceal(&mut decoder, 150).len(), 2 * FRAME_SAMPLES);
+        assert_eq!(decoder.concealed_frames(), 5);
+    }
+
+    #[test]
+    fn test_carry_over_keeps_odd_sized_chunks() {
+        let pcm: Vec<i16> = (0..1280)
+            .map(|i| ((i as f64 * 0.25).sin() * 6000.0) as i16)
+            .collect();
+        let aligned = G729Encoder::new().unwrap().encode(&pcm);
+        assert_eq!(aligned.len(), 16 * FRAME_BYTES);
+
+        // TTS gibi 256 örneklik bloklar: 1280 örnek = 16 tam çerçeve, kayıp yok
+        let mut encoder = G729Encoder::new().unwrap();
+        let chunked: Vec<u8> = pcm.chunks(256).flat_map(|c| encoder.encode(c)).collect();
+        assert_eq!(chunked, aligned);
+        assert!(encoder.flush().is_empty());
+
+        // Akış sonundaki yarım çerçeve flush ile sessizlikle tamamlanır
+        let mut encoder = G729Encoder::new().unwrap();
+        assert_eq!(encoder.encode(&pcm[..300]).len(), 3 * FRAME_BYTES);
+        assert_eq!(encoder.pending_samples(), 60);
+        assert_eq!(encoder.flush().len(), FRAME_BYTES);
+        assert_eq!(encoder.pending_samples(), 0);
+
+        // Ham akış: 7 byte'lık parçalar çerçeve sınırlarını aşsa da tüm çerçeveler çözülür
+        let whole = G729Decoder::new().unwrap().decode(&aligned);
+        let mut decoder = G729Decoder::new().unwrap();
+        let pieces: Vec<i16> = aligned
+            .chunks(7)
+            .flat_map(|c| decoder.decode_stream(c))
+            .collect();
+        assert_eq!(pieces, whole);
+
+        // 12 byte'lık parçalar (≡ 2 mod 10) akışta asla SID sayılmaz
+        let mut decoder = G729Decoder::new().unwrap();
+        let pieces: Vec<i16> = aligned
+            .chunks(12)
+            .flat_map(|c| decoder.decode_stream(c))
+            .collect();
+        assert_eq!(pieces.len(), 16 * FRAME_SAMPLES);
+        assert_eq!(pieces, whole);
+        assert!(!decoder.in_dtx());
+
+        decoder.decode_stream(&aligned[..15]);
+        assert_eq!(Decoder::flush(&mut decoder).len(), FRAME_SAMPLES);
+        assert!(Decoder::flush(&mut decoder).is_empty());
+    }
+
+    #[test]
+    fn test_dtx_packets_never_close_gaps() {
+        // 200ms konuşma, 600ms sessizlik, 200ms konuşma; 20ms'lik bloklar
+        let pcm: Vec<i16> = (0..8000)
+            .map(|i| match i {
+                1600..6400 => 0,
+                _ => ((i as f64 * 0.3).sin() * 6000.0) as i16,
+            })
+            .collect();
+        let mut encoder = G729Encoder::with_vad(true).unwrap();
+        let mut packets = Vec::new();
+        let mut timestamp = 0;
+        let mut send = |encoder: &G729Encoder, payload: Vec<u8>| {
+            if payload.is_empty() {
+                return;
+            }
+            timestamp += encoder.skipped_frames() * FRAME_SAMPLES;
+            let (frames, sid) = split_payload(&payload);
+            assert_eq!(
+                frames.len() * FRAME_BYTES + sid.map_or(0, <[u8]>::len),
+                payload.len()
+            );
+            packets.push((timestamp, encoder.skipped_frames(), payload.len()));
+            timestamp += (frames.len() + sid.is_some() as usize) * FRAME_SAMPLES;
+        };
+        for chunk in pcm.chunks(160) {
+            let payload = encoder.encode(chunk);
+            send(&encoder, payload);
+        }
+        while encoder.pending_samples() > 0 {
+            let payload = encoder.flush();
+            send(&encoder, payload);
+        }
+
+        // Son blok konuşma olduğundan, atlanan çerçeveler dahil zaman tam olarak girdiye eşittir
+        assert_eq!(timestamp, pcm.len());
+        assert!(packets.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(packets.iter().any(|&(_, skipped, _)| skipped > 0));
+    }
+
+    #[test]
+    fn test_decode_is_per_packet() {
+        let pcm: Vec<i16> = (0..320)
+            .map(|i| ((i as f64 * 0.25).sin() * 6000.0) as i16)
+            .collect();
+        let good = G729Encoder::with_vad(false).unwrap().encode(&pcm);
+        assert_eq!(good.len(), 4 * FRAME_BYTES);
+
+        // Bozuk 11 byte'lık paket: artık byte atlanır, sonraki paketlere taşınmaz
+        let mut decoder = G729Decoder::new().unwrap();
+        assert_eq!(decoder.decode(&[0x55; 11]).len(), FRAME_SAMPLES);
+        assert!(Decoder::flush(&mut decoder).is_empty());
+        for packet in good.chunks(2 * FRAME_BYTES) {
+            assert_eq!(decoder.decode(packet).len(), 2 * FRAME_SAMPLES);
+            assert!(!decoder.in_dtx());
+        }
+
+        // Hizalı 12 byte'lık payload her zaman konuşma + SID'dir
+        let mut decoder = G729Decoder::new().unwrap();
+        assert_eq!(decoder.decoded_size(12), 2 * FRAME_SAMPLES);
+        assert_eq!(decoder.decode(&good[..12]).len(), 2 * FRAME_SAMPLES);
+        assert!(decoder.in_dtx());
+    }
 }
@@ -0,0 +1,391 @@
+// sentiric-rtp-core/src/codecs/ilbc.rs
+
+//! iLBC (RFC 3951) kodek, RTP paketleme RFC 3952.
+//! `ilbc` cargo özelliği ile derlenir ve sistemdeki libilbc'ye (WebRTC türevi, FFI) bağlanır.
+//! İki çerçeve modu vardır: 20ms (160 örnek, 38 byte) ve 30ms (240 örnek, 50 byte).
+//! Mod SDP'de `a=fmtp:<pt> mode=20|30` ile müzakere edilir; parametre yoksa 30ms'dir.
+
+use super::{copy_into, CodecError, CodecType, Decoder, Encoder};
+use std::ffi::{c_int, c_void};
+
+extern "C" {
+    fn WebRtcIlbcfix_EncoderCreate(inst: *mut *mut c_void) -> i16;
+    fn WebRtcIlbcfix_EncoderFree(inst: *mut c_void) -> i16;
+    fn WebRtcIlbcfix_EncoderInit(inst: *mut c_void, frame_len: i16) -> i16;
+    fn WebRtcIlbcfix_Encode(
+        inst: *mut c_void,
+        speech_in: *const i16,
+        len: usize,
+        encoded: *mut u8,
+    ) -> c_int;
+
+    fn WebRtcIlbcfix_DecoderCreate(inst: *mut *mut c_void) -> i16;
+    fn WebRtcIlbcfix_DecoderFree(inst: *mut c_void) -> i16;
+    fn WebRtcIlbcfix_DecoderInit(inst: *mut c_void, frame_len: i16) -> i16;
+    fn WebRtcIlbcfix_Decode(
+        inst: *mut c_void,
+        encoded: *const u8,
+        len: usize,
+        decoded: *mut i16,
+        speech_type: *mut i16,
+    ) -> c_int;
+    fn WebRtcIlbcfix_DecodePlc(inst: *mut c_void, decoded: *mut i16, frames: usize) -> usize;
+}
+
+/// iLBC çerçeve modu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IlbcMode {
+    /// 20ms, 15.2 kbit/s
+    Ms20,
+    /// 30ms, 13.33 kbit/s (RFC 3952 varsayılanı)
+    #[default]
+    Ms30,
+}
+
+impl IlbcMode {
+    /// SDP `fmtp` satırından mod. RFC 3952: `mode` yoksa veya 20 değilse 30ms kullanılır.
+    pub fn from_fmtp(fmtp: Option<&str>) -> Self {
+        let Some(fmtp) = fmtp else {
+            return IlbcMode::Ms30;
+        };
+        let mode_20 = fmtp.split(';').any(|param| {
+            param
+                .split_once('=')
+                .is_some_and(|(k, v)| k.trim().eq_ignore_ascii_case("mode") && v.trim() == "20")
+        });
+        if mode_20 {
+            IlbcMode::Ms20
+        } else {
+            IlbcMode::Ms30
+        }
+    }
+
+    /// Payload uzunluğundan mod: 38'in katı 20ms, 50'nin katı 30ms (RFC 3952 Bölüm 5).
+    /// 950 gibi her ikisinin katı olan uzunluklarda `None` döner.
+    pub fn from_payload_len(len: usize) -> Option<Self> {
+        if len == 0 {
+            return None;
+        }
+        match (len.is_multiple_of(38), len.is_multiple_of(50)) {
+            (true, false) => Some(IlbcMode::Ms20),
+            (false, true) => Some(IlbcMode::Ms30),
+            _ => None,
+        }
+    }
+
+    pub fn frame_ms(&self) -> u8 {
+        match self {
+            IlbcMode::Ms20 => 20,
+            IlbcMode::Ms30 => 30,
+        }
+    }
+
+    /// Çerçeve başına örnek sayısı (8 kHz).
+    pub fn frame_samples(&self) -> usize {
+        self.frame_ms() as usize * 8
+    }
+
+    /// Çerçeve başına byte.
+    pub fn frame_bytes(&self) -> usize {
+        match self {
+            IlbcMode::Ms20 => 38,
+            IlbcMode::Ms30 => 50,
+        }
+    }
+
+    /// Bu modda `ptime_ms` süreli payload boyutu (yukarı yuvarlanmış çerçeve sayısı).
+    /// Örn. `mode=20`, 60ms = 3 çerçeve = 114 byte.
+    pub fn payload_size_bytes(&self, ptime_ms: u8) -> usize {
+        (ptime_ms as usize).div_ceil(self.frame_ms() as usize) * self.frame_bytes()
+    }
+
+    /// SDP `a=fmtp` değeri.
+    pub fn fmtp(&self) -> &'static str {
+        match self {
+            IlbcMode::Ms20 => "mode=20",
+            IlbcMode::Ms30 => "mode=30",
+        }
+    }
+}
+
+// --- ENCODER ---
+pub struct IlbcEncoder {
+    context: *mut c_void,
+    mode: IlbcMode,
+}
+
+impl IlbcEncoder {
+    /// libilbc örneği ayrılamaz veya başlatılamazsa `CodecError::Init` döner.
+    pub fn new(mode: IlbcMode) -> Result<Self, CodecError> {
+        let init_failed = |code: i16| CodecError::Init {
+            codec: CodecType::ILBC,
+            code: code as i32,
+        };
+        unsafe {
+            let mut ctx: *mut c_void = std::ptr::null_mut();
+            let code = WebRtcIlbcfix_EncoderCreate(&mut ctx);
+            if code != 0 || ctx.is_null() {
+                return Err(init_failed(code));
+            }
+            let code = WebRtcIlbcfix_EncoderInit(ctx, mode.frame_ms() as i16);
+            if code != 0 {
+                WebRtcIlbcfix_EncoderFree(ctx);
+                return Err(init_failed(code));
+            }
+            Ok(IlbcEncoder { context: ctx, mode })
+        }
+    }
+
+    /// SDP `fmtp` satırına göre (`mode=20/30`) yapılandırılmış encoder.
+    pub fn from_fmtp(fmtp: Option<&str>) -> Result<Self, CodecError> {
+        Self::new(IlbcMode::from_fmtp(fmtp))
+    }
+
+    pub fn mode(&self) -> IlbcMode {
+        self.mode
+    }
+}
+
+impl Drop for IlbcEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.context.is_null() {
+                WebRtcIlbcfix_EncoderFree(self.context);
+            }
+        }
+    }
+}
+
+unsafe impl Send for IlbcEncoder {}
+
+impl Encoder for IlbcEncoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::ILBC
+    }
+
+    /// Her tam çerçeve (160 veya 240 örnek) kodlanır; çerçeveye tamamlanmayan son
+    /// örnekler yok sayılır (`encode_into` bu durumda hata döner).
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        let frames = pcm_samples.len() / self.mode.frame_samples();
+        if frames == 0 {
+            return Vec::new();
+        }
+        let mut output = vec![0u8; frames * self.mode.frame_bytes()];
+        let written = unsafe {
+            WebRtcIlbcfix_Encode(
+                self.context,
+                pcm_samples.as_ptr(),
+                frames * self.mode.frame_samples(),
+                output.as_mut_ptr(),
+            )
+        };
+        output.truncate(written.max(0) as usize);
+        output
+    }
+
+    fn max_encoded_size(&self, samples: usize) -> usize {
+        samples / self.mode.frame_samples() * self.mode.frame_bytes()
+    }
+
+    /// Girdi mod çerçevesinin (160 veya 240 örnek) tam katı değilse
+    /// `CodecError::FrameSizeMismatch`; kodlayıcı durumu değişmez.
+    fn encode_into(&mut self, pcm_samples: &[i16], out: &mut [u8]) -> Result<usize, CodecError> {
+        let frame = self.mode.frame_samples();
+        if !pcm_samples.len().is_multiple_of(frame) {
+            return Err(CodecError::FrameSizeMismatch {
+                frame,
+                actual: pcm_samples.len(),
+            });
+        }
+        let needed = self.max_encoded_size(pcm_samples.len());
+        if out.len() < needed {
+            return Err(CodecError::BufferTooSmall {
+                needed,
+                available: out.len(),
+            });
+        }
+        copy_into(&self.encode(pcm_samples), out)
+    }
+}
+
+// --- DECODER ---
+pub struct IlbcDecoder {
+    context: *mut c_void,
+    mode: IlbcMode,
+    concealed_frames: u64,
+}
+
+impl IlbcDecoder {
+    /// libilbc örneği ayrılamaz veya başlatılamazsa `CodecError::Init` döner.
+    pub fn new(mode: IlbcMode) -> Result<Self, CodecError> {
+        let init_failed = |code: i16| CodecError::Init {
+            codec: CodecType::ILBC,
+            code: code as i32,
+        };
+        unsafe {
+            let mut ctx: *mut c_void = std::ptr::null_mut();
+            let code = WebRtcIlbcfix_DecoderCreate(&mut ctx);
+            if code != 0 || ctx.is_null() {
+                return Err(init_failed(code));
+            }
+            let code = WebRtcIlbcfix_DecoderInit(ctx, mode.frame_ms() as i16);
+            if code != 0 {
+                WebRtcIlbcfix_DecoderFree(ctx);
+                return Err(init_failed(code));
+            }
+            Ok(IlbcDecoder {
+                context: ctx,
+                mode,
+                concealed_frames: 0,
+            })
+        }
+    }
+
+    /// SDP `fmtp` satırına göre (`mode=20/30`) yapılandırılmış decoder.
+    pub fn from_fmtp(fmtp: Option<&str>) -> Result<Self, CodecError> {
+        Self::new(IlbcMode::from_fmtp(fmtp))
+    }
+
+    /// Geçerli mod. Karşı taraf farklı modda gönderirse payload boyutundan algılanıp değişir.
+    pub fn mode(&self) -> IlbcMode {
+        self.mode
+    }
+
+    /// iLBC'nin yerleşik PLC'si ile `frames` adet kayıp çerçeve üretilir.
+    pub fn conceal_frames(&mut self, frames: usize) -> Vec<i16> {
+        if frames == 0 {
+            return Vec::new();
+        }
+        let mut output = vec![0i16; frames * self.mode.frame_samples()];
+        let produced =
+            unsafe { WebRtcIlbcfix_DecodePlc(self.context, output.as_mut_ptr(), frames) };
+        output.truncate(produced.min(output.len()));
+        self.concealed_frames += frames as u64;
+        output
+    }
+
+    /// Başlangıçtan beri telafi edilen çerçeve sayısı (kayıp istatistiği).
+    pub fn concealed_frames(&self) -> u64 {
+        self.concealed_frames
+    }
+}
+
+impl Drop for IlbcDecoder {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.context.is_null() {
+                WebRtcIlbcfix_DecoderFree(self.context);
+            }
+        }
+    }
+}
+
+unsafe impl Send for IlbcDecoder {}
+
+impl Decoder for IlbcDecoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::ILBC
+    }
+
+    /// Payload bir veya daha fazla çerçeve taşır. Boyutu mevcut modla uyuşmuyorsa diğer
+    /// moda geçilir (RFC 3952 Bölüm 5); hiçbirine uymayan payload boş çıktı verir.
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        if payload.is_empty() {
+            return Vec::new();
+        }
+        if !payload.len().is_multiple_of(self.mode.frame_bytes()) {
+            let Some(mode) = IlbcMode::from_payload_len(payload.len()) else {
+                return Vec::new();
+            };
+            if unsafe { WebRtcIlbcfix_DecoderInit(self.context, mode.frame_ms() as i16) } != 0 {
+                return Vec::new();
+            }
+            self.mode = mode;
+        }
+
+        let frames = payload.len() / self.mode.frame_bytes();
+        let mut output = vec![0i16; frames * self.mode.frame_samples()];
+        let mut speech_type: i16 = 0;
+        let decoded = unsafe {
+            WebRtcIlbcfix_Decode(
+                self.context,
+                payload.as_ptr(),
+                payload.len(),
+                output.as_mut_ptr(),
+                &mut speech_type,
+            )
+        };
+        if decoded < 0 {
+            return Vec::new();
+        }
+        output.truncate((decoded as usize).min(output.len()));
+        output
+    }
+
+    /// `samples` örneği kapsayan sayıda (yukarı yuvarlanmış) çerçeve telafi edilir.
+    fn conceal(&mut self, samples: usize) -> Vec<i16> {
+        self.conceal_frames(samples.div_ceil(self.mode.frame_samples()))
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(samples: usize) -> Vec<i16> {
+        (0..samples)
+            .map(|i| ((i as f64 * 0.3).sin() * 8000.0) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn test_ilbc_mode_negotiation() {
+        assert_eq!(IlbcMode::from_fmtp(None), IlbcMode::Ms30);
+        assert_eq!(IlbcMode::from_fmtp(Some("mode=20")), IlbcMode::Ms20);
+        assert_eq!(
+            IlbcMode::from_fmtp(Some("ptime=20; Mode = 20")),
+            IlbcMode::Ms20
+        );
+        assert_eq!(IlbcMode::from_fmtp(Some("mode=30")), IlbcMode::Ms30);
+        assert_eq!(IlbcMode::from_payload_len(76), Some(IlbcMode::Ms20));
+        assert_eq!(IlbcMode::from_payload_len(100), Some(IlbcMode::Ms30));
+        assert_eq!(IlbcMode::from_payload_len(37), None);
+
+        assert_eq!(IlbcMode::Ms20.payload_size_bytes(60), 114);
+        assert_eq!(IlbcMode::Ms30.payload_size_bytes(60), 100);
+        assert_eq!(IlbcMode::Ms20.payload_size_bytes(20), 38);
+    }
+
+    #[test]
+    fn test_ilbc_roundtrip_and_plc() {
+        for mode in [IlbcMode::Ms20, IlbcMode::Ms30] {
+            let mut encoder = IlbcEncoder::new(mode).unwrap();
+            let payload = encoder.encode(&tone(2 * mode.frame_samples()));
+            assert_eq!(payload.len(), 2 * mode.frame_bytes());
+            let mut out = [0u8; 100];
+            assert_eq!(
+                encoder.encode_into(&tone(mode.frame_samples() + 1), &mut out),
+                Err(CodecError::FrameSizeMismatch {
+                    frame: mode.frame_samples(),
+                    actual: mode.frame_samples() + 1
+                })
+            );
+
+            // Decoder diğer modda açılsa bile payload boyutundan modu algılar
+            let other = match mode {
+                IlbcMode::Ms20 => IlbcMode::Ms30,
+                IlbcMode::Ms30 => IlbcMode::Ms20,
+            };
+            let mut decoder = IlbcDecoder::new(other).unwrap();
+            assert_eq!(decoder.decode(&payload).len(), 2 * mode.frame_samples());
+            assert_eq!(decoder.mode(), mode);
+
+            assert_eq!(
+                decoder.conceal(mode.frame_samples()).len(),
+                mode.frame_samples()
+            );
+            assert_eq!(decoder.concealed_frames(), 1);
+        }
+    }
+}
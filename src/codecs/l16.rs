@@ -0,0 +1,99 @@
+// sentiric-rtp-core/src/codecs/l16.rs
+
+//! L16 doğrusal PCM (RFC 3551 Bölüm 4.5.11): 16-bit işaretli örnekler, ağ bayt sırası
+//! (big-endian). Çok kanallı akışlarda örnekler iç içe (interleaved) taşınır.
+//! Statik PT 10 (44.1 kHz stereo) ve 11 (44.1 kHz mono); diğer hız/kanal birleşimleri
+//! dinamik PT ile müzakere edilir (`a=rtpmap:<pt> L16/16000`).
+
+use super::{CodecType, Decoder, Encoder};
+
+/// Kayıpsız iç servis trafiği için yaygın dinamik PT'ler (mono).
+pub const L16_16K_PAYLOAD_TYPE: u8 = 116;
+pub const L16_24K_PAYLOAD_TYPE: u8 = 117;
+pub const L16_48K_PAYLOAD_TYPE: u8 = 118;
+
+// --- ENCODER ---
+pub struct L16Encoder {
+    rate: u32,
+    channels: u8,
+}
+
+impl L16Encoder {
+    /// rate: Örnekleme hızı (RTP saati ile aynı). channels: Kanal sayısı (en az 1).
+    pub fn new(rate: u32, channels: u8) -> Self {
+        Self {
+            rate,
+            channels: channels.max(1),
+        }
+    }
+}
+
+impl Encoder for L16Encoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::L16 {
+            rate: self.rate,
+            channels: self.channels,
+        }
+    }
+
+    /// Girdi örnekleri (çok kanallıysa iç içe) big-endian byte'lara çevrilir.
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        pcm_samples.iter().flat_map(|s| s.to_be_bytes()).collect()
+    }
+}
+
+// --- DECODER ---
+pub struct L16Decoder {
+    rate: u32,
+    channels: u8,
+}
+
+impl L16Decoder {
+    pub fn new(rate: u32, channels: u8) -> Self {
+        Self {
+            rate,
+            channels: channels.max(1),
+        }
+    }
+}
+
+impl Decoder for L16Decoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::L16 {
+            rate: self.rate,
+            channels: self.channels,
+        }
+    }
+
+    /// Tek sayıda byte içeren payload'un son byte'ı yok sayılır.
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        payload
+            .chunks_exact(2)
+            .map(|chunk| i16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect()
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l16_network_byte_order_roundtrip() {
+        let pcm = [0x0102i16, -2, i16::MIN, i16::MAX];
+        let payload = L16Encoder::new(16000, 1).encode(&pcm);
+        assert_eq!(payload, [0x01, 0x02, 0xFF, 0xFE, 0x80, 0x00, 0x7F, 0xFF]);
+
+        let mut decoder = L16Decoder::new(16000, 1);
+        assert_eq!(decoder.decode(&payload), pcm);
+        assert_eq!(decoder.decode(&payload[..3]), [0x0102]);
+        assert_eq!(
+            decoder.get_type(),
+            CodecType::L16 {
+                rate: 16000,
+                channels: 1
+            }
+        );
+    }
+}
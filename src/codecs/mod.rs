@@ -1,40 +1,250 @@
 // sentiric-rtp-core/src/codecs/mod.rs
 
+use crate::config::CodecConfig;
+use crate::error::Error;
+use std::fmt;
+
+pub mod clearmode;
+pub mod cn;
 pub mod codec_data;
+pub mod g711_plc;
+pub mod g722;
+pub mod g726;
+#[cfg(feature = "g729")]
 pub mod g729;
+pub mod gsm;
+#[cfg(feature = "ilbc")]
+pub mod ilbc;
+pub mod l16;
+#[cfg(feature = "opus")]
+pub mod opus;
+pub mod payload_map;
 pub mod pcma;
 pub mod pcmu;
+pub mod registry;
 
+pub use clearmode::{ClearmodeDecoder, ClearmodeEncoder};
+pub use cn::{ComfortNoiseDecoder, ComfortNoiseEncoder};
+pub use g722::{G722Decoder, G722Encoder};
+pub use g726::{G726Decoder, G726Encoder, G726Packing, G726Rate};
+#[cfg(feature = "g729")]
 pub use g729::{G729Decoder, G729Encoder};
+pub use gsm::{GsmDecoder, GsmEncoder};
+#[cfg(feature = "ilbc")]
+pub use ilbc::{IlbcDecoder, IlbcEncoder, IlbcMode};
+pub use l16::{L16Decoder, L16Encoder};
+#[cfg(feature = "opus")]
+pub use opus::{OpusDecoder, OpusEncoder};
+pub use payload_map::{PayloadMapping, PayloadTypeMap};
 pub use pcma::{PcmaDecoder, PcmaEncoder};
 pub use pcmu::{PcmuDecoder, PcmuEncoder};
+pub use registry::{CodecDescriptor, CodecRegistry, CustomCodecId};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
 pub enum CodecType {
     G729 = 18,
     PCMU = 0,
     PCMA = 8,
+    /// ITU-T G.722 geniş bant (16 kHz ses, 8 kHz RTP saati)
+    G722 = 9,
+    /// ITU-T G.726 ADPCM 16/24/32/40 kbit/s (dinamik PT; `G726-xx` veya `AAL2-G726-xx`)
+    G726_16 = 112,
+    G726_24 = 113,
+    G726_32 = 114,
+    G726_40 = 115,
+    /// GSM 06.10 Full Rate (RPE-LTP, 13 kbit/s, Payload 3)
+    GSM = 3,
+    /// L16 doğrusal PCM (RFC 3551), ağ bayt sırası. Statik PT 10 (44.1 kHz stereo) ve
+    /// 11 (44.1 kHz mono); diğer hızlar dinamik PT ile (`L16/16000`).
+    L16 {
+        rate: u32,
+        channels: u8,
+    } = 11,
+    /// iLBC (RFC 3951/3952, dinamik PT; `mode=20|30`)
+    #[cfg(feature = "ilbc")]
+    ILBC = 97,
+    /// Opus (RFC 7587, dinamik PT; WebRTC'de yaygın olarak 111)
+    #[cfg(feature = "opus")]
+    Opus = 111,
+    /// RFC 4040 Clearmode: 64 kbit/s şeffaf veri (dinamik PT, `CLEARMODE/8000`)
+    Clearmode = 125,
+    /// RFC 3389 Konfor Gürültüsü (Payload 13)
+    ComfortNoise = 13,
     /// RFC 4733/2833 DTMF Events (Payload 101)
     TelephoneEvent = 101,
+    /// Uygulamanın `CodecRegistry::register` ile eklediği kodek (dinamik PT).
+    /// Ad ve varsayılan PT defterdedir; `rate` hem örnekleme hızı hem RTP saatidir.
+    Custom {
+        id: CustomCodecId,
+        rate: u32,
+        channels: u8,
+    } = 96,
 }
 
 impl CodecType {
+    /// Kodekin işlediği PCM örnekleme hızı.
     pub fn sample_rate(&self) -> u32 {
         match self {
-            CodecType::PCMU | CodecType::PCMA | CodecType::G729 | CodecType::TelephoneEvent => 8000,
+            CodecType::PCMU
+            | CodecType::PCMA
+            | CodecType::G729
+            | CodecType::G726_16
+            | CodecType::G726_24
+            | CodecType::G726_32
+            | CodecType::G726_40
+            | CodecType::GSM
+            | CodecType::Clearmode
+            | CodecType::ComfortNoise
+            | CodecType::TelephoneEvent => 8000,
+            #[cfg(feature = "ilbc")]
+            CodecType::ILBC => 8000,
+            CodecType::G722 => 16000,
+            CodecType::L16 { rate, .. } | CodecType::Custom { rate, .. } => *rate,
+            #[cfg(feature = "opus")]
+            CodecType::Opus => 48000,
+        }
+    }
+
+    /// RTP zaman damgası saati (SDP `a=rtpmap` hızı).
+    /// RFC 3551: G.722, 16 kHz örneklenmesine rağmen 8000 Hz saat kullanır.
+    pub fn rtp_clock_rate(&self) -> u32 {
+        match self {
+            CodecType::G722 => 8000,
+            _ => self.sample_rate(),
+        }
+    }
+
+    /// Belirtilen ptime (ms) için RTP zaman damgası artışı.
+    /// Örn: G.722 20ms = 320 sample ama zaman damgası 160 artar.
+    pub fn rtp_timestamp_increment(&self, ptime_ms: u8) -> u32 {
+        self.rtp_clock_rate() * ptime_ms as u32 / 1000
+    }
+
+    /// Kanal sayısı; L16 ve özel kodekler dışındakiler burada mono kabul edilir.
+    pub fn channels(&self) -> u8 {
+        match self {
+            CodecType::L16 { channels, .. } | CodecType::Custom { channels, .. } => *channels,
+            _ => 1,
         }
     }
 
+    /// Ses değil, oktetleri değiştirmeden taşıyan bir veri kanalı mı (Clearmode)?
+    /// Böyle akışlara HPF, noise gate ve yeniden örnekleme uygulanmamalıdır.
+    pub fn is_transparent(&self) -> bool {
+        matches!(self, CodecType::Clearmode)
+    }
+
+    /// Varsayılan (statik veya bu kütüphanenin önerdiği dinamik) PT'den kodek.
+    /// Müzakere edilmiş PT'ler için `PayloadTypeMap` kullanılmalıdır; karşı taraf
+    /// örneğin telephone-event'i 96 veya 100 ile gönderebilir.
     pub fn from_u8(id: u8) -> Option<Self> {
         match id {
             18 => Some(CodecType::G729),
             0 => Some(CodecType::PCMU),
             8 => Some(CodecType::PCMA),
+            3 => Some(CodecType::GSM),
+            9 => Some(CodecType::G722),
+            13 => Some(CodecType::ComfortNoise),
+            10 => Some(CodecType::L16 {
+                rate: 44100,
+                channels: 2,
+            }),
+            11 => Some(CodecType::L16 {
+                rate: 44100,
+                channels: 1,
+            }),
+            l16::L16_16K_PAYLOAD_TYPE => Some(CodecType::L16 {
+                rate: 16000,
+                channels: 1,
+            }),
+            l16::L16_24K_PAYLOAD_TYPE => Some(CodecType::L16 {
+                rate: 24000,
+                channels: 1,
+            }),
+            l16::L16_48K_PAYLOAD_TYPE => Some(CodecType::L16 {
+                rate: 48000,
+                channels: 1,
+            }),
+            112 => Some(CodecType::G726_16),
+            113 => Some(CodecType::G726_24),
+            114 => Some(CodecType::G726_32),
+            115 => Some(CodecType::G726_40),
+            #[cfg(feature = "ilbc")]
+            97 => Some(CodecType::ILBC),
+            #[cfg(feature = "opus")]
+            111 => Some(CodecType::Opus),
+            clearmode::CLEARMODE_PAYLOAD_TYPE => Some(CodecType::Clearmode),
             101 => Some(CodecType::TelephoneEvent),
             _ => None,
         }
     }
 
+    /// `from_u8`'in tersi: Kodeğin statik veya bu kütüphanenin önerdiği dinamik PT'si.
+    /// L16'da yalnızca bilinen hız/kanal birleşimlerinin kendi PT'si vardır. Uygulama
+    /// kodekleri için 96 döner; kayıtta atanan PT `CodecRegistry::config` ile alınır.
+    pub fn default_payload_type(&self) -> u8 {
+        match *self {
+            CodecType::G729 => 18,
+            CodecType::PCMU => 0,
+            CodecType::PCMA => 8,
+            CodecType::G722 => 9,
+            CodecType::G726_16 => 112,
+            CodecType::G726_24 => 113,
+            CodecType::G726_32 => 114,
+            CodecType::G726_40 => 115,
+            CodecType::GSM => 3,
+            CodecType::L16 { rate, channels } => match (rate, channels) {
+                (44100, 2) => 10,
+                (16000, 1) => l16::L16_16K_PAYLOAD_TYPE,
+                (24000, 1) => l16::L16_24K_PAYLOAD_TYPE,
+                (48000, 1) => l16::L16_48K_PAYLOAD_TYPE,
+                _ => 11,
+            },
+            #[cfg(feature = "ilbc")]
+            CodecType::ILBC => 97,
+            #[cfg(feature = "opus")]
+            CodecType::Opus => 111,
+            CodecType::Clearmode => clearmode::CLEARMODE_PAYLOAD_TYPE,
+            CodecType::ComfortNoise => 13,
+            CodecType::TelephoneEvent => 101,
+            CodecType::Custom { .. } => 96,
+        }
+    }
+
+    /// SDP `a=rtpmap` kodlama adından kodek (büyük/küçük harf duyarsız).
+    /// L16 için hız ve kanal sayısı doğrudan `clock_rate`/`channels`'tan alınır.
+    pub fn from_encoding_name(name: &str, clock_rate: u32, channels: u8) -> Option<Self> {
+        let name = name.trim().to_ascii_uppercase();
+        let bare = match name.strip_prefix("AAL2-") {
+            Some(rest) if rest.starts_with("G726-") => rest,
+            _ => &name,
+        };
+        match bare {
+            "PCMU" => Some(CodecType::PCMU),
+            "PCMA" => Some(CodecType::PCMA),
+            "G729" => Some(CodecType::G729),
+            "G722" => Some(CodecType::G722),
+            "GSM" => Some(CodecType::GSM),
+            "G726-16" => Some(CodecType::G726_16),
+            "G726-24" => Some(CodecType::G726_24),
+            "G726-32" => Some(CodecType::G726_32),
+            "G726-40" => Some(CodecType::G726_40),
+            "L16" => Some(CodecType::L16 {
+                rate: clock_rate,
+                channels: channels.max(1),
+            }),
+            #[cfg(feature = "ilbc")]
+            "ILBC" => Some(CodecType::ILBC),
+            #[cfg(feature = "opus")]
+            "OPUS" => Some(CodecType::Opus),
+            "CLEARMODE" => Some(CodecType::Clearmode),
+            "CN" => Some(CodecType::ComfortNoise),
+            "TELEPHONE-EVENT" => Some(CodecType::TelephoneEvent),
+            _ => None,
+        }
+    }
+
     /// Belirtilen ptime (ms) için örnek (sample) sayısını döndürür.
     /// Örn: 8000Hz * 20ms = 160 sample.
     pub fn samples_per_frame(&self, ptime_ms: u8) -> usize {
@@ -51,20 +261,138 @@ impl CodecType {
             CodecType::G729 => ptime_ms as usize,
             // G.711 (PCMU/PCMA): 1 sample = 1 byte. 20ms = 160 byte.
             CodecType::PCMU | CodecType::PCMA => self.samples_per_frame(ptime_ms),
+            // G.722: 64 kbps, 20ms = 160 byte (16 kHz'de 320 sample).
+            CodecType::G722 => ptime_ms as usize * 8,
+            // G.726: Örnek başına 2-5 bit, 1ms = 8 örnek. 32 kbit/s 20ms = 80 byte.
+            CodecType::G726_16 | CodecType::G726_24 | CodecType::G726_32 | CodecType::G726_40 => {
+                let bits = G726Rate::from_codec_type(*self).map_or(4, |r| r.bits_per_sample());
+                ptime_ms as usize * bits as usize
+            }
+            // L16: Örnek başına 2 byte, her kanal için. 16 kHz mono 20ms = 640 byte.
+            // Özel kodeklerin bit hızı bilinmez; aynı 16-bit doğrusal üst sınır kullanılır.
+            CodecType::L16 { channels, .. } | CodecType::Custom { channels, .. } => {
+                self.samples_per_frame(ptime_ms) * *channels as usize * 2
+            }
+            // GSM: 20ms başına 33 byte'lık sabit çerçeve.
+            CodecType::GSM => (ptime_ms as usize).div_ceil(20) * gsm::GSM_FRAME_BYTES,
+            // iLBC: 30ms modunda 50 byte, 20ms modunda 38 byte'lık çerçeveler. Mod burada
+            // bilinmez, ptime 30'un katıysa 30ms modu varsayılan bir tahmindir; müzakere
+            // edilen mod için `IlbcMode::payload_size_bytes` kullanılmalıdır.
+            #[cfg(feature = "ilbc")]
+            CodecType::ILBC => {
+                if ptime_ms.is_multiple_of(30) {
+                    ptime_ms as usize / 30 * 50
+                } else {
+                    (ptime_ms as usize).div_ceil(20) * 38
+                }
+            }
+            // Opus değişken bit hızlıdır; RFC 6716 üst sınırı (20ms başına 1275 byte) döner.
+            #[cfg(feature = "opus")]
+            CodecType::Opus => (ptime_ms as usize).div_ceil(20) * opus::MAX_OPUS_PACKET,
+            // Clearmode: 64 kbit/s, 1ms = 8 oktet.
+            CodecType::Clearmode => ptime_ms as usize * 8,
+            // CN: 1 byte seviye + en fazla 10 yansıma katsayısı
+            CodecType::ComfortNoise => 1 + cn::CN_MAX_ORDER,
             // DTMF değişkendir, event packet genellikle 4 byte
             CodecType::TelephoneEvent => 4,
         }
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// Çağıranın çıkış tamponu sonucu almaya yetmiyor.
+    BufferTooSmall { needed: usize, available: usize },
+    /// Girdi, kodekin çerçeve boyutunun tam katı değil.
+    FrameSizeMismatch { frame: usize, actual: usize },
+    /// Kodek örneği başlatılamadı (FFI kütüphanesinin dönüş kodu; yoksa 0).
+    Init { codec: CodecType, code: i32 },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::BufferTooSmall { needed, available } => {
+                write!(f, "output buffer too small: {} < {}", available, needed)
+            }
+            CodecError::FrameSizeMismatch { frame, actual } => {
+                write!(
+                    f,
+                    "input length {} is not a multiple of frame size {}",
+                    actual, frame
+                )
+            }
+            CodecError::Init { codec, code } => {
+                write!(f, "{:?} codec initialization failed (code {})", codec, code)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
+
+/// `src`'yi `out` başına kopyalar (varsayılan `*_into` uygulamaları için).
+fn copy_into<T: Copy>(src: &[T], out: &mut [T]) -> Result<usize, CodecError> {
+    let available = out.len();
+    let dst = out.get_mut(..src.len()).ok_or(CodecError::BufferTooSmall {
+        needed: src.len(),
+        available,
+    })?;
+    dst.copy_from_slice(src);
+    Ok(src.len())
+}
+
 pub trait Encoder: Send {
     fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8>;
     fn get_type(&self) -> CodecType;
+
+    /// `samples` örneklik girdi için `encode_into`'nun yazabileceği en fazla byte.
+    /// Varsayılan: 16-bit doğrusal boyut (sıkıştıran kodekler için güvenli üst sınır).
+    fn max_encoded_size(&self, samples: usize) -> usize {
+        samples * 2
+    }
+
+    /// Kodlanan payload'u çağıranın tamponuna yazar ve yazılan byte sayısını döner.
+    /// Varsayılan uygulama `encode` üzerinden kopyalar (ayırma yapar); medya döngüsündeki
+    /// kodekler ayırmasız sürümünü sağlar.
+    fn encode_into(&mut self, pcm_samples: &[i16], out: &mut [u8]) -> Result<usize, CodecError> {
+        copy_into(&self.encode(pcm_samples), out)
+    }
+
+    /// Akış sonunda çağrılır: Ara bellekte bekleyen, çerçeveye tamamlanmamış girdiyi
+    /// kodlayıp döner. Varsayılan: Ara belleği olmayan kodekler için boş.
+    fn flush(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
 }
 
 pub trait Decoder: Send {
     fn decode(&mut self, payload: &[u8]) -> Vec<i16>;
     fn get_type(&self) -> CodecType;
+
+    /// Kayıp paket telafisi (PLC): Kaybolan `samples` örneklik sesi kodekin kendi
+    /// yöntemiyle üretir. Desteklemeyen kodekler boş döner; çağıran sessizlik ekler.
+    fn conceal(&mut self, _samples: usize) -> Vec<i16> {
+        Vec::new()
+    }
+
+    /// `payload_len` byte'lık payload için `decode_into`'nun yazabileceği en fazla örnek.
+    /// Varsayılan: Byte başına 8 örnek (G.729/G.726/GSM/iLBC gibi sabit oranlı kodekler için üst sınır).
+    fn decoded_size(&self, payload_len: usize) -> usize {
+        payload_len * 8
+    }
+
+    /// Çözülen PCM'i çağıranın tamponuna yazar ve yazılan örnek sayısını döner.
+    /// Varsayılan uygulama `decode` üzerinden kopyalar (ayırma yapar).
+    fn decode_into(&mut self, payload: &[u8], out: &mut [i16]) -> Result<usize, CodecError> {
+        copy_into(&self.decode(payload), out)
+    }
+
+    /// Akış sonunda çağrılır: Ara bellekte bekleyen yarım çerçeveden kalan sesi döner.
+    /// Varsayılan: Ara belleği olmayan kodekler için boş.
+    fn flush(&mut self) -> Vec<i16> {
+        Vec::new()
+    }
 }
 
 /// Boş Encoder (DTMF gibi ses olmayan türler için)
@@ -89,25 +417,104 @@ impl Decoder for NoOpDecoder {
     }
 }
 
-pub struct CodecFactory;
+/// Kodekleri bir `CodecRegistry` üzerinden kurar. Uygulamanın kaydettiği kodekler için
+/// `CodecFactory::new(&registry)`; yalnızca yerleşik kodekler gerekiyorsa
+/// `CodecFactory::builtin()` kısayolu kullanılabilir.
+#[derive(Debug, Clone, Copy)]
+pub struct CodecFactory<'a> {
+    registry: &'a CodecRegistry,
+}
 
-impl CodecFactory {
-    pub fn create_encoder(codec: CodecType) -> Box<dyn Encoder> {
-        match codec {
-            CodecType::G729 => Box::new(G729Encoder::new()),
-            CodecType::PCMU => Box::new(pcmu::PcmuEncoder::new()),
-            CodecType::PCMA => Box::new(pcma::PcmaEncoder::new()),
-            CodecType::TelephoneEvent => Box::new(NoOpEncoder {}),
-        }
+impl Default for CodecFactory<'static> {
+    fn default() -> Self {
+        Self::builtin()
     }
+}
 
-    pub fn create_decoder(codec: CodecType) -> Box<dyn Decoder> {
-        match codec {
-            CodecType::G729 => Box::new(G729Decoder::new()),
-            // Decoder'larda dither/state olmadığı için eskisi gibi kalabilir
-            CodecType::PCMU => Box::new(pcmu::PcmuDecoder {}),
-            CodecType::PCMA => Box::new(pcma::PcmaDecoder {}),
-            CodecType::TelephoneEvent => Box::new(NoOpDecoder {}),
-        }
+impl CodecFactory<'static> {
+    /// Süreç genelindeki yerleşik defter (`CodecRegistry::builtin()`) üzerinden kurar.
+    pub fn builtin() -> Self {
+        Self::new(CodecRegistry::builtin())
+    }
+}
+
+impl<'a> CodecFactory<'a> {
+    pub fn new(registry: &'a CodecRegistry) -> Self {
+        Self { registry }
+    }
+
+    pub fn registry(&self) -> &'a CodecRegistry {
+        self.registry
+    }
+
+    /// SDP'de müzakere edilen parametrelerle (fmtp) encoder oluşturur.
+    /// Örn. G.729 `annexb=yes` ise VAD/DTX açılır; Opus `a=fmtp` tercihleri uygulanır.
+    /// G.726 paketleme düzeni kodlama adından (`AAL2-G726-32`), iLBC modu `mode=20|30`'dan seçilir.
+    pub fn create_encoder_for(&self, config: &CodecConfig) -> Result<Box<dyn Encoder>, Error> {
+        self.registry.create_encoder(config)
+    }
+
+    /// Kodeğin kayıtlı varsayılan ayarıyla encoder (örn. G.729 `annexb=no`).
+    /// Kodek başlatılamazsa (örn. FFI kütüphanesi kaynak ayıramazsa) hata döner; süreç çökmez.
+    pub fn create_encoder(&self, codec: CodecType) -> Result<Box<dyn Encoder>, Error> {
+        let config = self.default_config(codec, codec.default_payload_type())?;
+        self.registry.create_encoder(&config)
+    }
+
+    /// SDP'de müzakere edilen kodlama adı ve fmtp ile decoder oluşturur
+    /// (G.726 AAL2 paketleme, iLBC modu gibi).
+    pub fn create_decoder_for(&self, config: &CodecConfig) -> Result<Box<dyn Decoder>, Error> {
+        self.registry.create_decoder(config)
+    }
+
+    /// Müzakere edilmiş PT haritası üzerinden encoder oluşturur; PT bilinmiyorsa
+    /// `Error::UnsupportedPayload`. G.726 paketleme düzeni ve `a=fmtp` haritadan alınır.
+    pub fn create_encoder_for_payload(
+        &self,
+        map: &PayloadTypeMap,
+        payload_type: u8,
+    ) -> Result<Box<dyn Encoder>, Error> {
+        let config = self.config_for_payload(map, payload_type)?;
+        self.registry.create_encoder(&config)
+    }
+
+    /// Gelen paketin PT'sini haritadan çözerek decoder oluşturur; PT bilinmiyorsa
+    /// `Error::UnsupportedPayload`. G.726 paketleme düzeni ve `a=fmtp` haritadan alınır.
+    pub fn create_decoder_for_payload(
+        &self,
+        map: &PayloadTypeMap,
+        payload_type: u8,
+    ) -> Result<Box<dyn Decoder>, Error> {
+        let config = self.config_for_payload(map, payload_type)?;
+        self.registry.create_decoder(&config)
+    }
+
+    /// Haritadaki eşlemeye uyarlanmış varsayılan kodek ayarı.
+    fn config_for_payload(
+        &self,
+        map: &PayloadTypeMap,
+        payload_type: u8,
+    ) -> Result<CodecConfig, Error> {
+        let mapping = map
+            .get(payload_type)
+            .ok_or(Error::UnsupportedPayload(payload_type))?;
+        let mut config = self.default_config(mapping.codec, payload_type)?;
+        mapping.apply_to(&mut config);
+        Ok(config)
+    }
+
+    /// Defterdeki varsayılan ayar. Kayıtlı olmayan uygulama kodeğinde
+    /// `Error::UnsupportedEncoding(ad)`, diğer durumlarda `Error::UnsupportedPayload`.
+    fn default_config(&self, codec: CodecType, payload_type: u8) -> Result<CodecConfig, Error> {
+        self.registry.config(codec).ok_or_else(|| match codec {
+            CodecType::Custom { id, .. } => Error::UnsupportedEncoding(id.to_string()),
+            _ => Error::UnsupportedPayload(payload_type),
+        })
+    }
+
+    /// Kodek başlatılamazsa (örn. FFI kütüphanesi kaynak ayıramazsa) hata döner; süreç çökmez.
+    pub fn create_decoder(&self, codec: CodecType) -> Result<Box<dyn Decoder>, Error> {
+        let config = self.default_config(codec, codec.default_payload_type())?;
+        self.registry.create_decoder(&config)
     }
 }
@@ -0,0 +1,327 @@
+// sentiric-rtp-core/src/codecs/opus.rs
+
+//! Opus (RFC 6716) kodek, RTP paketleme RFC 7587.
+//! `opus` cargo özelliği ile derlenir ve sistemdeki libopus'a (FFI) bağlanır.
+//! RTP saati her zaman 48 kHz'dir; her RTP paketi tam olarak bir Opus paketi taşır.
+
+use super::{CodecError, CodecType, Decoder, Encoder};
+use crate::config::OpusFmtp;
+use std::ffi::{c_int, c_void};
+
+extern "C" {
+    fn opus_encoder_create(
+        fs: i32,
+        channels: c_int,
+        application: c_int,
+        error: *mut c_int,
+    ) -> *mut c_void;
+    fn opus_encoder_destroy(st: *mut c_void);
+    fn opus_encode(
+        st: *mut c_void,
+        pcm: *const i16,
+        frame_size: c_int,
+        data: *mut u8,
+        max_data_bytes: i32,
+    ) -> i32;
+    fn opus_encoder_ctl(st: *mut c_void, request: c_int, ...) -> c_int;
+
+    fn opus_decoder_create(fs: i32, channels: c_int, error: *mut c_int) -> *mut c_void;
+    fn opus_decoder_destroy(st: *mut c_void);
+    fn opus_decode(
+        st: *mut c_void,
+        data: *const u8,
+        len: i32,
+        pcm: *mut i16,
+        frame_size: c_int,
+        decode_fec: c_int,
+    ) -> c_int;
+}
+
+/// RFC 7587: RTP zaman damgası saati.
+pub const OPUS_CLOCK_RATE: u32 = 48000;
+/// RFC 6716: Tek bir Opus çerçevesinin en büyük boyutu (byte).
+pub const MAX_OPUS_PACKET: usize = 1275;
+/// 120ms @ 48kHz: Bir paketin taşıyabileceği en uzun süre (kanal başına örnek).
+const MAX_FRAME_SAMPLES: usize = 5760;
+
+const OPUS_APPLICATION_VOIP: c_int = 2048;
+const OPUS_SET_BITRATE_REQUEST: c_int = 4002;
+const OPUS_SET_MAX_BANDWIDTH_REQUEST: c_int = 4004;
+const OPUS_SET_INBAND_FEC_REQUEST: c_int = 4012;
+const OPUS_SET_PACKET_LOSS_PERC_REQUEST: c_int = 4014;
+const OPUS_SET_DTX_REQUEST: c_int = 4016;
+const OPUS_BANDWIDTH_NARROWBAND: c_int = 1101;
+const OPUS_BANDWIDTH_MEDIUMBAND: c_int = 1102;
+const OPUS_BANDWIDTH_WIDEBAND: c_int = 1103;
+const OPUS_BANDWIDTH_SUPERWIDEBAND: c_int = 1104;
+const OPUS_BANDWIDTH_FULLBAND: c_int = 1105;
+
+/// FEC açıkken encoder'a bildirilen beklenen paket kaybı oranı (%).
+const FEC_EXPECTED_LOSS_PERC: c_int = 10;
+
+/// `maxplaybackrate` değerini encoder bant genişliği sınırına çevirir.
+fn max_bandwidth(maxplaybackrate: u32) -> c_int {
+    match maxplaybackrate {
+        0..=8000 => OPUS_BANDWIDTH_NARROWBAND,
+        8001..=12000 => OPUS_BANDWIDTH_MEDIUMBAND,
+        12001..=16000 => OPUS_BANDWIDTH_WIDEBAND,
+        16001..=24000 => OPUS_BANDWIDTH_SUPERWIDEBAND,
+        _ => OPUS_BANDWIDTH_FULLBAND,
+    }
+}
+
+/// Kanal başına örnek sayısı geçerli bir Opus çerçeve süresi mi (2.5/5/10/20/40/60 ms)?
+fn is_valid_frame_size(samples_per_channel: usize) -> bool {
+    matches!(samples_per_channel, 120 | 240 | 480 | 960 | 1920 | 2880)
+}
+
+// --- ENCODER ---
+pub struct OpusEncoder {
+    context: *mut c_void,
+    channels: usize,
+}
+
+impl OpusEncoder {
+    /// channels: 1 (mono) veya 2 (stereo, örnekler iç içe/interleaved).
+    /// fmtp: Karşı tarafın ilan ettiği alıcı tercihleri (FEC, DTX, bit hızı, bant genişliği).
+    /// libopus başlatılamazsa hata kodu ile `CodecError::Init` döner.
+    pub fn new(channels: u8, fmtp: &OpusFmtp) -> Result<Self, CodecError> {
+        let channels = channels.clamp(1, 2) as usize;
+        unsafe {
+            let mut error: c_int = 0;
+            let ctx = opus_encoder_create(
+                OPUS_CLOCK_RATE as i32,
+                channels as c_int,
+                OPUS_APPLICATION_VOIP,
+                &mut error,
+            );
+            if ctx.is_null() || error != 0 {
+                if !ctx.is_null() {
+                    opus_encoder_destroy(ctx);
+                }
+                return Err(CodecError::Init {
+                    codec: CodecType::Opus,
+                    code: error,
+                });
+            }
+            opus_encoder_ctl(ctx, OPUS_SET_INBAND_FEC_REQUEST, fmtp.useinbandfec as c_int);
+            if fmtp.useinbandfec {
+                opus_encoder_ctl(
+                    ctx,
+                    OPUS_SET_PACKET_LOSS_PERC_REQUEST,
+                    FEC_EXPECTED_LOSS_PERC,
+                );
+            }
+            opus_encoder_ctl(ctx, OPUS_SET_DTX_REQUEST, fmtp.usedtx as c_int);
+            if let Some(bitrate) = fmtp.maxaveragebitrate {
+                opus_encoder_ctl(ctx, OPUS_SET_BITRATE_REQUEST, bitrate as c_int);
+            }
+            if let Some(rate) = fmtp.maxplaybackrate {
+                opus_encoder_ctl(ctx, OPUS_SET_MAX_BANDWIDTH_REQUEST, max_bandwidth(rate));
+            }
+            Ok(OpusEncoder {
+                context: ctx,
+                channels,
+            })
+        }
+    }
+
+    pub fn channels(&self) -> u8 {
+        self.channels as u8
+    }
+}
+
+impl Drop for OpusEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.context.is_null() {
+                opus_encoder_destroy(self.context);
+            }
+        }
+    }
+}
+
+unsafe impl Send for OpusEncoder {}
+
+impl Encoder for OpusEncoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::Opus
+    }
+
+    /// Girdinin tamamı tek bir Opus paketi olarak kodlanır (bir RTP payload'u).
+    /// Geçerli bir çerçeve süresine (örn. 20ms = kanal başına 960 örnek) denk gelmeyen
+    /// girdi için boş çıktı döner. DTX etkinse sessizlikte 1-2 byte'lık paket üretilebilir.
+    fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+        if !pcm_samples.len().is_multiple_of(self.channels)
+            || !is_valid_frame_size(pcm_samples.len() / self.channels)
+        {
+            return Vec::new();
+        }
+        let mut output = vec![0u8; MAX_OPUS_PACKET * 3];
+        let written = unsafe {
+            opus_encode(
+                self.context,
+                pcm_samples.as_ptr(),
+                (pcm_samples.len() / self.channels) as c_int,
+                output.as_mut_ptr(),
+                output.len() as i32,
+            )
+        };
+        output.truncate(written.max(0) as usize);
+        output
+    }
+
+    fn max_encoded_size(&self, _samples: usize) -> usize {
+        MAX_OPUS_PACKET * 3
+    }
+}
+
+// --- DECODER ---
+pub struct OpusDecoder {
+    context: *mut c_void,
+    channels: usize,
+    /// Son çözülen paketin süresi (kanal başına örnek); kayıp telafisinde kullanılır.
+    last_frame_size: usize,
+}
+
+impl OpusDecoder {
+    /// channels: Çıktı kanal sayısı. Opus, mono/stereo akışı her iki çıktıya da çözebilir.
+    pub fn new(channels: u8) -> Result<Self, CodecError> {
+        let channels = channels.clamp(1, 2) as usize;
+        unsafe {
+            let mut error: c_int = 0;
+            let ctx = opus_decoder_create(OPUS_CLOCK_RATE as i32, channels as c_int, &mut error);
+            if ctx.is_null() || error != 0 {
+                if !ctx.is_null() {
+                    opus_decoder_destroy(ctx);
+                }
+                return Err(CodecError::Init {
+                    codec: CodecType::Opus,
+                    code: error,
+                });
+            }
+            Ok(OpusDecoder {
+                context: ctx,
+                channels,
+                last_frame_size: 960,
+            })
+        }
+    }
+
+    pub fn channels(&self) -> u8 {
+        self.channels as u8
+    }
+
+    fn decode_raw(&mut self, data: Option<&[u8]>, frame_size: usize, fec: bool) -> Vec<i16> {
+        let mut pcm = vec![0i16; frame_size * self.channels];
+        let (ptr, len) = data.map_or((std::ptr::null(), 0), |d| (d.as_ptr(), d.len() as i32));
+        let decoded = unsafe {
+            opus_decode(
+                self.context,
+                ptr,
+                len,
+                pcm.as_mut_ptr(),
+                frame_size as c_int,
+                fec as c_int,
+            )
+        };
+        if decoded < 0 {
+            return Vec::new();
+        }
+        pcm.truncate(decoded as usize * self.channels);
+        pcm
+    }
+
+    /// Kayıp paket telafisi (PLC): Son paket süresi kadar tahmini ses üretir.
+    pub fn conceal(&mut self) -> Vec<i16> {
+        self.decode_raw(None, self.last_frame_size, false)
+    }
+
+    /// `payload`'dan önceki paket kaybolduysa (`previous_lost`), önce kayıp çerçeve
+    /// `payload` içindeki in-band FEC verisinden kurtarılır, ardından `payload` normal çözülür.
+    /// Dönen örnekler: [kurtarılan önceki çerçeve] + [mevcut çerçeve].
+    /// Paket FEC taşımıyorsa kurtarma adımı PLC'ye düşer.
+    pub fn decode_with_fec(&mut self, payload: &[u8], previous_lost: bool) -> Vec<i16> {
+        let mut output = Vec::new();
+        if previous_lost && !payload.is_empty() {
+            output = self.decode_raw(Some(payload), self.last_frame_size, true);
+        }
+        output.extend(self.decode(payload));
+        output
+    }
+}
+
+impl Drop for OpusDecoder {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.context.is_null() {
+                opus_decoder_destroy(self.context);
+            }
+        }
+    }
+}
+
+unsafe impl Send for OpusDecoder {}
+
+impl Decoder for OpusDecoder {
+    fn get_type(&self) -> CodecType {
+        CodecType::Opus
+    }
+
+    fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+        if payload.is_empty() {
+            return Vec::new();
+        }
+        let pcm = self.decode_raw(Some(payload), MAX_FRAME_SAMPLES, false);
+        if !pcm.is_empty() {
+            self.last_frame_size = pcm.len() / self.channels;
+        }
+        pcm
+    }
+
+    /// Bir paket en fazla 120ms taşır.
+    fn decoded_size(&self, _payload_len: usize) -> usize {
+        MAX_FRAME_SAMPLES * self.channels
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(samples: usize, channels: usize) -> Vec<i16> {
+        (0..samples * channels)
+            .map(|i| (((i / channels) as f64 * 0.05).sin() * 8000.0) as i16)
+            .collect()
+    }
+
+    #[test]
+    fn test_opus_roundtrip_mono_and_stereo() {
+        for channels in [1u8, 2] {
+            let mut encoder = OpusEncoder::new(channels, &OpusFmtp::default()).unwrap();
+            let mut decoder = OpusDecoder::new(channels).unwrap();
+            let packet = encoder.encode(&tone(960, channels as usize));
+            assert!(!packet.is_empty() && packet.len() <= MAX_OPUS_PACKET);
+            assert_eq!(decoder.decode(&packet).len(), 960 * channels as usize);
+        }
+        // 15ms geçerli bir Opus çerçevesi değildir
+        assert!(OpusEncoder::new(1, &OpusFmtp::default())
+            .unwrap()
+            .encode(&tone(720, 1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_opus_fec_recovers_previous_frame() {
+        let mut encoder = OpusEncoder::new(1, &OpusFmtp::default()).unwrap();
+        let mut decoder = OpusDecoder::new(1).unwrap();
+        let packets: Vec<Vec<u8>> = (0..5).map(|_| encoder.encode(&tone(960, 1))).collect();
+        decoder.decode(&packets[0]);
+        decoder.decode(&packets[1]);
+        // packets[2] kayboldu; packets[3] ile birlikte kurtarılır
+        let pcm = decoder.decode_with_fec(&packets[3], true);
+        assert_eq!(pcm.len(), 2 * 960);
+        assert_eq!(decoder.conceal().len(), 960);
+    }
+}
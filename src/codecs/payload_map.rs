@@ -0,0 +1,270 @@
+// sentiric-rtp-core/src/codecs/payload_map.rs
+
+//! Oturum başına dinamik payload type (PT) eşlemesi.
+//! `CodecType` ayırt edicileri yalnızca varsayılan PT'lerdir; karşı taraf aynı kodeği başka
+//! bir PT ile müzakere edebilir (örn. telephone-event için 96 veya 100). Bu harita SDP'den
+//! (`a=rtpmap`) veya `AudioProfile`'dan kurulur ve gelen/giden paketlerde PT <-> kodek
+//! dönüşümü yapar. Sabit boyutludur (128 PT); yalnızca SDP çözümlenirken (`a=fmtp`) heap
+//! ayırır, medya döngüsündeki sorgular ayırmaz.
+
+use super::{CodecRegistry, CodecType, G726Packing, G726Rate};
+use crate::config::{CodecConfig, OpusFmtp};
+use crate::error::Error;
+
+/// Bir PT'nin müzakere edilmiş karşılığı.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadMapping {
+    pub payload_type: u8,
+    pub codec: CodecType,
+    /// RTP zaman damgası saati (`a=rtpmap` hızı).
+    pub clock_rate: u32,
+    pub channels: u8,
+    /// G.726 kod kelimesi sıralaması (`AAL2-G726-xx` müzakere edildiyse `Aal2`).
+    /// Diğer kodeklerde varsayılan değerdedir.
+    pub packing: G726Packing,
+    /// Karşı tarafın `a=fmtp` parametreleri (örn. `annexb=no`, `mode=20`).
+    pub fmtp: Option<String>,
+}
+
+impl PayloadMapping {
+    /// Profildeki/defterdeki kodek ayarını bu eşlemeye uyarlar: PT müzakere edilen değer
+    /// olur, G.726 için kodlama adı müzakere edilen paketleme düzenini yansıtır.
+    /// Müzakere edilmiş `a=fmtp` varsa ayarın varsayılan parametrelerinin yerini alır.
+    pub fn apply_to(&self, config: &mut CodecConfig) {
+        config.payload_type = self.payload_type;
+        if let Some(rate) = G726Rate::from_codec_type(self.codec) {
+            config.name = self.packing.encoding_name(rate).into();
+        }
+        if let Some(fmtp) = &self.fmtp {
+            if config.opus_fmtp.is_some() {
+                config.opus_fmtp = Some(OpusFmtp::parse(fmtp));
+            }
+            config.fmtp = Some(fmtp.clone());
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PayloadTypeMap {
+    entries: [Option<PayloadMapping>; 128],
+}
+
+impl Default for PayloadTypeMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PayloadTypeMap {
+    /// Boş harita.
+    pub fn new() -> Self {
+        Self {
+            entries: std::array::from_fn(|_| None),
+        }
+    }
+
+    /// RFC 3551 statik PT'leri ile önceden doldurulmuş harita.
+    pub fn with_static() -> Self {
+        let mut map = Self::new();
+        for pt in [0u8, 3, 8, 9, 10, 11, 13, 18] {
+            if let Some(codec) = CodecType::from_u8(pt) {
+                map.insert(pt, codec, codec.rtp_clock_rate(), codec.channels());
+            }
+        }
+        map
+    }
+
+    /// PT'yi kodeğe bağlar; varsa önceki eşlemeyi değiştirir. 127'den büyük PT yok sayılır.
+    pub fn insert(&mut self, payload_type: u8, codec: CodecType, clock_rate: u32, channels: u8) {
+        self.insert_mapping(PayloadMapping {
+            payload_type,
+            codec,
+            clock_rate,
+            channels,
+            packing: G726Packing::default(),
+            fmtp: None,
+        });
+    }
+
+    /// Hazır eşlemeyi ekler (örn. G.726 AAL2 paketlemesiyle); 127'den büyük PT yok sayılır.
+    pub fn insert_mapping(&mut self, mapping: PayloadMapping) {
+        if let Some(slot) = self.entries.get_mut(mapping.payload_type as usize) {
+            *slot = Some(PayloadMapping {
+                channels: mapping.channels.max(1),
+                ..mapping
+            });
+        }
+    }
+
+    /// `a=rtpmap:<pt> <ad>/<saat>[/<kanal>]` satırını (veya `a=rtpmap:` önekisiz halini)
+    /// çözer ve haritaya ekler. Bozuk satırda `Error::Parse`, tanınmayan kodlama adında
+    /// `Error::UnsupportedEncoding` döner; her iki durumda harita değişmez.
+    /// Aynı PT için daha önce eklenmiş `a=fmtp` parametreleri korunur.
+    /// Yalnızca yerleşik kodekler tanınır; uygulama kodekleri için `insert_rtpmap_with`.
+    pub fn insert_rtpmap(&mut self, line: &str) -> Result<CodecType, Error> {
+        self.insert_rtpmap_with(CodecRegistry::builtin(), line)
+    }
+
+    /// `insert_rtpmap` gibi; kodlama adı `registry` üzerinden çözülür.
+    pub fn insert_rtpmap_with(
+        &mut self,
+        registry: &CodecRegistry,
+        line: &str,
+    ) -> Result<CodecType, Error> {
+        let parse_error = || Error::Parse(line.to_string());
+        let trimmed = line.trim();
+        let trimmed = trimmed.strip_prefix("a=rtpmap:").unwrap_or(trimmed);
+        let (pt, encoding) = trimmed
+            .split_once(char::is_whitespace)
+            .ok_or_else(parse_error)?;
+        let payload_type: u8 = pt
+            .trim()
+            .parse()
+            .ok()
+            .filter(|pt| *pt < 128)
+            .ok_or_else(parse_error)?;
+
+        let mut parts = encoding.trim().split('/');
+        let name = parts.next().unwrap_or_default();
+        let clock_rate: u32 = parts
+            .next()
+            .and_then(|rate| rate.trim().parse().ok())
+            .ok_or_else(parse_error)?;
+        let channels: u8 = match parts.next() {
+            Some(ch) => ch.trim().parse().map_err(|_| parse_error())?,
+            None => 1,
+        };
+
+        let codec = registry
+            .resolve(name, clock_rate, channels)
+            .ok_or_else(|| Error::UnsupportedEncoding(name.to_string()))?;
+        let fmtp = self.get(payload_type).and_then(|m| m.fmtp.clone());
+        self.insert_mapping(PayloadMapping {
+            payload_type,
+            codec,
+            clock_rate,
+            channels,
+            packing: G726Packing::from_encoding_name(name),
+            fmtp,
+        });
+        Ok(codec)
+    }
+
+    /// `a=fmtp:<pt> <parametreler>` satırını (veya `a=fmtp:` önekisiz halini) eşlenmiş PT'ye
+    /// bağlar. Bozuk satırda `Error::Parse`, eşlenmemiş PT'de `Error::UnsupportedPayload`
+    /// döner (RFC 4566: bilinmeyen PT'nin fmtp'si yok sayılabilir); harita değişmez.
+    pub fn insert_fmtp(&mut self, line: &str) -> Result<(), Error> {
+        let parse_error = || Error::Parse(line.to_string());
+        let trimmed = line.trim();
+        let trimmed = trimmed.strip_prefix("a=fmtp:").unwrap_or(trimmed);
+        let (pt, params) = trimmed
+            .split_once(char::is_whitespace)
+            .ok_or_else(parse_error)?;
+        let payload_type: u8 = pt
+            .trim()
+            .parse()
+            .ok()
+            .filter(|pt| *pt < 128)
+            .ok_or_else(parse_error)?;
+        let mapping = self.entries[payload_type as usize]
+            .as_mut()
+            .ok_or(Error::UnsupportedPayload(payload_type))?;
+        mapping.fmtp = Some(params.trim().to_string());
+        Ok(())
+    }
+
+    pub fn remove(&mut self, payload_type: u8) -> Option<PayloadMapping> {
+        self.entries.get_mut(payload_type as usize)?.take()
+    }
+
+    /// Gelen paketin PT'sine karşılık gelen eşleme.
+    pub fn get(&self, payload_type: u8) -> Option<&PayloadMapping> {
+        self.entries.get(payload_type as usize)?.as_ref()
+    }
+
+    /// Gelen paketin PT'sine karşılık gelen kodek.
+    pub fn codec(&self, payload_type: u8) -> Option<CodecType> {
+        self.get(payload_type).map(|m| m.codec)
+    }
+
+    /// Giden paket için kodeğin müzakere edilmiş PT'si (birden fazlaysa en küçüğü).
+    pub fn payload_type(&self, codec: CodecType) -> Option<u8> {
+        self.iter()
+            .find(|m| m.codec == codec)
+            .map(|m| m.payload_type)
+    }
+
+    /// PT sırasıyla tüm eşlemeler.
+    pub fn iter(&self) -> impl Iterator<Item = &PayloadMapping> {
+        self.entries.iter().flatten()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_map_dynamic_telephone_event() {
+        let mut map = PayloadTypeMap::with_static();
+        assert_eq!(map.codec(0), Some(CodecType::PCMU));
+        assert_eq!(map.codec(101), None);
+
+        assert_eq!(
+            map.insert_rtpmap("a=rtpmap:96 telephone-event/8000"),
+            Ok(CodecType::TelephoneEvent)
+        );
+        assert_eq!(map.codec(96), Some(CodecType::TelephoneEvent));
+        assert_eq!(map.payload_type(CodecType::TelephoneEvent), Some(96));
+
+        // Kanal ve saat bilgisi korunur
+        map.insert_rtpmap("100 L16/48000/2").unwrap();
+        let l16 = map.get(100).unwrap();
+        assert_eq!(
+            l16.codec,
+            CodecType::L16 {
+                rate: 48000,
+                channels: 2
+            }
+        );
+        assert_eq!((l16.clock_rate, l16.channels), (48000, 2));
+
+        // Tanınmayan veya bozuk satırlar haritayı değiştirmez
+        assert_eq!(
+            map.insert_rtpmap("a=rtpmap:97 H264/90000"),
+            Err(Error::UnsupportedEncoding("H264".to_string()))
+        );
+        assert_eq!(
+            map.insert_rtpmap("a=rtpmap:200 PCMU/8000"),
+            Err(Error::Parse("a=rtpmap:200 PCMU/8000".to_string()))
+        );
+        assert_eq!(map.codec(97), None);
+
+        // G.726 paketleme düzeni kodlama adından korunur
+        map.insert_rtpmap("a=rtpmap:98 AAL2-G726-32/8000").unwrap();
+        map.insert_rtpmap("a=rtpmap:99 G726-32/8000").unwrap();
+        assert_eq!(map.get(98).unwrap().packing, G726Packing::Aal2);
+        assert_eq!(map.get(99).unwrap().packing, G726Packing::Rfc3551);
+        assert_eq!(map.codec(98), map.codec(99));
+
+        // fmtp eşlenmiş PT'ye bağlanır, rtpmap yeniden eklendiğinde korunur
+        assert_eq!(map.insert_fmtp("a=fmtp:96 0-15"), Ok(()));
+        map.insert_rtpmap("a=rtpmap:96 telephone-event/8000")
+            .unwrap();
+        assert_eq!(map.get(96).unwrap().fmtp.as_deref(), Some("0-15"));
+        assert_eq!(
+            map.insert_fmtp("a=fmtp:120 mode=20"),
+            Err(Error::UnsupportedPayload(120))
+        );
+        assert!(map.insert_fmtp("a=fmtp:96").is_err());
+
+        assert!(map.remove(96).is_some());
+        assert_eq!(map.payload_type(CodecType::TelephoneEvent), None);
+        assert!(!map.is_empty());
+    }
+}
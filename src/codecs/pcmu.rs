@@ -1,10 +1,14 @@
 // sentiric-rtp-core/src/codecs/pcmu.rs
 
 use super::codec_data::ULAW_TO_LINEAR_LUT;
-use super::{CodecType, Decoder, Encoder};
+use super::g711_plc::G711Plc;
+use super::{CodecError, CodecType, Decoder, Encoder};
 
 pub struct PcmuEncoder;
-pub struct PcmuDecoder;
+pub struct PcmuDecoder {
+    /// G.711 Appendix I kayıp telafisi (geçmiş tampon ve sentez durumu).
+    plc: G711Plc,
+}
 
 impl Default for PcmuEncoder {
     fn default() -> Self {
@@ -60,28 +64,89 @@ impl PcmuEncoder {
     }
 }
 
-unsafe impl Send for PcmuEncoder {}
 impl Encoder for PcmuEncoder {
     fn get_type(&self) -> CodecType {
         CodecType::PCMU
     }
     fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
-        pcm_samples
-            .iter()
-            .map(|&s| self.linear_to_ulaw(s))
-            .collect()
+        let mut output = vec![0u8; pcm_samples.len()];
+        let written = self.encode_into(pcm_samples, &mut output).unwrap_or(0);
+        output.truncate(written);
+        output
+    }
+
+    /// 1 örnek = 1 byte.
+    fn max_encoded_size(&self, samples: usize) -> usize {
+        samples
+    }
+
+    fn encode_into(&mut self, pcm_samples: &[i16], out: &mut [u8]) -> Result<usize, CodecError> {
+        let available = out.len();
+        let out = out
+            .get_mut(..pcm_samples.len())
+            .ok_or(CodecError::BufferTooSmall {
+                needed: pcm_samples.len(),
+                available,
+            })?;
+        for (dst, &s) in out.iter_mut().zip(pcm_samples) {
+            *dst = self.linear_to_ulaw(s);
+        }
+        Ok(pcm_samples.len())
+    }
+}
+
+impl Default for PcmuDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PcmuDecoder {
+    pub fn new() -> Self {
+        Self {
+            plc: G711Plc::new(),
+        }
+    }
+
+    /// Başlangıçtan beri PLC ile üretilen örnek sayısı.
+    pub fn concealed_samples(&self) -> u64 {
+        self.plc.concealed_samples()
     }
 }
 
-unsafe impl Send for PcmuDecoder {}
 impl Decoder for PcmuDecoder {
     fn get_type(&self) -> CodecType {
         CodecType::PCMU
     }
     fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
-        payload
-            .iter()
-            .map(|&b| ULAW_TO_LINEAR_LUT[b as usize])
-            .collect()
+        let mut pcm = vec![0i16; payload.len()];
+        let written = self.decode_into(payload, &mut pcm).unwrap_or(0);
+        pcm.truncate(written);
+        pcm
+    }
+
+    /// 1 byte = 1 örnek.
+    fn decoded_size(&self, payload_len: usize) -> usize {
+        payload_len
+    }
+
+    fn decode_into(&mut self, payload: &[u8], out: &mut [i16]) -> Result<usize, CodecError> {
+        let available = out.len();
+        let pcm = out
+            .get_mut(..payload.len())
+            .ok_or(CodecError::BufferTooSmall {
+                needed: payload.len(),
+                available,
+            })?;
+        for (dst, &b) in pcm.iter_mut().zip(payload) {
+            *dst = ULAW_TO_LINEAR_LUT[b as usize];
+        }
+        self.plc.process_good(pcm);
+        Ok(payload.len())
+    }
+
+    /// G.711 Appendix I: Perde periyodu tekrarı ile kayıp telafisi.
+    fn conceal(&mut self, samples: usize) -> Vec<i16> {
+        self.plc.conceal(samples)
     }
 }
@@ -0,0 +1,520 @@
+// sentiric-rtp-core/src/codecs/registry.rs
+
+//! Kodek kayıt defteri: Uygulamalar kendi encoder/decoder kurucularını bir kodek
+//! tanımı (ad, saat hızı, kanal, varsayılan fmtp) altında kaydeder; `AudioProfile`,
+//! `PayloadTypeMap` ve `CodecFactory` kodekleri bu defter üzerinden çözer.
+//! Yerleşik kodekler önceden kayıtlıdır. Aynı kodek yeniden kaydedilirse son kayıt geçerlidir
+//! (örn. G.729 için donanım hızlandırmalı bir uygulama). Kurucular yalnızca oturum
+//! kurulurken çağrılır; medya döngüsünde defter kullanılmaz.
+
+use super::*;
+use crate::config::CodecConfig;
+use crate::error::Error;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock};
+
+/// `a=rtpmap`/`a=fmtp` ile ilan edilen kodek tanımı. Ad ve fmtp sahiplenilebilir;
+/// yapılandırmadan okunan adlar için `String` verilebilir.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodecDescriptor {
+    /// SDP kodlama adı (büyük/küçük harf duyarsız eşleşir).
+    pub name: Cow<'static, str>,
+    /// RTP saati (`a=rtpmap` hızı).
+    pub clock_rate: u32,
+    pub channels: u8,
+    /// Teklifte kullanılacak varsayılan `a=fmtp` parametreleri.
+    pub fmtp: Option<Cow<'static, str>>,
+}
+
+impl CodecDescriptor {
+    pub fn new(name: impl Into<Cow<'static, str>>, clock_rate: u32, channels: u8) -> Self {
+        Self {
+            name: name.into(),
+            clock_rate,
+            channels,
+            fmtp: None,
+        }
+    }
+
+    pub fn with_fmtp(mut self, fmtp: impl Into<Cow<'static, str>>) -> Self {
+        self.fmtp = Some(fmtp.into());
+        self
+    }
+}
+
+/// `CodecRegistry::register` ile uygulama kodeğine atanan opak kimlik.
+/// Süreç genelinde tekildir; ad ve fmtp defterdeki `CodecDescriptor`'da tutulur.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomCodecId(u32);
+
+impl CustomCodecId {
+    fn next() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for CustomCodecId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "custom-{}", self.0)
+    }
+}
+
+/// Uygulama kodeklerine varsayılan PT atanırken atlanan, yerleşik dinamik PT'ler.
+const RESERVED_DYNAMIC_PAYLOAD_TYPES: [u8; 3] = [
+    l16::L16_16K_PAYLOAD_TYPE,
+    l16::L16_24K_PAYLOAD_TYPE,
+    l16::L16_48K_PAYLOAD_TYPE,
+];
+
+/// Müzakere edilmiş ayardan (fmtp dahil) encoder kuran fonksiyon.
+pub type EncoderConstructor =
+    Arc<dyn Fn(&CodecConfig) -> Result<Box<dyn Encoder>, Error> + Send + Sync>;
+/// Müzakere edilmiş ayardan (fmtp dahil) decoder kuran fonksiyon.
+pub type DecoderConstructor =
+    Arc<dyn Fn(&CodecConfig) -> Result<Box<dyn Decoder>, Error> + Send + Sync>;
+
+#[derive(Clone)]
+struct Registration {
+    codec: CodecType,
+    descriptor: CodecDescriptor,
+    /// Uygulama kodeğine atanan varsayılan dinamik PT; yerleşiklerde `CodecType`'tan gelir.
+    payload_type: Option<u8>,
+    encoder: EncoderConstructor,
+    decoder: DecoderConstructor,
+}
+
+impl Registration {
+    fn default_payload_type(&self) -> u8 {
+        self.payload_type
+            .unwrap_or_else(|| self.codec.default_payload_type())
+    }
+
+    /// L16 tüm hız/kanal birleşimleri için tek kayıttır; diğerleri birebir eşleşir.
+    fn matches(&self, codec: CodecType) -> bool {
+        match (self.codec, codec) {
+            (CodecType::L16 { .. }, CodecType::L16 { .. }) => true,
+            (registered, codec) => registered == codec,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CodecRegistry {
+    entries: Vec<Registration>,
+}
+
+impl Default for CodecRegistry {
+    fn default() -> Self {
+        Self::with_builtin()
+    }
+}
+
+impl fmt::Debug for CodecRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|e| (e.codec, &e.descriptor)))
+            .finish()
+    }
+}
+
+impl CodecRegistry {
+    /// Boş defter.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Yerleşik kodeklerin kayıtlı olduğu defter.
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        let builtin = [
+            (CodecType::PCMU, CodecDescriptor::new("PCMU", 8000, 1)),
+            (CodecType::PCMA, CodecDescriptor::new("PCMA", 8000, 1)),
+            #[cfg(feature = "g729")]
+            (
+                CodecType::G729,
+                CodecDescriptor::new("G729", 8000, 1).with_fmtp("annexb=no"),
+            ),
+            (CodecType::G722, CodecDescriptor::new("G722", 8000, 1)),
+            (CodecType::G726_16, CodecDescriptor::new("G726-16", 8000, 1)),
+            (CodecType::G726_24, CodecDescriptor::new("G726-24", 8000, 1)),
+            (CodecType::G726_32, CodecDescriptor::new("G726-32", 8000, 1)),
+            (CodecType::G726_40, CodecDescriptor::new("G726-40", 8000, 1)),
+            (CodecType::GSM, CodecDescriptor::new("GSM", 8000, 1)),
+            (
+                CodecType::L16 {
+                    rate: 16000,
+                    channels: 1,
+                },
+                CodecDescriptor::new("L16", 16000, 1),
+            ),
+            #[cfg(feature = "ilbc")]
+            (
+                CodecType::ILBC,
+                CodecDescriptor::new("iLBC", 8000, 1).with_fmtp("mode=30"),
+            ),
+            #[cfg(feature = "opus")]
+            (CodecType::Opus, CodecDescriptor::new("opus", 48000, 2)),
+            (
+                CodecType::Clearmode,
+                CodecDescriptor::new("CLEARMODE", 8000, 1),
+            ),
+            (CodecType::ComfortNoise, CodecDescriptor::new("CN", 8000, 1)),
+            (
+                CodecType::TelephoneEvent,
+                CodecDescriptor::new("telephone-event", 8000, 1).with_fmtp("0-16"),
+            ),
+        ];
+        for (codec, descriptor) in builtin {
+            registry.register_codec(codec, descriptor, builtin_encoder, builtin_decoder);
+        }
+        registry
+    }
+
+    /// Süreç genelinde paylaşılan, yalnızca yerleşik kodekleri içeren defter.
+    pub fn builtin() -> &'static CodecRegistry {
+        static BUILTIN: OnceLock<CodecRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(Self::with_builtin)
+    }
+
+    /// Uygulamaya özgü yeni bir kodek kaydeder ve ona atanan `CodecType::Custom`'ı döner.
+    /// Aynı ad, saat ve kanal ile yeniden kayıt önceki kaydın yerini alır ve aynı kodeği döner.
+    /// Kodeğe defterde kullanılmayan ilk dinamik PT (96-127) varsayılan olarak atanır;
+    /// aralık doluysa 96 kullanılır.
+    pub fn register<E, D>(
+        &mut self,
+        descriptor: CodecDescriptor,
+        encoder: E,
+        decoder: D,
+    ) -> CodecType
+    where
+        E: Fn(&CodecConfig) -> Result<Box<dyn Encoder>, Error> + Send + Sync + 'static,
+        D: Fn(&CodecConfig) -> Result<Box<dyn Decoder>, Error> + Send + Sync + 'static,
+    {
+        let channels = descriptor.channels.max(1);
+        let existing = self.entries.iter().find(|e| {
+            matches!(e.codec, CodecType::Custom { .. })
+                && e.descriptor.name.eq_ignore_ascii_case(&descriptor.name)
+                && e.descriptor.clock_rate == descriptor.clock_rate
+                && e.descriptor.channels == channels
+        });
+        let (codec, payload_type) = match existing {
+            Some(entry) => (entry.codec, entry.payload_type),
+            None => (
+                CodecType::Custom {
+                    id: CustomCodecId::next(),
+                    rate: descriptor.clock_rate,
+                    channels,
+                },
+                Some(self.free_dynamic_payload_type()),
+            ),
+        };
+        self.register_codec(codec, descriptor, encoder, decoder);
+        if let Some(entry) = self.entries.last_mut() {
+            entry.payload_type = payload_type;
+        }
+        codec
+    }
+
+    /// Defterdeki hiçbir kodeğin varsayılanı olmayan ilk dinamik PT.
+    fn free_dynamic_payload_type(&self) -> u8 {
+        let in_use = |pt: u8| {
+            RESERVED_DYNAMIC_PAYLOAD_TYPES.contains(&pt)
+                || self.entries.iter().any(|e| e.default_payload_type() == pt)
+        };
+        (96..=127).find(|&pt| !in_use(pt)).unwrap_or(96)
+    }
+
+    /// `codec` için kurucuları kaydeder; yerleşik bir kodeğin uygulamasını da değiştirebilir.
+    pub fn register_codec<E, D>(
+        &mut self,
+        codec: CodecType,
+        descriptor: CodecDescriptor,
+        encoder: E,
+        decoder: D,
+    ) where
+        E: Fn(&CodecConfig) -> Result<Box<dyn Encoder>, Error> + Send + Sync + 'static,
+        D: Fn(&CodecConfig) -> Result<Box<dyn Decoder>, Error> + Send + Sync + 'static,
+    {
+        self.entries.retain(|e| !e.matches(codec));
+        self.entries.push(Registration {
+            codec,
+            descriptor,
+            payload_type: None,
+            encoder: Arc::new(encoder),
+            decoder: Arc::new(decoder),
+        });
+    }
+
+    fn find(&self, codec: CodecType) -> Option<&Registration> {
+        self.entries.iter().find(|e| e.matches(codec))
+    }
+
+    pub fn contains(&self, codec: CodecType) -> bool {
+        self.find(codec).is_some()
+    }
+
+    pub fn descriptor(&self, codec: CodecType) -> Option<&CodecDescriptor> {
+        self.find(codec).map(|e| &e.descriptor)
+    }
+
+    /// SDP kodlama adını (`a=rtpmap:<pt> <ad>/<saat>[/<kanal>]`) kayıtlı bir kodeğe çözer.
+    /// Önce uygulama kodekleri, ardından yerleşik adlar (`CodecType::from_encoding_name`) denenir.
+    pub fn resolve(&self, name: &str, clock_rate: u32, channels: u8) -> Option<CodecType> {
+        let custom = self.entries.iter().find(|e| {
+            matches!(e.codec, CodecType::Custom { .. })
+                && e.descriptor.name.eq_ignore_ascii_case(name.trim())
+                && e.descriptor.clock_rate == clock_rate
+                && e.descriptor.channels == channels.max(1)
+        });
+        if let Some(entry) = custom {
+            return Some(entry.codec);
+        }
+        CodecType::from_encoding_name(name, clock_rate, channels).filter(|c| self.contains(*c))
+    }
+
+    /// Kodeğin kayıtlı tanımından, varsayılan PT ile bir `CodecConfig`.
+    /// Uygulama kodeklerinde PT, kayıtta atanan dinamik PT'dir.
+    pub fn config(&self, codec: CodecType) -> Option<CodecConfig> {
+        let entry = self.find(codec)?;
+        let descriptor = &entry.descriptor;
+        let (rate, channels) = match codec {
+            CodecType::L16 { rate, channels } => (rate, channels),
+            _ => (descriptor.clock_rate, descriptor.channels),
+        };
+        Some(CodecConfig {
+            codec,
+            payload_type: entry
+                .payload_type
+                .unwrap_or_else(|| codec.default_payload_type()),
+            name: descriptor.name.clone(),
+            rate,
+            channels,
+            fmtp: descriptor.fmtp.as_deref().map(str::to_string),
+            opus_fmtp: match codec {
+                #[cfg(feature = "opus")]
+                CodecType::Opus => Some(crate::config::OpusFmtp::default()),
+                _ => None,
+            },
+        })
+    }
+
+    /// Ayardaki kodek kayıtlı değilse `Error::UnsupportedEncoding`.
+    pub fn create_encoder(&self, config: &CodecConfig) -> Result<Box<dyn Encoder>, Error> {
+        let entry = self
+            .find(config.codec)
+            .ok_or_else(|| Error::UnsupportedEncoding(config.name.to_string()))?;
+        (entry.encoder)(config)
+    }
+
+    /// Ayardaki kodek kayıtlı değilse `Error::UnsupportedEncoding`.
+    pub fn create_decoder(&self, config: &CodecConfig) -> Result<Box<dyn Decoder>, Error> {
+        let entry = self
+            .find(config.codec)
+            .ok_or_else(|| Error::UnsupportedEncoding(config.name.to_string()))?;
+        (entry.decoder)(config)
+    }
+
+    /// Kayıt sırasıyla tüm kodekler ve tanımları.
+    pub fn iter(&self) -> impl Iterator<Item = (CodecType, &CodecDescriptor)> {
+        self.entries.iter().map(|e| (e.codec, &e.descriptor))
+    }
+}
+
+/// Yerleşik encoder'lar: G.729 `annexb`, G.726 paketleme düzeni (`AAL2-G726-32`),
+/// iLBC `mode=20|30` ve Opus `a=fmtp` tercihleri ayardan uygulanır.
+fn builtin_encoder(config: &CodecConfig) -> Result<Box<dyn Encoder>, Error> {
+    Ok(match config.codec {
+        #[cfg(feature = "g729")]
+        CodecType::G729 => Box::new(G729Encoder::from_fmtp(config.fmtp.as_deref())?),
+        CodecType::PCMU => Box::new(PcmuEncoder::new()),
+        CodecType::PCMA => Box::new(PcmaEncoder::new()),
+        CodecType::G722 => Box::new(G722Encoder::new()),
+        CodecType::G726_16 | CodecType::G726_24 | CodecType::G726_32 | CodecType::G726_40 => {
+            let rate = G726Rate::from_codec_type(config.codec).unwrap_or(G726Rate::Kbps32);
+            Box::new(G726Encoder::new(
+                rate,
+                G726Packing::from_encoding_name(&config.name),
+            ))
+        }
+        CodecType::GSM => Box::new(GsmEncoder::new()),
+        CodecType::L16 { rate, channels } => Box::new(L16Encoder::new(rate, channels)),
+        #[cfg(feature = "ilbc")]
+        CodecType::ILBC => Box::new(IlbcEncoder::from_fmtp(config.fmtp.as_deref())?),
+        #[cfg(feature = "opus")]
+        CodecType::Opus => {
+            let fmtp = config.opus_fmtp.clone().unwrap_or_default();
+            Box::new(OpusEncoder::new(fmtp.channels(), &fmtp)?)
+        }
+        CodecType::Clearmode => Box::new(ClearmodeEncoder::new()),
+        CodecType::ComfortNoise => Box::new(ComfortNoiseEncoder::default()),
+        CodecType::TelephoneEvent => Box::new(NoOpEncoder {}),
+        // G.729 yalnızca `g729` özelliğiyle kayıtlıdır
+        #[cfg(not(feature = "g729"))]
+        CodecType::G729 => return Err(Error::UnsupportedEncoding(config.name.to_string())),
+        CodecType::Custom { .. } => {
+            return Err(Error::UnsupportedEncoding(config.name.to_string()))
+        }
+    })
+}
+
+fn builtin_decoder(config: &CodecConfig) -> Result<Box<dyn Decoder>, Error> {
+    Ok(match config.codec {
+        #[cfg(feature = "g729")]
+        CodecType::G729 => Box::new(G729Decoder::new()?),
+        CodecType::PCMU => Box::new(PcmuDecoder::new()),
+        CodecType::PCMA => Box::new(PcmaDecoder::new()),
+        CodecType::G722 => Box::new(G722Decoder::new()),
+        CodecType::G726_16 | CodecType::G726_24 | CodecType::G726_32 | CodecType::G726_40 => {
+            let rate = G726Rate::from_codec_type(config.codec).unwrap_or(G726Rate::Kbps32);
+            Box::new(G726Decoder::new(
+                rate,
+                G726Packing::from_encoding_name(&config.name),
+            ))
+        }
+        CodecType::GSM => Box::new(GsmDecoder::new()),
+        CodecType::L16 { rate, channels } => Box::new(L16Decoder::new(rate, channels)),
+        #[cfg(feature = "ilbc")]
+        CodecType::ILBC => Box::new(IlbcDecoder::from_fmtp(config.fmtp.as_deref())?),
+        #[cfg(feature = "opus")]
+        CodecType::Opus => Box::new(OpusDecoder::new(1)?),
+        CodecType::Clearmode => Box::new(ClearmodeDecoder::new()),
+        CodecType::ComfortNoise => Box::new(ComfortNoiseDecoder::default()),
+        CodecType::TelephoneEvent => Box::new(NoOpDecoder {}),
+        // G.729 yalnızca `g729` özelliğiyle kayıtlıdır
+        #[cfg(not(feature = "g729"))]
+        CodecType::G729 => return Err(Error::UnsupportedEncoding(config.name.to_string())),
+        CodecType::Custom { .. } => {
+            return Err(Error::UnsupportedEncoding(config.name.to_string()))
+        }
+    })
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Uygulamaya özgü örnek kodek: Örnekleri olduğu gibi taşır.
+    struct EchoCodec(CodecType);
+
+    impl Encoder for EchoCodec {
+        fn encode(&mut self, pcm_samples: &[i16]) -> Vec<u8> {
+            pcm_samples.iter().flat_map(|s| s.to_le_bytes()).collect()
+        }
+        fn get_type(&self) -> CodecType {
+            self.0
+        }
+    }
+
+    impl Decoder for EchoCodec {
+        fn decode(&mut self, payload: &[u8]) -> Vec<i16> {
+            payload
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]))
+                .collect()
+        }
+        fn get_type(&self) -> CodecType {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_register_custom_codec() {
+        let mut registry = CodecRegistry::with_builtin();
+        let descriptor = CodecDescriptor::new("X-ECHO", 16000, 1).with_fmtp("v=1");
+        let codec = registry.register(
+            descriptor,
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Encoder>),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Decoder>),
+        );
+        assert_eq!(codec.sample_rate(), 16000);
+        assert_eq!(registry.resolve("x-echo", 16000, 1), Some(codec));
+        assert_eq!(registry.resolve("X-ECHO", 8000, 1), None);
+        assert_eq!(registry.resolve("pcmu", 8000, 1), Some(CodecType::PCMU));
+
+        let config = registry.config(codec).unwrap();
+        assert_eq!(config.fmtp_line().as_deref(), Some("a=fmtp:96 v=1"));
+        let mut encoder = registry.create_encoder(&config).unwrap();
+        let mut decoder = registry.create_decoder(&config).unwrap();
+        assert_eq!(encoder.get_type(), codec);
+        assert_eq!(decoder.decode(&encoder.encode(&[1, -2, 3])), [1, -2, 3]);
+
+        // Kayıtlı olmayan özel kodek paylaşılan yerleşik defterde kurulamaz
+        assert_eq!(
+            CodecRegistry::builtin().create_encoder(&config).err(),
+            Some(Error::UnsupportedEncoding("X-ECHO".to_string()))
+        );
+
+        // Her uygulama kodeğine ayrı, yerleşiklerle çakışmayan bir dinamik PT atanır
+        let other = registry.register(
+            CodecDescriptor::new(String::from("X-OTHER"), 8000, 1),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Encoder>),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Decoder>),
+        );
+        assert_ne!(other, codec);
+        let pt = registry.config(other).unwrap().payload_type;
+        assert!((97..=127).contains(&pt));
+        assert!(!registry
+            .iter()
+            .any(|(c, _)| c != other && c.default_payload_type() == pt));
+        let again = registry.register(
+            CodecDescriptor::new("x-echo", 16000, 1),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Encoder>),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Decoder>),
+        );
+        assert_eq!(again, codec);
+        assert_eq!(registry.config(codec).unwrap().payload_type, 96);
+
+        // Fabrika kendisine verilen defteri kullanır
+        let factory = CodecFactory::new(&registry);
+        assert_eq!(factory.create_encoder(codec).unwrap().get_type(), codec);
+        let mut map = PayloadTypeMap::new();
+        map.insert_rtpmap_with(&registry, "a=rtpmap:120 X-ECHO/16000")
+            .unwrap();
+        assert!(factory.create_decoder_for_payload(&map, 120).is_ok());
+        assert!(CodecFactory::builtin()
+            .create_decoder_for_payload(&map, 120)
+            .is_err());
+    }
+
+    #[test]
+    fn test_override_builtin_codec() {
+        let mut registry = CodecRegistry::with_builtin();
+        registry.register_codec(
+            CodecType::PCMU,
+            CodecDescriptor::new("PCMU", 8000, 1),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Encoder>),
+            |c: &CodecConfig| Ok(Box::new(EchoCodec(c.codec)) as Box<dyn Decoder>),
+        );
+        let config = registry.config(CodecType::PCMU).unwrap();
+        // EchoCodec örnek başına 2 byte, yerleşik PCMU 1 byte üretir
+        assert_eq!(
+            registry
+                .create_encoder(&config)
+                .unwrap()
+                .encode(&[0; 4])
+                .len(),
+            8
+        );
+        assert_eq!(
+            registry
+                .iter()
+                .filter(|(c, _)| *c == CodecType::PCMU)
+                .count(),
+            1
+        );
+
+        let l16 = CodecType::L16 {
+            rate: 24000,
+            channels: 1,
+        };
+        let config = registry.config(l16).unwrap();
+        assert_eq!(config.rtpmap_line(), "a=rtpmap:117 L16/24000");
+        assert_eq!(registry.create_decoder(&config).unwrap().get_type(), l16);
+    }
+}
@@ -1,6 +1,10 @@
 // sentiric-rtp-core/src/config.rs
-use crate::codecs::CodecType;
+use crate::codecs::{CodecDescriptor, CodecRegistry, CodecType, Decoder, Encoder, PayloadTypeMap};
+use crate::error::Error;
+use crate::srtp::{CryptoAttribute, SrtpError};
+use std::borrow::Cow;
 use std::env;
+use std::fmt;
 
 /// Platform genelinde geçerli Medya Anayasası.
 /// Hangi kodeklerin, hangi sırayla ve hangi ayarlarla kullanılacağını belirler.
@@ -10,15 +14,155 @@ pub struct AudioProfile {
     pub codecs: Vec<CodecConfig>,
     /// Paketleme süresi (ms). Standart: 20ms.
     pub ptime: u8,
+    /// SDES-SRTP (RFC 4568) `a=crypto` satırları. Boşsa medya RTP/AVP olarak teklif edilir.
+    pub crypto: Vec<CryptoAttribute>,
+    /// Kodeklerin çözüldüğü ve kurulduğu defter (yerleşik kodekler önceden kayıtlı).
+    pub registry: CodecRegistry,
 }
 
 #[derive(Clone, Debug)]
 pub struct CodecConfig {
     pub codec: CodecType,
     pub payload_type: u8,
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub rate: u32,
-    pub fmtp: Option<&'static str>,
+    /// `a=rtpmap` kanal sayısı. RFC 7587: Opus her zaman `opus/48000/2` ilan edilir.
+    pub channels: u8,
+    /// `a=fmtp` parametreleri; müzakere edilmiş haritadan gelirse karşı tarafın değeridir.
+    pub fmtp: Option<String>,
+    /// Opus `a=fmtp` parametreleri (RFC 7587 Bölüm 6.1). Diğer kodeklerde `None`.
+    pub opus_fmtp: Option<OpusFmtp>,
+}
+
+impl CodecConfig {
+    /// `a=rtpmap:` satırı (kanal sayısı 1 ise yazılmaz).
+    pub fn rtpmap_line(&self) -> String {
+        if self.channels > 1 {
+            format!(
+                "a=rtpmap:{} {}/{}/{}",
+                self.payload_type, self.name, self.rate, self.channels
+            )
+        } else {
+            format!("a=rtpmap:{} {}/{}", self.payload_type, self.name, self.rate)
+        }
+    }
+
+    /// `a=fmtp:` satırı. Opus parametreleri varsa statik `fmtp` yerine onlar yazılır.
+    pub fn fmtp_line(&self) -> Option<String> {
+        let params = match &self.opus_fmtp {
+            Some(opus) => opus.to_string(),
+            None => self.fmtp.clone()?,
+        };
+        Some(format!("a=fmtp:{} {}", self.payload_type, params))
+    }
+}
+
+/// Opus SDP parametreleri (RFC 7587 Bölüm 6.1).
+/// Alıcının tercihlerini ifade eder: Karşı tarafın teklifinden çözümlenen değerler
+/// kendi encoder'ımızı yapılandırmak için kullanılır.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpusFmtp {
+    /// Alıcının işleyebileceği en yüksek örnekleme hızı (8000-48000).
+    pub maxplaybackrate: Option<u32>,
+    /// Alıcı stereo tercih ediyor mu?
+    pub stereo: bool,
+    /// Gönderici büyük olasılıkla stereo gönderecek mi?
+    pub sprop_stereo: bool,
+    /// Alıcı in-band FEC çözebiliyor (encoder FEC üretmeli).
+    pub useinbandfec: bool,
+    /// Alıcı DTX'i (sessizlikte paket göndermeme) destekliyor.
+    pub usedtx: bool,
+    /// Alıcının tercih ettiği en yüksek ortalama bit hızı (6000-510000 bps).
+    pub maxaveragebitrate: Option<u32>,
+    pub minptime: Option<u8>,
+}
+
+impl Default for OpusFmtp {
+    /// Telekom profili: 10ms minimum paket, kayıplara karşı in-band FEC açık.
+    fn default() -> Self {
+        Self {
+            maxplaybackrate: None,
+            stereo: false,
+            sprop_stereo: false,
+            useinbandfec: true,
+            usedtx: false,
+            maxaveragebitrate: None,
+            minptime: Some(10),
+        }
+    }
+}
+
+impl OpusFmtp {
+    /// `a=fmtp:111 minptime=10;useinbandfec=1` satırını veya sadece parametre kısmını çözer.
+    /// RFC 7587: Bilinmeyen veya geçersiz parametreler yok sayılır; belirtilmeyen bayraklar 0'dır.
+    pub fn parse(fmtp: &str) -> Self {
+        let mut params = fmtp.trim();
+        if let Some(rest) = params.strip_prefix("a=fmtp:") {
+            params = rest.split_once(' ').map_or("", |(_, p)| p);
+        }
+        let mut out = Self {
+            useinbandfec: false,
+            minptime: None,
+            ..Self::default()
+        };
+        for param in params.split(';') {
+            let Some((key, value)) = param.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            let flag = value == "1";
+            match key.trim().to_ascii_lowercase().as_str() {
+                "maxplaybackrate" => {
+                    out.maxplaybackrate = value.parse().ok().filter(|r| *r >= 8000)
+                }
+                "stereo" => out.stereo = flag,
+                "sprop-stereo" => out.sprop_stereo = flag,
+                "useinbandfec" => out.useinbandfec = flag,
+                "usedtx" => out.usedtx = flag,
+                "maxaveragebitrate" => {
+                    out.maxaveragebitrate = value.parse::<u32>().ok().map(|b| b.clamp(6000, 510000))
+                }
+                "minptime" => out.minptime = value.parse().ok(),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// Encoder kanal sayısı: Karşı taraf stereo istemediyse mono gönderilir.
+    pub fn channels(&self) -> u8 {
+        if self.stereo {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl fmt::Display for OpusFmtp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut params = Vec::new();
+        if let Some(minptime) = self.minptime {
+            params.push(format!("minptime={}", minptime));
+        }
+        if let Some(rate) = self.maxplaybackrate {
+            params.push(format!("maxplaybackrate={}", rate));
+        }
+        if let Some(bitrate) = self.maxaveragebitrate {
+            params.push(format!("maxaveragebitrate={}", bitrate));
+        }
+        for (name, enabled) in [
+            ("stereo", self.stereo),
+            ("sprop-stereo", self.sprop_stereo),
+            ("useinbandfec", self.useinbandfec),
+            ("usedtx", self.usedtx),
+        ] {
+            if enabled {
+                params.push(format!("{}=1", name));
+            }
+        }
+        f.write_str(&params.join(";"))
+    }
 }
 
 impl Default for AudioProfile {
@@ -30,47 +174,114 @@ impl Default for AudioProfile {
 
         let mut profile = Self {
             ptime: 20, // Telekom standardı: 20ms paketler
+            crypto: Vec::new(),
+            registry: CodecRegistry::with_builtin(),
             codecs: vec![
                 // --- SES KODEKLERİ ---
 
                 // 1. G.729 (Bant genişliği dostu, lisans gerektirmez - bcg729)
+                #[cfg(feature = "g729")]
                 CodecConfig {
                     codec: CodecType::G729,
                     payload_type: 18,
-                    name: "G729",
+                    name: "G729".into(),
                     rate: 8000,
-                    fmtp: Some("annexb=no"),
+                    channels: 1,
+                    fmtp: Some("annexb=no".to_string()),
+                    opus_fmtp: None,
                 },
                 // 2. PCMU (En güvenli, en uyumlu, düşük işlemci yükü)
                 CodecConfig {
                     codec: CodecType::PCMU,
                     payload_type: 0,
-                    name: "PCMU",
+                    name: "PCMU".into(),
                     rate: 8000,
+                    channels: 1,
                     fmtp: None,
+                    opus_fmtp: None,
                 },
                 // 3. PCMA (Avrupa standardı)
                 CodecConfig {
                     codec: CodecType::PCMA,
                     payload_type: 8,
-                    name: "PCMA",
+                    name: "PCMA".into(),
+                    rate: 8000,
+                    channels: 1,
+                    fmtp: None,
+                    opus_fmtp: None,
+                },
+                // 4. G.722 (HD ses). RFC 3551: rtpmap saati 8000, ses 16 kHz.
+                CodecConfig {
+                    codec: CodecType::G722,
+                    payload_type: 9,
+                    name: "G722".into(),
+                    rate: 8000,
+                    channels: 1,
+                    fmtp: None,
+                    opus_fmtp: None,
+                },
+                // 5. GSM 06.10 (Eski SIP trunk'ları ve sesli mesaj arşivleri)
+                CodecConfig {
+                    codec: CodecType::GSM,
+                    payload_type: 3,
+                    name: "GSM".into(),
+                    rate: 8000,
+                    channels: 1,
+                    fmtp: None,
+                    opus_fmtp: None,
+                },
+                // 6. iLBC (Yüksek kayıplı hatlar). RFC 3952: mode yoksa 30ms.
+                #[cfg(feature = "ilbc")]
+                CodecConfig {
+                    codec: CodecType::ILBC,
+                    payload_type: 97,
+                    name: "iLBC".into(),
                     rate: 8000,
+                    channels: 1,
+                    fmtp: Some("mode=30".to_string()),
+                    opus_fmtp: None,
+                },
+                // 7. Opus (WebRTC). RFC 7587: rtpmap her zaman opus/48000/2.
+                #[cfg(feature = "opus")]
+                CodecConfig {
+                    codec: CodecType::Opus,
+                    payload_type: 111,
+                    name: "opus".into(),
+                    rate: 48000,
+                    channels: 2,
                     fmtp: None,
+                    opus_fmtp: Some(OpusFmtp::default()),
                 },
                 // --- SİNYAL KODEKLERİ ---
-                // 4. DTMF (Tuşlama)
+                // 8. Konfor Gürültüsü (RFC 3389, sessizlik bastırma)
+                CodecConfig {
+                    codec: CodecType::ComfortNoise,
+                    payload_type: 13,
+                    name: "CN".into(),
+                    rate: 8000,
+                    channels: 1,
+                    fmtp: None,
+                    opus_fmtp: None,
+                },
+                // 9. DTMF (Tuşlama)
                 CodecConfig {
                     codec: CodecType::TelephoneEvent,
                     payload_type: 101,
-                    name: "telephone-event",
+                    name: "telephone-event".into(),
                     rate: 8000,
-                    fmtp: Some("0-16"),
+                    channels: 1,
+                    fmtp: Some("0-16".to_string()),
+                    opus_fmtp: None,
                 },
             ],
         };
 
         // Eğer tercih edilen kodek listede varsa, onu bulup EN BAŞA (Priority 1) yerleştirir.
-        if let Some(pos) = profile.codecs.iter().position(|c| c.name == preferred) {
+        if let Some(pos) = profile
+            .codecs
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(&preferred))
+        {
             let pref_codec = profile.codecs.remove(pos);
             profile.codecs.insert(0, pref_codec);
         }
@@ -80,16 +291,330 @@ impl Default for AudioProfile {
 }
 
 impl AudioProfile {
-    /// Tercih edilen birincil **SES** kodeğini döndürür (DTMF hariç).
+    /// Tercih edilen birincil **SES** kodeğini döndürür (DTMF ve CN hariç).
     pub fn preferred_audio_codec(&self) -> CodecType {
         self.codecs
             .iter()
-            .find(|c| c.codec != CodecType::TelephoneEvent) // DTMF'i atla
+            .find(|c| !matches!(c.codec, CodecType::TelephoneEvent | CodecType::ComfortNoise)) // DTMF/CN'i atla
             .map(|c| c.codec)
             .unwrap_or(CodecType::PCMU) // Hiçbiri yoksa Fallback
     }
 
+    /// G.729 Annex B (VAD/DTX/CNG) profilde etkin mi? G.729 yoksa false.
+    #[cfg(feature = "g729")]
+    pub fn g729_annexb(&self) -> bool {
+        self.codecs
+            .iter()
+            .find(|c| c.codec == CodecType::G729)
+            .is_some_and(|c| crate::codecs::g729::annexb_enabled(c.fmtp.as_deref()))
+    }
+
+    /// Profilin kendi PT'leriyle kurulmuş harita (yerel teklif).
+    pub fn payload_map(&self) -> PayloadTypeMap {
+        let mut map = PayloadTypeMap::new();
+        for c in &self.codecs {
+            map.insert(c.payload_type, c.codec, c.rate, c.channels);
+        }
+        map
+    }
+
+    /// Profilin kendi PT'sine (yerel teklif) karşılık gelen kodek ayarı.
     pub fn get_by_payload(&self, pt: u8) -> Option<CodecConfig> {
         self.codecs.iter().find(|c| c.payload_type == pt).cloned()
     }
+
+    /// PT, karşı tarafla müzakere edilmiş `map` üzerinden çözülür; profildeki aynı kodeğin
+    /// ayarı, PT'si müzakere edilen değerle döner (örn. telephone-event PT 96).
+    /// G.726 için kodlama adı müzakere edilen paketleme düzenini taşır (`AAL2-G726-32`).
+    /// Teklifte olmayan ama defterde bulunan kodekler (örn. L16, G.726) defterdeki ayarla çözülür.
+    pub fn get_by_payload_in(&self, map: &PayloadTypeMap, pt: u8) -> Option<CodecConfig> {
+        let mapping = map.get(pt)?;
+        let mut config = self
+            .codecs
+            .iter()
+            .find(|c| c.codec == mapping.codec)
+            .cloned()
+            .or_else(|| self.registry.config(mapping.codec))?;
+        mapping.apply_to(&mut config);
+        Some(config)
+    }
+
+    /// Uygulamaya özgü kodeği deftere kaydeder ve teklifin sonuna `payload_type` ile ekler.
+    /// PT 127'den büyükse `Error::UnsupportedPayload`, teklifte başka bir kodek tarafından
+    /// kullanılıyorsa `Error::PayloadTypeInUse` döner; bu durumlarda profil ve defter değişmez.
+    /// Aynı kodeğin yeniden kaydı önceki ayarın yerini alır.
+    pub fn register_codec<E, D>(
+        &mut self,
+        payload_type: u8,
+        descriptor: CodecDescriptor,
+        encoder: E,
+        decoder: D,
+    ) -> Result<CodecType, Error>
+    where
+        E: Fn(&CodecConfig) -> Result<Box<dyn Encoder>, Error> + Send + Sync + 'static,
+        D: Fn(&CodecConfig) -> Result<Box<dyn Decoder>, Error> + Send + Sync + 'static,
+    {
+        if payload_type > 127 {
+            return Err(Error::UnsupportedPayload(payload_type));
+        }
+        let existing =
+            self.registry
+                .resolve(&descriptor.name, descriptor.clock_rate, descriptor.channels);
+        let clash = self
+            .codecs
+            .iter()
+            .any(|c| c.payload_type == payload_type && Some(c.codec) != existing);
+        if clash {
+            return Err(Error::PayloadTypeInUse(payload_type));
+        }
+
+        let codec = self.registry.register(descriptor, encoder, decoder);
+        if let Some(mut config) = self.registry.config(codec) {
+            config.payload_type = payload_type;
+            self.codecs.retain(|c| c.codec != codec);
+            self.codecs.push(config);
+        }
+        Ok(codec)
+    }
+
+    /// Profildeki PT için, ayarın fmtp'siyle defterden encoder kurar.
+    pub fn create_encoder(&self, pt: u8) -> Result<Box<dyn Encoder>, Error> {
+        let config = self
+            .get_by_payload(pt)
+            .ok_or(Error::UnsupportedPayload(pt))?;
+        self.registry.create_encoder(&config)
+    }
+
+    /// Profildeki PT için, ayarın fmtp'siyle defterden decoder kurar.
+    pub fn create_decoder(&self, pt: u8) -> Result<Box<dyn Decoder>, Error> {
+        let config = self
+            .get_by_payload(pt)
+            .ok_or(Error::UnsupportedPayload(pt))?;
+        self.registry.create_decoder(&config)
+    }
+
+    /// Müzakere edilmiş `map`'teki PT için encoder; PT veya kodek profilde yoksa
+    /// `Error::UnsupportedPayload`.
+    pub fn create_encoder_in(
+        &self,
+        map: &PayloadTypeMap,
+        pt: u8,
+    ) -> Result<Box<dyn Encoder>, Error> {
+        let config = self
+            .get_by_payload_in(map, pt)
+            .ok_or(Error::UnsupportedPayload(pt))?;
+        self.registry.create_encoder(&config)
+    }
+
+    /// Müzakere edilmiş `map`'teki PT için decoder; PT veya kodek profilde yoksa
+    /// `Error::UnsupportedPayload`.
+    pub fn create_decoder_in(
+        &self,
+        map: &PayloadTypeMap,
+        pt: u8,
+    ) -> Result<Box<dyn Decoder>, Error> {
+        let config = self
+            .get_by_payload_in(map, pt)
+            .ok_or(Error::UnsupportedPayload(pt))?;
+        self.registry.create_decoder(&config)
+    }
+
+    /// Teklife eklenecek `a=crypto:` satırlarını üretir.
+    pub fn crypto_lines(&self) -> Vec<String> {
+        self.crypto.iter().map(|c| c.to_sdp_line()).collect()
+    }
+
+    /// SDP metnindeki (veya medya bloğundaki) tüm `a=crypto:` satırlarını çözümler.
+    /// Tanınmayan süitli satırlar atlanır (RFC 4568 §7.1); bozuk satırlar veya hiç
+    /// kullanılabilir satır kalmaması hata döner.
+    pub fn parse_crypto_lines(sdp: &str) -> Result<Vec<CryptoAttribute>, SrtpError> {
+        let mut attributes = Vec::new();
+        let mut unsupported = None;
+        for line in sdp
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with("a=crypto:"))
+        {
+            match CryptoAttribute::parse(line) {
+                Ok(attribute) => attributes.push(attribute),
+                Err(SrtpError::UnsupportedProfile(suite)) => unsupported = Some(suite),
+                Err(e) => return Err(e),
+            }
+        }
+        match unsupported {
+            Some(suite) if attributes.is_empty() => Err(SrtpError::UnsupportedProfile(suite)),
+            _ => Ok(attributes),
+        }
+    }
+
+    pub fn get_crypto_by_tag(&self, tag: u32) -> Option<&CryptoAttribute> {
+        self.crypto.iter().find(|c| c.tag == tag)
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opus_fmtp_parse_and_format() {
+        let fmtp = OpusFmtp::parse(
+            "a=fmtp:111 minptime=10; useinbandfec=1;stereo=1;maxplaybackrate=16000;maxaveragebitrate=900000;x-google=5",
+        );
+        assert!(fmtp.useinbandfec && fmtp.stereo && !fmtp.usedtx);
+        assert_eq!(fmtp.maxplaybackrate, Some(16000));
+        assert_eq!(fmtp.maxaveragebitrate, Some(510000));
+        assert_eq!(fmtp.channels(), 2);
+        assert_eq!(OpusFmtp::parse(&fmtp.to_string()), fmtp);
+
+        // Belirtilmeyen bayraklar kapalıdır
+        assert!(!OpusFmtp::parse("usedtx=1").useinbandfec);
+        assert_eq!(
+            OpusFmtp::default().to_string(),
+            "minptime=10;useinbandfec=1"
+        );
+
+        let pcmu = AudioProfile::default().get_by_payload(0).unwrap();
+        assert_eq!(pcmu.rtpmap_line(), "a=rtpmap:0 PCMU/8000");
+        assert_eq!(pcmu.fmtp_line(), None);
+    }
+
+    #[test]
+    fn test_parse_crypto_lines_skips_unknown_suites() {
+        let known =
+            "a=crypto:2 AES_CM_128_HMAC_SHA1_80 inline:WVNfX19zZW1jdGwgKCkgewkyMjA7fQp9CnVubGVz";
+        let unknown =
+            "a=crypto:1 FUTURE_CIPHER_256 inline:WVNfX19zZW1jdGwgKCkgewkyMjA7fQp9CnVubGVz";
+
+        let sdp = format!("m=audio 4000 RTP/SAVP 0\r\n{}\r\n{}\r\n", unknown, known);
+        let parsed = AudioProfile::parse_crypto_lines(&sdp).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].tag, 2);
+
+        assert_eq!(
+            AudioProfile::parse_crypto_lines(unknown),
+            Err(SrtpError::UnsupportedProfile(
+                "FUTURE_CIPHER_256".to_string()
+            ))
+        );
+        assert!(matches!(
+            AudioProfile::parse_crypto_lines(&format!("{}\na=crypto:x", known)),
+            Err(SrtpError::Parse(_))
+        ));
+        assert_eq!(
+            AudioProfile::parse_crypto_lines("m=audio 4000 RTP/AVP 0"),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_get_by_payload_resolves_negotiated_pt() {
+        let profile = AudioProfile::default();
+        assert_eq!(
+            profile.get_by_payload(101).unwrap().codec,
+            CodecType::TelephoneEvent
+        );
+        assert!(profile.get_by_payload(96).is_none());
+
+        // Karşı taraf DTMF'i PT 96 ile teklif etti
+        let mut remote = PayloadTypeMap::with_static();
+        remote
+            .insert_rtpmap("a=rtpmap:96 telephone-event/8000")
+            .unwrap();
+        let dtmf = profile.get_by_payload_in(&remote, 96).unwrap();
+        assert_eq!(dtmf.codec, CodecType::TelephoneEvent);
+        assert_eq!(dtmf.rtpmap_line(), "a=rtpmap:96 telephone-event/8000");
+        assert_eq!(dtmf.fmtp_line().as_deref(), Some("a=fmtp:96 0-16"));
+
+        // Karşı tarafın fmtp'si profildeki varsayılanın yerini alır
+        remote.insert_fmtp("a=fmtp:96 0-15").unwrap();
+        let dtmf = profile.get_by_payload_in(&remote, 96).unwrap();
+        assert_eq!(dtmf.fmtp.as_deref(), Some("0-15"));
+        assert_eq!(dtmf.fmtp_line().as_deref(), Some("a=fmtp:96 0-15"));
+        assert!(profile.get_by_payload_in(&remote, 101).is_none());
+
+        // L16 teklifte yok, ancak karşı taraf PT 116 ile teklif ederse çözülür
+        assert!(!profile.codecs.iter().any(|c| c.payload_type == 116));
+        assert!(profile.get_by_payload(116).is_none());
+        remote.insert_rtpmap("a=rtpmap:116 L16/16000").unwrap();
+        let l16 = profile.get_by_payload_in(&remote, 116).unwrap();
+        assert_eq!(l16.rtpmap_line(), "a=rtpmap:116 L16/16000");
+        assert!(profile.get_by_payload(114).is_none());
+    }
+
+    #[test]
+    fn test_profile_resolves_registered_codec() {
+        use crate::codecs::{L16Decoder, L16Encoder};
+
+        let mut profile = AudioProfile::default();
+        let codec = profile
+            .register_codec(
+                120,
+                CodecDescriptor::new("X-LINEAR", 16000, 1),
+                |_: &CodecConfig| Ok(Box::new(L16Encoder::new(16000, 1)) as Box<dyn Encoder>),
+                |_: &CodecConfig| Ok(Box::new(L16Decoder::new(16000, 1)) as Box<dyn Decoder>),
+            )
+            .unwrap();
+        assert_eq!(
+            profile.get_by_payload(120).unwrap().rtpmap_line(),
+            "a=rtpmap:120 X-LINEAR/16000"
+        );
+        assert!(profile.create_encoder(120).is_ok());
+        assert!(profile.create_decoder(0).is_ok());
+        assert_eq!(
+            profile.create_decoder(77).err(),
+            Some(Error::UnsupportedPayload(77))
+        );
+
+        // Karşı taraf aynı kodeği başka bir PT ile teklif etti
+        let mut remote = PayloadTypeMap::new();
+        assert_eq!(
+            remote.insert_rtpmap_with(&profile.registry, "a=rtpmap:99 x-linear/16000"),
+            Ok(codec)
+        );
+        assert!(remote.insert_rtpmap("a=rtpmap:98 X-LINEAR/16000").is_err());
+        let mut decoder = profile.create_decoder_in(&remote, 99).unwrap();
+        assert_eq!(decoder.decode(&[0x01, 0x02]), [0x0102]);
+
+        // PT çakışması reddedilir; aynı kodeğin yeniden kaydı aynı PT'yi kullanabilir
+        let encoder = |_: &CodecConfig| Ok(Box::new(L16Encoder::new(8000, 1)) as Box<dyn Encoder>);
+        let decoder = |_: &CodecConfig| Ok(Box::new(L16Decoder::new(8000, 1)) as Box<dyn Decoder>);
+        for pt in [0, 101, 120] {
+            assert_eq!(
+                profile.register_codec(
+                    pt,
+                    CodecDescriptor::new("X-OTHER", 8000, 1),
+                    encoder,
+                    decoder
+                ),
+                Err(Error::PayloadTypeInUse(pt))
+            );
+        }
+        assert_eq!(
+            profile.register_codec(
+                128,
+                CodecDescriptor::new("X-OTHER", 8000, 1),
+                encoder,
+                decoder
+            ),
+            Err(Error::UnsupportedPayload(128))
+        );
+        assert_eq!(
+            profile.registry.resolve("X-OTHER", 8000, 1),
+            None,
+            "reddedilen kayıt deftere eklenmemeli"
+        );
+        let again = profile.register_codec(
+            120,
+            CodecDescriptor::new(String::from("x-linear"), 16000, 1),
+            |_: &CodecConfig| Ok(Box::new(L16Encoder::new(16000, 1)) as Box<dyn Encoder>),
+            |_: &CodecConfig| Ok(Box::new(L16Decoder::new(16000, 1)) as Box<dyn Decoder>),
+        );
+        assert_eq!(again, Ok(codec));
+        assert_eq!(
+            profile.codecs.iter().filter(|c| c.codec == codec).count(),
+            1
+        );
+    }
 }
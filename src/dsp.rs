@@ -14,6 +14,8 @@ pub struct AudioResampler {
     hpf_y2: f32,
     input_rate: usize,
     output_rate: usize,
+    /// Şeffaf mod: Girdi hiçbir işlemden geçmeden aynen döner (Clearmode gibi veri kanalları).
+    bypass: bool,
 }
 
 impl AudioResampler {
@@ -28,9 +30,21 @@ impl AudioResampler {
             hpf_y2: 0.0,
             input_rate,
             output_rate,
+            bypass: false,
         }
     }
 
+    /// Şeffaf modu açar/kapatır. Açıkken HPF, noise gate, anti-aliasing ve yeniden
+    /// örnekleme atlanır; filtre durumları dokunulmadan kalır.
+    /// Örn: `resampler.set_bypass(codec.is_transparent())`
+    pub fn set_bypass(&mut self, enabled: bool) {
+        self.bypass = enabled;
+    }
+
+    pub fn is_bypassed(&self) -> bool {
+        self.bypass
+    }
+
     /// 300Hz High-Pass Filter (Butterworth IIR)
     /// G.711'in cızırtı yapmasına neden olan bas frekansları ve DC kaymasını temizler.
     fn apply_hpf(&mut self, sample: f32) -> f32 {
@@ -72,7 +86,7 @@ impl AudioResampler {
 
     // [ARCH-COMPLIANCE] &self yerine &mut self alarak state mutasyonunu güvenli ve lock-free hale getirdik.
     pub fn process(&mut self, input: &[i16]) -> Vec<i16> {
-        if self.input_rate == self.output_rate {
+        if self.bypass || self.input_rate == self.output_rate {
             return input.to_vec();
         }
 
@@ -0,0 +1,79 @@
+// sentiric-rtp-core/src/error.rs
+
+//! Kütüphane genelinde tek hata türü.
+//! Modüllerin kendi hata türleri (`CodecError`, `SrtpError`, `StunError`...) korunur;
+//! `Error` bunları `From` ile toplar, böylece medya düğümü tek bir `?` zinciriyle
+//! hatayı yukarı taşıyabilir ve hiçbir kötü çağrı süreci çökertmez.
+
+use crate::codecs::CodecError;
+use crate::framing::FramingError;
+use crate::srtp::SrtpError;
+use crate::stun::StunError;
+use crate::turn::TurnError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Kodek başlatma, çerçeve boyutu veya çıkış tamponu hatası.
+    Codec(CodecError),
+    /// PT, oturumun payload haritasında müzakere edilmemiş.
+    UnsupportedPayload(u8),
+    /// PT, profilde başka bir kodeğe atanmış.
+    PayloadTypeInUse(u8),
+    /// SDP kodlama adı (`a=rtpmap`) desteklenen bir kodeğe karşılık gelmiyor.
+    UnsupportedEncoding(String),
+    /// Metin (SDP satırı vb.) çözümlenemedi.
+    Parse(String),
+    Srtp(SrtpError),
+    Stun(StunError),
+    Turn(TurnError),
+    Framing(FramingError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Codec(e) => write!(f, "{}", e),
+            Error::UnsupportedPayload(pt) => write!(f, "unsupported payload type {}", pt),
+            Error::PayloadTypeInUse(pt) => write!(f, "payload type {} already in use", pt),
+            Error::UnsupportedEncoding(name) => write!(f, "unsupported encoding '{}'", name),
+            Error::Parse(input) => write!(f, "cannot parse '{}'", input),
+            Error::Srtp(e) => write!(f, "{}", e),
+            Error::Stun(e) => write!(f, "{}", e),
+            Error::Turn(e) => write!(f, "{}", e),
+            Error::Framing(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<CodecError> for Error {
+    fn from(e: CodecError) -> Self {
+        Error::Codec(e)
+    }
+}
+
+impl From<SrtpError> for Error {
+    fn from(e: SrtpError) -> Self {
+        Error::Srtp(e)
+    }
+}
+
+impl From<StunError> for Error {
+    fn from(e: StunError) -> Self {
+        Error::Stun(e)
+    }
+}
+
+impl From<TurnError> for Error {
+    fn from(e: TurnError) -> Self {
+        Error::Turn(e)
+    }
+}
+
+impl From<FramingError> for Error {
+    fn from(e: FramingError) -> Self {
+        Error::Framing(e)
+    }
+}
@@ -0,0 +1,188 @@
+// sentiric-rtp-core/src/framing.rs
+
+//! RTP/RTCP over TCP/TLS çerçeveleme (RFC 4571).
+//! Her paketin önüne 16-bit big-endian uzunluk alanı eklenir. Soket işlemleri yapılmaz
+//! (Sans-IO): Uygulama TCP/TLS akışından okuduğu byte parçalarını `StreamDeframer`'a
+//! verir, çıkan tam paketleri `RtpEndpoint`/SRTP/Jitter Buffer hattına iletir.
+
+use std::fmt;
+
+/// RFC 4571 uzunluk alanının boyutu.
+pub const LENGTH_PREFIX_SIZE: usize = 2;
+/// 16-bit uzunluk alanının taşıyabileceği en büyük paket.
+pub const MAX_FRAME_SIZE: usize = u16::MAX as usize;
+/// Varsayılan çerçeve limiti. Ses paketleri MTU'yu aşmaz; daha büyüğü saldırı veya senkron kaybıdır.
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FramingError {
+    /// Paket (veya gelen uzunluk alanı) izin verilen boyutu aşıyor.
+    FrameTooLarge { size: usize, max: usize },
+    /// Akış daha önce bozuldu; deframer `reset` edilmeden kullanılamaz.
+    StreamCorrupted,
+}
+
+impl fmt::Display for FramingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FramingError::FrameTooLarge { size, max } => {
+                write!(f, "RFC 4571 frame too large: {} > {}", size, max)
+            }
+            FramingError::StreamCorrupted => write!(f, "RFC 4571 stream corrupted"),
+        }
+    }
+}
+
+impl std::error::Error for FramingError {}
+
+/// Paketi uzunluk önekiyle birlikte `out` sonuna ekler.
+pub fn encode_frame(packet: &[u8], out: &mut Vec<u8>) -> Result<(), FramingError> {
+    if packet.len() > MAX_FRAME_SIZE {
+        return Err(FramingError::FrameTooLarge {
+            size: packet.len(),
+            max: MAX_FRAME_SIZE,
+        });
+    }
+    out.reserve(LENGTH_PREFIX_SIZE + packet.len());
+    out.extend_from_slice(&(packet.len() as u16).to_be_bytes());
+    out.extend_from_slice(packet);
+    Ok(())
+}
+
+/// Tek paketlik kolaylık fonksiyonu.
+pub fn frame_packet(packet: &[u8]) -> Result<Vec<u8>, FramingError> {
+    let mut out = Vec::with_capacity(LENGTH_PREFIX_SIZE + packet.len());
+    encode_frame(packet, &mut out)?;
+    Ok(out)
+}
+
+/// Akış (stream) tabanlı RFC 4571 çözücü.
+/// Gelen parçalar paket sınırlarıyla hizalı olmak zorunda değildir; bir parça birden çok
+/// paket veya bir paketin sadece birkaç byte'ını içerebilir.
+pub struct StreamDeframer {
+    buffer: Vec<u8>,
+    read_pos: usize,
+    max_frame_size: usize,
+    corrupted: bool,
+}
+
+impl Default for StreamDeframer {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FRAME_SIZE)
+    }
+}
+
+impl StreamDeframer {
+    /// max_frame_size: Kabul edilecek en büyük paket (önek hariç). En fazla 65535.
+    pub fn new(max_frame_size: usize) -> Self {
+        let max_frame_size = max_frame_size.min(MAX_FRAME_SIZE);
+        Self {
+            buffer: Vec::with_capacity(LENGTH_PREFIX_SIZE + max_frame_size),
+            read_pos: 0,
+            max_frame_size,
+            corrupted: false,
+        }
+    }
+
+    /// TCP/TLS akışından okunan ham byte'ları tampona ekler.
+    /// Daha önce tüketilmiş bölge burada sıkıştırılır, böylece tampon sınırsız büyümez.
+    pub fn push(&mut self, chunk: &[u8]) {
+        if self.read_pos > 0 {
+            self.buffer.drain(..self.read_pos);
+            self.read_pos = 0;
+        }
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Tamamlanmış bir sonraki paketi döndürür. Yeterli veri yoksa `Ok(None)`.
+    /// Dönen dilim bir sonraki `push` çağrısına kadar geçerlidir.
+    /// Uzunluk alanı limiti aşarsa akış bozuk kabul edilir (çerçeve senkronu kaybolmuştur).
+    pub fn next_packet(&mut self) -> Result<Option<&[u8]>, FramingError> {
+        if self.corrupted {
+            return Err(FramingError::StreamCorrupted);
+        }
+        loop {
+            let available = &self.buffer[self.read_pos..];
+            if available.len() < LENGTH_PREFIX_SIZE {
+                return Ok(None);
+            }
+            let len = u16::from_be_bytes([available[0], available[1]]) as usize;
+            if len > self.max_frame_size {
+                self.corrupted = true;
+                return Err(FramingError::FrameTooLarge {
+                    size: len,
+                    max: self.max_frame_size,
+                });
+            }
+            if available.len() < LENGTH_PREFIX_SIZE + len {
+                return Ok(None);
+            }
+            let start = self.read_pos + LENGTH_PREFIX_SIZE;
+            self.read_pos = start + len;
+            // Boş çerçeveler (keep-alive) atlanır
+            if len > 0 {
+                return Ok(Some(&self.buffer[start..start + len]));
+            }
+        }
+    }
+
+    /// Henüz tamamlanmamış (tamponda bekleyen) byte sayısı.
+    pub fn pending_bytes(&self) -> usize {
+        self.buffer.len() - self.read_pos
+    }
+
+    /// Yeni bağlantı veya hata sonrası durumu sıfırlar.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.read_pos = 0;
+        self.corrupted = false;
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deframer_handles_arbitrary_chunking() {
+        let packets: Vec<Vec<u8>> = vec![vec![0x80; 172], vec![0x81; 12], vec![0x82; 60]];
+        let mut stream = Vec::new();
+        for p in &packets {
+            encode_frame(p, &mut stream).unwrap();
+        }
+        // Keep-alive (boş çerçeve) araya sıkıştırılır
+        stream.splice(174..174, [0, 0]);
+
+        let mut deframer = StreamDeframer::default();
+        let mut received = Vec::new();
+        for byte in stream.chunks(7) {
+            deframer.push(byte);
+            while let Some(packet) = deframer.next_packet().unwrap() {
+                received.push(packet.to_vec());
+            }
+        }
+        assert_eq!(received, packets);
+        assert_eq!(deframer.pending_bytes(), 0);
+    }
+
+    #[test]
+    fn test_deframer_rejects_oversized_frames() {
+        let mut deframer = StreamDeframer::new(1500);
+        deframer.push(&[0x10, 0x00, 0xAA]);
+        assert_eq!(
+            deframer.next_packet(),
+            Err(FramingError::FrameTooLarge {
+                size: 4096,
+                max: 1500
+            })
+        );
+        assert_eq!(deframer.next_packet(), Err(FramingError::StreamCorrupted));
+
+        deframer.reset();
+        deframer.push(&frame_packet(&[1, 2, 3]).unwrap());
+        assert_eq!(deframer.next_packet(), Ok(Some(&[1u8, 2, 3][..])));
+
+        assert!(frame_packet(&vec![0; MAX_FRAME_SIZE + 1]).is_err());
+    }
+}
@@ -61,6 +61,13 @@ impl JitterBuffer {
     /// Oynatılması gereken paketi döndürür.
     /// Eğer paket henüz gelmediyse veya tamponlama süresi dolmadıysa None döner.
     pub fn pop(&mut self) -> Option<RtpPacket> {
+        self.pop_with_loss().map(|(packet, _)| packet)
+    }
+
+    /// `pop` ile aynıdır; ayrıca bu paketten önce kayıp sayılıp atlanan **paket** sayısını döndürür.
+    /// `Decoder::conceal` örnek sayısı beklediğinden sayı paket başına örnekle çarpılmalıdır:
+    /// `decoder.conceal(lost as usize * codec.samples_per_frame(ptime))`.
+    pub fn pop_with_loss(&mut self) -> Option<(RtpPacket, u16)> {
         if !self.initialized {
             return None;
         }
@@ -75,7 +82,7 @@ impl JitterBuffer {
         // Beklenen paket var mı?
         if let Some(packet) = self.buffer.remove(&self.expected_seq) {
             self.expected_seq = self.expected_seq.wrapping_add(1);
-            return Some(packet);
+            return Some((packet, 0));
         }
 
         // Beklenen paket yok (Packet Loss veya Gecikme).
@@ -87,12 +94,11 @@ impl JitterBuffer {
             // Eğer 5 paketten fazla boşluk varsa veya buffer %50 doluysa atla
             // Bu "Catch-up" (Yaklama) mantığıdır.
             if gap > 5 || self.buffer.len() > (self.max_capacity / 2) {
-                // Kayıp paketi atla
-                // İdealde burada Packet Loss Concealment (PLC) devreye girer.
+                // Kayıp paketi atla; atlanan sayı PLC için çağırana bildirilir.
                 self.expected_seq = next_available_seq;
                 let packet = self.buffer.remove(&next_available_seq);
                 self.expected_seq = self.expected_seq.wrapping_add(1);
-                return packet;
+                return packet.map(|p| (p, gap));
             }
         }
 
@@ -194,4 +200,25 @@ mod tests {
         let p = jb.pop().expect("Should skip to next available");
         assert_eq!(p.header.sequence_number, 106); // 101-105 arasını atladı
     }
+
+    #[test]
+    fn test_pop_with_loss_reports_skipped_packets() {
+        let mut jb = JitterBuffer::new(10, 10);
+        jb.push(create_dummy_packet(100));
+
+        thread::sleep(Duration::from_millis(15));
+        let (p, lost) = jb.pop_with_loss().expect("First packet should be ready");
+        assert_eq!((p.header.sequence_number, lost), (100, 0));
+
+        // 101-105 kayıp; buffer yarıdan fazla dolunca 106'ya atlanır
+        for i in 106..115 {
+            jb.push(create_dummy_packet(i));
+        }
+        let (p, lost) = jb.pop_with_loss().expect("Should skip to next available");
+        assert_eq!((p.header.sequence_number, lost), (106, 5));
+
+        // Sıradaki paket kayıpsız gelir
+        let (p, lost) = jb.pop_with_loss().unwrap();
+        assert_eq!((p.header.sequence_number, lost), (107, 0));
+    }
 }
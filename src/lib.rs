@@ -3,22 +3,38 @@
 pub mod codecs;
 pub mod config;
 pub mod dsp;
+pub mod error;
+pub mod framing;
 pub mod jitter_buffer;
 pub mod net_utils;
 pub mod pacer;
 pub mod rtp;
 pub mod session;
+pub mod srtp;
+pub mod stun;
+pub mod turn;
 pub mod wav;
 
 pub use codecs::{
-    CodecFactory, CodecType, Decoder, Encoder, G729Decoder, G729Encoder, PcmaDecoder, PcmaEncoder,
-    PcmuDecoder, PcmuEncoder,
+    ClearmodeDecoder, ClearmodeEncoder, CodecDescriptor, CodecError, CodecFactory, CodecRegistry,
+    CodecType, ComfortNoiseDecoder, ComfortNoiseEncoder, CustomCodecId, Decoder, Encoder,
+    G722Decoder, G722Encoder, G726Decoder, G726Encoder, GsmDecoder, GsmEncoder, PayloadMapping,
+    PayloadTypeMap, PcmaDecoder, PcmaEncoder, PcmuDecoder, PcmuEncoder,
 };
+#[cfg(feature = "g729")]
+pub use codecs::{G729Decoder, G729Encoder};
+pub use error::Error;
+pub use framing::StreamDeframer;
 pub use jitter_buffer::JitterBuffer;
 pub use pacer::Pacer;
 pub use rtp::{RtcpPacket, RtpHeader, RtpPacket};
-pub use session::RtpEndpoint;
+pub use session::{LatchEvent, LatchPolicy, RtpEndpoint};
+pub use srtp::{
+    CryptoAttribute, DtlsRole, DtlsSrtpKeys, ExtMap, Fingerprint, SetupRole, SrtpContext,
+    SrtpError, SrtpPolicy, SrtpProfile,
+};
+pub use stun::{IceCredentials, StunMessage};
 pub use wav::WavAudio;
 // YENİ: AudioResampler dışarıya açıldı
-pub use config::{AudioProfile, CodecConfig};
+pub use config::{AudioProfile, CodecConfig, OpusFmtp};
 pub use dsp::{simple_resample, AudioResampler};
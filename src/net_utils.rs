@@ -45,3 +45,20 @@ pub fn is_private_ip(ip: IpAddr) -> bool {
 pub fn is_public_ip(ip: IpAddr) -> bool {
     !is_private_ip(ip)
 }
+
+/// `ip`, `network/prefix_len` alt ağında mı? Adres aileleri farklıysa false döner.
+pub fn ip_in_subnet(ip: IpAddr, network: IpAddr, prefix_len: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let prefix = prefix_len.min(32) as u32;
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let prefix = prefix_len.min(128) as u32;
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
@@ -1,55 +1,707 @@
 // sentiric-rtp-core/src/session.rs
 
-use std::net::SocketAddr;
-use std::sync::{Arc, Mutex};
+use crate::net_utils::ip_in_subnet;
+use std::hint::spin_loop;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV6};
+use std::sync::atomic::{fence, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
+/// Symmetric RTP kilitlenme politikası.
+/// Varsayılan değerler medya ele geçirme (RTP hijacking) saldırılarına karşı sıkılaştırılmıştır.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatchPolicy {
+    /// Kilitlenme veya dolaşım (roaming) için aynı adres ve SSRC ile art arda gelmesi
+    /// gereken geçerli RTP paketi sayısı.
+    pub min_consecutive_packets: u32,
+    /// Sadece bu IP adresine kilitlenilir (örn. SDP `c=` satırı).
+    pub allowed_ip: Option<IpAddr>,
+    /// Sadece bu alt ağdan gelen kaynaklara kilitlenilir: (ağ adresi, prefix uzunluğu).
+    pub allowed_subnet: Option<(IpAddr, u8)>,
+    /// Sadece bu kaynak porta kilitlenilir (örn. SDP `m=` portu). RTCP için mux yoksa port + 1.
+    pub allowed_port: Option<u16>,
+    /// Son hedef değişikliğinden sonra yeni bir dolaşıma izin verilmeyen süre.
+    pub roaming_hold_down: Duration,
+}
+
+impl Default for LatchPolicy {
+    fn default() -> Self {
+        Self {
+            min_consecutive_packets: 3,
+            allowed_ip: None,
+            allowed_subnet: None,
+            allowed_port: None,
+            roaming_hold_down: Duration::from_secs(2),
+        }
+    }
+}
+
+impl LatchPolicy {
+    /// Eski davranış: İlk geçerli pakette kilitlenir, her kaynağa anında dolaşır.
+    pub fn permissive() -> Self {
+        Self {
+            min_consecutive_packets: 1,
+            roaming_hold_down: Duration::ZERO,
+            ..Self::default()
+        }
+    }
+
+    /// Kilitlenmeyi SDP'de ilan edilen adres ve porta kısıtlar.
+    pub fn restricted_to(addr: SocketAddr) -> Self {
+        Self {
+            allowed_ip: Some(addr.ip()),
+            allowed_port: Some(addr.port()),
+            ..Self::default()
+        }
+    }
+
+    /// Adres ve alt ağ kısıtları. Port kısıtı RTP/RTCP'ye göre ayrıca uygulanır.
+    fn allows(&self, source: SocketAddr) -> bool {
+        self.allowed_ip.is_none_or(|ip| ip == source.ip())
+            && self
+                .allowed_subnet
+                .is_none_or(|(net, prefix)| ip_in_subnet(source.ip(), net, prefix))
+    }
+}
+
+/// Paketin kilitlenme için reddedilme nedeni.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatchRejection {
+    /// Beklenen türde geçerli bir RTP/RTCP paketi değil.
+    InvalidPacket,
+    /// Kaynak adres politikadaki adres/alt ağ/port kısıtına uymuyor.
+    AddressNotAllowed,
+}
+
+/// `RtpEndpoint::latch` kararının sonucu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatchEvent {
+    /// İlk kez kilitlenildi.
+    Latched(SocketAddr),
+    /// Hedef yeni bir kaynağa taşındı.
+    Roamed {
+        from: SocketAddr,
+        to: SocketAddr,
+    },
+    /// Paket mevcut hedeften geldi, değişiklik yok.
+    Unchanged,
+    /// Aday kaynak henüz yeterli sayıda ardışık paket göndermedi.
+    Pending {
+        candidate: SocketAddr,
+        count: u32,
+        required: u32,
+    },
+    /// Aday doğrulandı ama hold-down süresi dolmadan dolaşıma izin verilmiyor.
+    HoldDown {
+        candidate: SocketAddr,
+        remaining: Duration,
+    },
+    Rejected(LatchRejection),
+}
+
+impl LatchEvent {
+    /// Gönderim hedefi bu kararla değişti mi?
+    pub fn target_changed(&self) -> bool {
+        matches!(self, LatchEvent::Latched(_) | LatchEvent::Roamed { .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LatchState {
+    target: Option<SocketAddr>,
+    latched: bool,
+    /// Son hedef değişikliği (`SharedState::epoch`'tan itibaren nanosaniye).
+    last_change: Option<u64>,
+    /// Doğrulanmakta olan aday: (adres, SSRC, ardışık paket sayısı)
+    candidate: Option<(SocketAddr, u32, u32)>,
+}
+
+const STATE_WORDS: usize = 9;
+
+impl LatchState {
+    fn to_words(self) -> [u64; STATE_WORDS] {
+        let [t0, t1, t2] = encode_addr(self.target);
+        let [c0, c1, c2] = encode_addr(self.candidate.map(|(addr, _, _)| addr));
+        let (ssrc, count) = self.candidate.map_or((0, 0), |(_, s, c)| (s, c));
+        [
+            t0,
+            t1,
+            t2,
+            self.latched as u64,
+            self.last_change.map_or(0, |t| t.saturating_add(1)),
+            c0,
+            c1,
+            c2,
+            ((ssrc as u64) << 32) | count as u64,
+        ]
+    }
+
+    fn from_words(w: &[u64; STATE_WORDS]) -> Self {
+        let candidate =
+            decode_addr([w[5], w[6], w[7]]).map(|addr| (addr, (w[8] >> 32) as u32, w[8] as u32));
+        Self {
+            target: decode_addr([w[0], w[1], w[2]]),
+            latched: w[3] != 0,
+            last_change: w[4].checked_sub(1),
+            candidate,
+        }
+    }
+}
+
+const ADDR_PRESENT: u64 = 1 << 63;
+const ADDR_V6: u64 = 1 << 62;
+
+/// SocketAddr -> 3 kelime: IP'nin üst/alt 64 biti ve (bayraklar | scope_id | port).
+fn encode_addr(addr: Option<SocketAddr>) -> [u64; 3] {
+    match addr {
+        None => [0; 3],
+        Some(SocketAddr::V4(a)) => [0, u32::from(*a.ip()) as u64, ADDR_PRESENT | a.port() as u64],
+        Some(SocketAddr::V6(a)) => {
+            let ip = u128::from(*a.ip());
+            [
+                (ip >> 64) as u64,
+                ip as u64,
+                ADDR_PRESENT | ADDR_V6 | ((a.scope_id() as u64) << 16) | a.port() as u64,
+            ]
+        }
+    }
+}
+
+fn decode_addr(w: [u64; 3]) -> Option<SocketAddr> {
+    if w[2] & ADDR_PRESENT == 0 {
+        return None;
+    }
+    let port = w[2] as u16;
+    if w[2] & ADDR_V6 != 0 {
+        let ip = Ipv6Addr::from(((w[0] as u128) << 64) | w[1] as u128);
+        Some(SocketAddr::V6(SocketAddrV6::new(
+            ip,
+            port,
+            0,
+            (w[2] >> 16) as u32,
+        )))
+    } else {
+        Some(SocketAddr::new(
+            IpAddr::V4(Ipv4Addr::from(w[1] as u32)),
+            port,
+        ))
+    }
+}
+
+/// Seqlock ile korunan paylaşımlı durum.
+/// Okuyucular (gönderim yolu) hiçbir zaman bloklanmaz ve kilit almaz; yazma sırasında
+/// okudukları anlık görüntü tutarsızsa tekrar okurlar. Yazıcılar (latch) `seq` üzerinde
+/// CAS ile sıraya girer. Mutex olmadığı için zehirlenme (poisoning) de yoktur.
+#[derive(Debug)]
+struct SharedState {
+    seq: AtomicU64,
+    words: [AtomicU64; STATE_WORDS],
+    epoch: Instant,
+}
+
+impl SharedState {
+    fn new(state: LatchState) -> Self {
+        Self {
+            seq: AtomicU64::new(0),
+            words: state.to_words().map(AtomicU64::new),
+            epoch: Instant::now(),
+        }
+    }
+
+    fn load(&self) -> LatchState {
+        loop {
+            let before = self.seq.load(Ordering::Acquire);
+            if before & 1 == 0 {
+                let words = std::array::from_fn(|i| self.words[i].load(Ordering::Relaxed));
+                fence(Ordering::Acquire);
+                if self.seq.load(Ordering::Relaxed) == before {
+                    return LatchState::from_words(&words);
+                }
+            }
+            spin_loop();
+        }
+    }
+
+    /// Durumu yazıcı olarak günceller; `f` dönüşünde yeni durum tek seferde yayınlanır.
+    fn update<R>(&self, f: impl FnOnce(&mut LatchState) -> R) -> R {
+        let mut seq = self.seq.load(Ordering::Relaxed);
+        loop {
+            if seq & 1 == 0 {
+                match self.seq.compare_exchange_weak(
+                    seq,
+                    seq + 1,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => break,
+                    Err(current) => seq = current,
+                }
+            } else {
+                spin_loop();
+                seq = self.seq.load(Ordering::Relaxed);
+            }
+        }
+        fence(Ordering::Release);
+
+        // `f` panik yapsa bile sıra numarası serbest bırakılır, okuyucular takılmaz.
+        struct Release<'a>(&'a AtomicU64, u64);
+        impl Drop for Release<'_> {
+            fn drop(&mut self) {
+                self.0.store(self.1, Ordering::Release);
+            }
+        }
+        let _release = Release(&self.seq, seq + 2);
+
+        let current = std::array::from_fn(|i| self.words[i].load(Ordering::Relaxed));
+        let mut state = LatchState::from_words(&current);
+        let result = f(&mut state);
+        for (slot, word) in self.words.iter().zip(state.to_words()) {
+            slot.store(word, Ordering::Relaxed);
+        }
+        result
+    }
+
+    fn nanos_since_epoch(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.epoch)
+            .as_nanos()
+            .min(u64::MAX as u128 - 1) as u64
+    }
+}
+
 /// RtpEndpoint: Dinamik hedef kilitlenme mantığı (Symmetric RTP).
+/// RTP ve RTCP hedefleri ayrı ayrı kilitlenir: NAT arkasında RTCP, RTP portu + 1'den
+/// veya tamamen farklı bir eşlemeden gelebilir.
+/// Klonlar aynı durumu paylaşır; hedef okumaları kilitsizdir ve gönderim döngüsünde güvenle çağrılabilir.
 #[derive(Debug, Clone)]
 pub struct RtpEndpoint {
-    target_addr: Arc<Mutex<Option<SocketAddr>>>,
-    is_latched: Arc<Mutex<bool>>,
+    state: Arc<SharedState>,
+    rtcp_state: Arc<SharedState>,
+    policy: Arc<LatchPolicy>,
+    rtcp_mux: bool,
+    /// `a=rtcp:` ile ilan edilen adres; port kısıtı RTCP için bu adresin portuna uygulanır.
+    rtcp_target: Option<SocketAddr>,
 }
 
 impl RtpEndpoint {
+    /// İlk geçerli pakette kilitlenen (eski davranış) uç nokta oluşturur.
+    /// Ardışık paket ve bekleme süresi korumaları için `with_policy` kullanın.
     pub fn new(initial_target: Option<SocketAddr>) -> Self {
+        Self::with_policy(initial_target, LatchPolicy::permissive())
+    }
+
+    pub fn with_policy(initial_target: Option<SocketAddr>, policy: LatchPolicy) -> Self {
         Self {
-            target_addr: Arc::new(Mutex::new(initial_target)),
-            is_latched: Arc::new(Mutex::new(false)),
+            state: Arc::new(SharedState::new(LatchState {
+                target: initial_target,
+                ..LatchState::default()
+            })),
+            rtcp_state: Arc::new(SharedState::new(LatchState::default())),
+            policy: Arc::new(policy),
+            rtcp_mux: false,
+            rtcp_target: None,
         }
     }
 
-    /// Gelen paketin adresine kilitlenir.
+    /// RFC 5761 `a=rtcp-mux` müzakere edildiyse RTCP, RTP ile aynı adrese gönderilir.
+    pub fn with_rtcp_mux(mut self, rtcp_mux: bool) -> Self {
+        self.rtcp_mux = rtcp_mux;
+        self
+    }
+
+    /// RFC 3605 `a=rtcp:` ile ilan edilen RTCP adresi (kilitlenene kadar kullanılır).
+    /// Port kısıtlı politikada RTCP bu adresin portundan kabul edilir.
+    pub fn with_rtcp_target(mut self, rtcp_target: Option<SocketAddr>) -> Self {
+        self.rtcp_state.update(|state| state.target = rtcp_target);
+        self.rtcp_target = rtcp_target;
+        self
+    }
+
+    pub fn policy(&self) -> &LatchPolicy {
+        &self.policy
+    }
+
+    pub fn is_rtcp_mux(&self) -> bool {
+        self.rtcp_mux
+    }
+
+    /// Gelen RTP paketinin kaynağına kilitlenmeyi dener.
     /// Docker ve NAT senaryolarında Master otoritedir.
-    pub fn latch(&self, source_addr: SocketAddr) -> bool {
-        let mut latched = self.is_latched.lock().unwrap();
-        let mut target = self.target_addr.lock().unwrap();
+    pub fn latch(&self, source_addr: SocketAddr, packet: &[u8]) -> LatchEvent {
+        self.latch_at(source_addr, packet, Instant::now())
+    }
 
-        // Zaten aynı adrese kilitliysek bir şey yapma
-        if *latched && *target == Some(source_addr) {
-            return false;
+    /// `latch` ile aynıdır; zaman dışarıdan verilir (Sans-IO / test).
+    pub fn latch_at(&self, source_addr: SocketAddr, packet: &[u8], now: Instant) -> LatchEvent {
+        let Some(ssrc) = rtp_ssrc(packet) else {
+            return LatchEvent::Rejected(LatchRejection::InvalidPacket);
+        };
+        let port = self.policy.allowed_port;
+        self.latch_on(&self.state, source_addr, ssrc, port, now, "LATCH")
+    }
+
+    /// Gelen RTCP paketinin kaynağına RTP'den bağımsız olarak kilitlenir.
+    /// Aynı politika uygulanır; port kısıtı `a=rtcp` portu, mux varsa RTP portu,
+    /// aksi halde `allowed_port + 1` olarak yorumlanır.
+    pub fn latch_rtcp(&self, source_addr: SocketAddr, packet: &[u8]) -> LatchEvent {
+        self.latch_rtcp_at(source_addr, packet, Instant::now())
+    }
+
+    pub fn latch_rtcp_at(
+        &self,
+        source_addr: SocketAddr,
+        packet: &[u8],
+        now: Instant,
+    ) -> LatchEvent {
+        let Some(ssrc) = rtcp_ssrc(packet) else {
+            return LatchEvent::Rejected(LatchRejection::InvalidPacket);
+        };
+        let port = self.policy.allowed_port.map(|p| match self.rtcp_target {
+            Some(rtcp_target) => rtcp_target.port(),
+            None if self.rtcp_mux => p,
+            None => p.wrapping_add(1),
+        });
+        self.latch_on(&self.rtcp_state, source_addr, ssrc, port, now, "RTCP LATCH")
+    }
+
+    fn latch_on(
+        &self,
+        shared: &SharedState,
+        source_addr: SocketAddr,
+        ssrc: u32,
+        allowed_port: Option<u16>,
+        now: Instant,
+        label: &str,
+    ) -> LatchEvent {
+        if !self.policy.allows(source_addr)
+            || allowed_port.is_some_and(|port| port != source_addr.port())
+        {
+            debug!(
+                "⛔ [{}] İzin verilmeyen kaynak reddedildi: {}",
+                label, source_addr
+            );
+            return LatchEvent::Rejected(LatchRejection::AddressNotAllowed);
         }
 
-        // Kilitlenme (Latching)
-        if !*latched {
-            info!("🔒 [LATCH] Medya hedefi kilitlendi: {}", source_addr);
-        } else {
-            debug!("🔄 [ROAMING] Medya hedefi güncellendi: {}", source_addr);
+        // Hızlı yol: Kilitli hedeften gelen paketler yazıcı sırasına girmez.
+        let snapshot = shared.load();
+        if snapshot.latched && snapshot.target == Some(source_addr) && snapshot.candidate.is_none()
+        {
+            return LatchEvent::Unchanged;
         }
 
-        *target = Some(source_addr);
-        *latched = true;
-        true
+        let now = shared.nanos_since_epoch(now);
+        let event = shared.update(|state| decide(state, &self.policy, source_addr, ssrc, now));
+
+        match event {
+            LatchEvent::Latched(addr) => {
+                info!("🔒 [{}] Medya hedefi kilitlendi: {}", label, addr)
+            }
+            LatchEvent::Roamed { from, to } => {
+                debug!(
+                    "🔄 [{}] Medya hedefi güncellendi: {} -> {}",
+                    label, from, to
+                )
+            }
+            _ => {}
+        }
+        event
     }
 
+    /// RTP gönderim hedefi.
     pub fn get_target(&self) -> Option<SocketAddr> {
-        *self.target_addr.lock().unwrap()
+        self.state.load().target
     }
 
+    /// RTCP gönderim hedefi. Öncelik sırası: kilitlenilen RTCP kaynağı, `a=rtcp` adresi,
+    /// RTP hedefi (mux) veya RTP hedefinin portu + 1.
+    pub fn get_rtcp_target(&self) -> Option<SocketAddr> {
+        if let Some(target) = self.rtcp_state.load().target {
+            return Some(target);
+        }
+        let mut target = self.get_target()?;
+        if !self.rtcp_mux {
+            target.set_port(target.port().checked_add(1)?);
+        }
+        Some(target)
+    }
+
+    pub fn is_latched(&self) -> bool {
+        self.state.load().latched
+    }
+
+    pub fn is_rtcp_latched(&self) -> bool {
+        self.rtcp_state.load().latched
+    }
+
+    /// RTP ve RTCP kilitlerini sıfırlar. RTP hedefi unutulur; RTCP hedefi `a=rtcp` ile
+    /// ilan edilen adrese döner ve port kısıtı bu adrese uygulanmaya devam eder.
     pub fn reset(&self) {
-        let mut latched = self.is_latched.lock().unwrap();
-        let mut target = self.target_addr.lock().unwrap();
-        *target = None;
-        *latched = false;
+        self.state.update(|state| *state = LatchState::default());
+        let rtcp_target = self.rtcp_target;
+        self.rtcp_state.update(|state| {
+            *state = LatchState {
+                target: rtcp_target,
+                ..LatchState::default()
+            }
+        });
+    }
+}
+
+fn decide(
+    state: &mut LatchState,
+    policy: &LatchPolicy,
+    source_addr: SocketAddr,
+    ssrc: u32,
+    now: u64,
+) -> LatchEvent {
+    // Zaten aynı adrese kilitliysek bir şey yapma
+    if state.latched && state.target == Some(source_addr) {
+        state.candidate = None;
+        return LatchEvent::Unchanged;
+    }
+
+    let count = match state.candidate {
+        Some((addr, cand_ssrc, count)) if addr == source_addr && cand_ssrc == ssrc => {
+            count.saturating_add(1)
+        }
+        _ => 1,
+    };
+    state.candidate = Some((source_addr, ssrc, count));
+
+    let required = policy.min_consecutive_packets.max(1);
+    if count < required {
+        return LatchEvent::Pending {
+            candidate: source_addr,
+            count,
+            required,
+        };
+    }
+
+    let event = match (state.latched, state.target) {
+        (true, Some(from)) => {
+            let elapsed = state.last_change.map_or(Duration::MAX, |t| {
+                Duration::from_nanos(now.saturating_sub(t))
+            });
+            if elapsed < policy.roaming_hold_down {
+                return LatchEvent::HoldDown {
+                    candidate: source_addr,
+                    remaining: policy.roaming_hold_down - elapsed,
+                };
+            }
+            LatchEvent::Roamed {
+                from,
+                to: source_addr,
+            }
+        }
+        _ => LatchEvent::Latched(source_addr),
+    };
+
+    state.target = Some(source_addr);
+    state.latched = true;
+    state.last_change = Some(now);
+    state.candidate = None;
+    event
+}
+
+/// Geçerli bir RTP paketinin SSRC'si. RTCP (PT 72-76, RFC 5761) ve sürümü 2 olmayan
+/// paketler kilitlenmeye katılmaz.
+fn rtp_ssrc(packet: &[u8]) -> Option<u32> {
+    if packet.len() < 12 || packet[0] >> 6 != 2 {
+        return None;
+    }
+    if (72..=76).contains(&(packet[1] & 0x7F)) {
+        return None;
+    }
+    Some(u32::from_be_bytes([
+        packet[8], packet[9], packet[10], packet[11],
+    ]))
+}
+
+/// Geçerli bir RTCP paketinin (bileşik paketin ilk parçası) gönderici SSRC'si (PT 200-206).
+fn rtcp_ssrc(packet: &[u8]) -> Option<u32> {
+    if packet.len() < 8 || packet[0] >> 6 != 2 || !(200..=206).contains(&packet[1]) {
+        return None;
+    }
+    Some(u32::from_be_bytes([
+        packet[4], packet[5], packet[6], packet[7],
+    ]))
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rtp(ssrc: u32) -> [u8; 12] {
+        let mut p = [0u8; 12];
+        p[0] = 0x80;
+        p[8..12].copy_from_slice(&ssrc.to_be_bytes());
+        p
+    }
+
+    #[test]
+    fn test_latch_requires_consecutive_packets_with_same_ssrc() {
+        let a: SocketAddr = "203.0.113.5:4000".parse().unwrap();
+        let b: SocketAddr = "198.51.100.9:5000".parse().unwrap();
+        let ep = RtpEndpoint::with_policy(None, LatchPolicy::default());
+        let now = Instant::now();
+
+        assert!(matches!(
+            ep.latch_at(a, &rtp(1), now),
+            LatchEvent::Pending { count: 1, .. }
+        ));
+        // Araya giren başka bir kaynak/SSRC sayacı sıfırlar
+        assert!(matches!(
+            ep.latch_at(a, &rtp(2), now),
+            LatchEvent::Pending { count: 1, .. }
+        ));
+        ep.latch_at(a, &rtp(2), now);
+        assert_eq!(ep.latch_at(a, &rtp(2), now), LatchEvent::Latched(a));
+        assert_eq!(ep.get_target(), Some(a));
+        assert_eq!(ep.latch_at(a, &rtp(2), now), LatchEvent::Unchanged);
+
+        // Hold-down süresince dolaşım yok, süre dolunca aday doğrudan taşınır
+        for _ in 0..3 {
+            ep.latch_at(b, &rtp(7), now + Duration::from_millis(500));
+        }
+        assert_eq!(
+            ep.latch_at(b, &rtp(7), now + Duration::from_millis(500)),
+            LatchEvent::HoldDown {
+                candidate: b,
+                remaining: Duration::from_millis(1500)
+            }
+        );
+        assert_eq!(ep.get_target(), Some(a));
+        assert_eq!(
+            ep.latch_at(b, &rtp(7), now + Duration::from_secs(3)),
+            LatchEvent::Roamed { from: a, to: b }
+        );
+        assert_eq!(
+            ep.latch(b, &[0x80, 0xC8, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0]),
+            LatchEvent::Rejected(LatchRejection::InvalidPacket)
+        );
+    }
+
+    #[test]
+    fn test_new_endpoint_latches_immediately() {
+        let a: SocketAddr = "203.0.113.5:4000".parse().unwrap();
+        let b: SocketAddr = "198.51.100.9:5000".parse().unwrap();
+        let ep = RtpEndpoint::new(None);
+        assert_eq!(ep.latch(a, &rtp(1)), LatchEvent::Latched(a));
+        assert_eq!(ep.latch(b, &rtp(2)), LatchEvent::Roamed { from: a, to: b });
+    }
+
+    #[test]
+    fn test_latch_policy_address_restrictions() {
+        let sdp: SocketAddr = "10.1.2.3:30000".parse().unwrap();
+        let ep = RtpEndpoint::with_policy(Some(sdp), LatchPolicy::restricted_to(sdp));
+        assert_eq!(
+            ep.latch("10.1.2.3:30002".parse().unwrap(), &rtp(1)),
+            LatchEvent::Rejected(LatchRejection::AddressNotAllowed)
+        );
+
+        let policy = LatchPolicy {
+            allowed_subnet: Some(("192.168.0.0".parse().unwrap(), 16)),
+            ..LatchPolicy::permissive()
+        };
+        let ep = RtpEndpoint::with_policy(None, policy);
+        assert_eq!(
+            ep.latch("192.169.0.1:4000".parse().unwrap(), &rtp(1)),
+            LatchEvent::Rejected(LatchRejection::AddressNotAllowed)
+        );
+        let inside: SocketAddr = "192.168.44.1:4000".parse().unwrap();
+        assert_eq!(ep.latch(inside, &rtp(1)), LatchEvent::Latched(inside));
+        ep.reset();
+        assert_eq!(ep.get_target(), None);
+        assert!(!ep.is_latched());
+    }
+
+    #[test]
+    fn test_endpoint_state_is_shared_and_lock_free() {
+        let v6: SocketAddr = "[2001:db8::1%3]:40000".parse().unwrap();
+        let ep = RtpEndpoint::with_policy(None, LatchPolicy::permissive());
+        let sender = ep.clone();
+        assert_eq!(ep.latch(v6, &rtp(9)), LatchEvent::Latched(v6));
+        assert_eq!(sender.get_target(), Some(v6));
+
+        let a: SocketAddr = "203.0.113.5:4000".parse().unwrap();
+        let b: SocketAddr = "203.0.113.6:4002".parse().unwrap();
+        let reader = ep.clone();
+        let handle = std::thread::spawn(move || {
+            // Okuyucu hiçbir zaman yarım yazılmış bir adres görmemeli
+            for _ in 0..20_000 {
+                let target = reader.get_target();
+                assert!(target == Some(a) || target == Some(b) || target == Some(v6));
+            }
+        });
+        for i in 0..20_000 {
+            ep.latch(if i % 2 == 0 { a } else { b }, &rtp(i));
+        }
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_rtcp_target_is_tracked_separately() {
+        let sdp: SocketAddr = "10.1.2.3:30000".parse().unwrap();
+        let sr = [0x80u8, 200, 0, 6, 0, 0, 0, 5];
+        let ep = RtpEndpoint::with_policy(Some(sdp), LatchPolicy::permissive());
+        assert_eq!(
+            ep.get_rtcp_target(),
+            Some("10.1.2.3:30001".parse().unwrap())
+        );
+
+        // NAT arkasında RTCP farklı bir eşlemeden gelir
+        let rtp_src: SocketAddr = "203.0.113.5:41000".parse().unwrap();
+        let rtcp_src: SocketAddr = "203.0.113.5:52011".parse().unwrap();
+        assert_eq!(ep.latch(rtp_src, &rtp(5)), LatchEvent::Latched(rtp_src));
+        assert_eq!(
+            ep.get_rtcp_target(),
+            Some("203.0.113.5:41001".parse().unwrap())
+        );
+        assert_eq!(
+            ep.latch_rtcp(rtcp_src, &rtp(5)),
+            LatchEvent::Rejected(LatchRejection::InvalidPacket)
+        );
+        assert_eq!(ep.latch_rtcp(rtcp_src, &sr), LatchEvent::Latched(rtcp_src));
+        assert!(ep.is_rtcp_latched());
+        assert_eq!(ep.get_target(), Some(rtp_src));
+        assert_eq!(ep.get_rtcp_target(), Some(rtcp_src));
+
+        let muxed = RtpEndpoint::with_policy(Some(sdp), LatchPolicy::restricted_to(sdp))
+            .with_rtcp_mux(true);
+        assert_eq!(muxed.get_rtcp_target(), Some(sdp));
+        let explicit: SocketAddr = "10.1.2.3:30009".parse().unwrap();
+        let ep = RtpEndpoint::with_policy(Some(sdp), LatchPolicy::restricted_to(sdp))
+            .with_rtcp_target(Some(explicit));
+        assert_eq!(ep.get_rtcp_target(), Some(explicit));
+        // Port kısıtı RTCP için `a=rtcp` ile ilan edilen porta uygulanır
+        assert!(matches!(
+            ep.latch_rtcp(explicit, &sr),
+            LatchEvent::Pending { .. } | LatchEvent::Latched(_)
+        ));
+        assert_eq!(
+            ep.latch_rtcp("10.1.2.3:30001".parse().unwrap(), &sr),
+            LatchEvent::Rejected(LatchRejection::AddressNotAllowed)
+        );
+        // Sıfırlama yalnızca kilidi temizler; `a=rtcp` hedefi ve port kısıtı korunur
+        ep.reset();
+        assert!(!ep.is_rtcp_latched());
+        assert_eq!(ep.get_rtcp_target(), Some(explicit));
+        assert_eq!(
+            ep.latch_rtcp("10.1.2.3:30001".parse().unwrap(), &sr),
+            LatchEvent::Rejected(LatchRejection::AddressNotAllowed)
+        );
+
+        // `a=rtcp` yoksa port + 1
+        let ep = RtpEndpoint::with_policy(Some(sdp), LatchPolicy::restricted_to(sdp));
+        assert_eq!(
+            ep.latch_rtcp(explicit, &sr),
+            LatchEvent::Rejected(LatchRejection::AddressNotAllowed)
+        );
+        assert!(matches!(
+            ep.latch_rtcp("10.1.2.3:30001".parse().unwrap(), &sr),
+            LatchEvent::Pending { .. }
+        ));
     }
 }
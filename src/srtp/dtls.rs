@@ -0,0 +1,335 @@
+// sentiric-rtp-core/src/srtp/dtls.rs
+
+//! DTLS-SRTP (RFC 5764) anahtar materyali dönüşümü ve SDP yardımcıları.
+//! DTLS el sıkışması burada YAPILMAZ (I/O yasağı). Uygulama, el sıkışma sonrası
+//! `EXTRACTOR-dtls_srtp` etiketiyle dışa aktarılan materyali ve müzakere edilen
+//! `use_srtp` profil kimliğini bu modüle verir.
+
+use super::{SrtpContext, SrtpError, SrtpPolicy, SrtpProfile};
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use std::fmt;
+
+/// RFC 5764 Bölüm 4.2: TLS exporter etiketi.
+pub const DTLS_SRTP_EXPORTER_LABEL: &str = "EXTRACTOR-dtls_srtp";
+
+impl SrtpProfile {
+    /// RFC 5764 `use_srtp` uzantısındaki SRTPProtectionProfile kimliği.
+    /// AES-192/256 süitleri için IANA kaydı yoktur.
+    pub fn dtls_profile_id(&self) -> Option<u16> {
+        match self {
+            SrtpProfile::AesCm128HmacSha1_80 => Some(0x0001),
+            SrtpProfile::AesCm128HmacSha1_32 => Some(0x0002),
+            _ => None,
+        }
+    }
+
+    pub fn from_dtls_profile_id(id: u16) -> Option<Self> {
+        match id {
+            0x0001 => Some(SrtpProfile::AesCm128HmacSha1_80),
+            0x0002 => Some(SrtpProfile::AesCm128HmacSha1_32),
+            _ => None,
+        }
+    }
+}
+
+/// DTLS el sıkışmasındaki rolümüz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DtlsRole {
+    Client,
+    Server,
+}
+
+/// Exporter çıktısından ayrıştırılmış istemci/sunucu yazma anahtarları.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DtlsSrtpKeys {
+    pub profile: SrtpProfile,
+    pub client_write_key: Vec<u8>,
+    pub server_write_key: Vec<u8>,
+    pub client_write_salt: Vec<u8>,
+    pub server_write_salt: Vec<u8>,
+}
+
+impl fmt::Debug for DtlsSrtpKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DtlsSrtpKeys")
+            .field("profile", &self.profile)
+            .finish()
+    }
+}
+
+impl DtlsSrtpKeys {
+    /// Exporter'dan istenmesi gereken materyal uzunluğu: 2 * (key + salt).
+    pub fn keying_material_len(profile: SrtpProfile) -> usize {
+        2 * (profile.master_key_len() + profile.master_salt_len())
+    }
+
+    /// RFC 5764 Bölüm 4.2 düzeni:
+    /// client_write_key | server_write_key | client_write_salt | server_write_salt
+    pub fn from_keying_material(material: &[u8], profile_id: u16) -> Result<Self, SrtpError> {
+        let profile = SrtpProfile::from_dtls_profile_id(profile_id)
+            .ok_or_else(|| SrtpError::UnsupportedProfile(format!("0x{:04X}", profile_id)))?;
+        if material.len() != Self::keying_material_len(profile) {
+            return Err(SrtpError::InvalidKeyLength);
+        }
+
+        let key_len = profile.master_key_len();
+        let salt_len = profile.master_salt_len();
+        let (client_write_key, rest) = material.split_at(key_len);
+        let (server_write_key, rest) = rest.split_at(key_len);
+        let (client_write_salt, server_write_salt) = rest.split_at(salt_len);
+
+        Ok(Self {
+            profile,
+            client_write_key: client_write_key.to_vec(),
+            server_write_key: server_write_key.to_vec(),
+            client_write_salt: client_write_salt.to_vec(),
+            server_write_salt: server_write_salt.to_vec(),
+        })
+    }
+
+    /// (gönderim, alım) politikalarını döndürür. İstemci kendi anahtarıyla şifreler,
+    /// sunucunun anahtarıyla çözer; sunucu için tersi geçerlidir.
+    pub fn policies(&self, role: DtlsRole) -> (SrtpPolicy, SrtpPolicy) {
+        let client = SrtpPolicy::new(
+            self.profile,
+            &self.client_write_key,
+            &self.client_write_salt,
+        );
+        let server = SrtpPolicy::new(
+            self.profile,
+            &self.server_write_key,
+            &self.server_write_salt,
+        );
+        match role {
+            DtlsRole::Client => (client, server),
+            DtlsRole::Server => (server, client),
+        }
+    }
+
+    /// (gönderim, alım) SRTP context'lerini kurar.
+    pub fn into_contexts(self, role: DtlsRole) -> Result<(SrtpContext, SrtpContext), SrtpError> {
+        let (outbound, inbound) = self.policies(role);
+        Ok((SrtpContext::new(outbound)?, SrtpContext::new(inbound)?))
+    }
+}
+
+/// `a=fingerprint` hash fonksiyonları (RFC 8122).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FingerprintHash {
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl FingerprintHash {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FingerprintHash::Sha1 => "sha-1",
+            FingerprintHash::Sha224 => "sha-224",
+            FingerprintHash::Sha256 => "sha-256",
+            FingerprintHash::Sha384 => "sha-384",
+            FingerprintHash::Sha512 => "sha-512",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sha-1" => Some(FingerprintHash::Sha1),
+            "sha-224" => Some(FingerprintHash::Sha224),
+            "sha-256" => Some(FingerprintHash::Sha256),
+            "sha-384" => Some(FingerprintHash::Sha384),
+            "sha-512" => Some(FingerprintHash::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            FingerprintHash::Sha1 => Sha1::digest(data).to_vec(),
+            FingerprintHash::Sha224 => Sha224::digest(data).to_vec(),
+            FingerprintHash::Sha256 => Sha256::digest(data).to_vec(),
+            FingerprintHash::Sha384 => Sha384::digest(data).to_vec(),
+            FingerprintHash::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// SDP `a=fingerprint:<hash> <XX:XX:...>` özniteliği.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingerprint {
+    pub hash: FingerprintHash,
+    pub value: Vec<u8>,
+}
+
+impl Fingerprint {
+    /// DER kodlu sertifikanın parmak izini hesaplar.
+    pub fn from_certificate_der(hash: FingerprintHash, der: &[u8]) -> Self {
+        Self {
+            hash,
+            value: hash.digest(der),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, SrtpError> {
+        let value = line.trim();
+        let value = value.strip_prefix("a=").unwrap_or(value);
+        let value = value.strip_prefix("fingerprint:").unwrap_or(value);
+
+        let (hash_name, hex) = value
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| SrtpError::Parse(format!("invalid fingerprint: {}", line)))?;
+        let hash = FingerprintHash::from_name(hash_name).ok_or_else(|| {
+            SrtpError::Parse(format!("unsupported fingerprint hash: {}", hash_name))
+        })?;
+        let bytes = hex
+            .trim()
+            .split(':')
+            .map(|b| u8::from_str_radix(b, 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| SrtpError::Parse(format!("invalid fingerprint value: {}", hex)))?;
+        if bytes.len() != hash.digest(&[]).len() {
+            return Err(SrtpError::Parse(format!(
+                "fingerprint length mismatch for {}",
+                hash.name()
+            )));
+        }
+        Ok(Self { hash, value: bytes })
+    }
+
+    /// Karşı tarafın DTLS sertifikasının SDP'de ilan edilen parmak izine uyup uymadığını kontrol eder.
+    pub fn verify(&self, der: &[u8]) -> bool {
+        super::constant_time_eq(&self.hash.digest(der), &self.value)
+    }
+
+    pub fn to_sdp_line(&self) -> String {
+        format!("a=fingerprint:{}", self)
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.hash.name())?;
+        for (i, b) in self.value.iter().enumerate() {
+            if i > 0 {
+                write!(f, ":")?;
+            }
+            write!(f, "{:02X}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// SDP `a=setup` özniteliği (RFC 4145 / RFC 5763).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupRole {
+    Active,
+    Passive,
+    ActPass,
+    HoldConn,
+}
+
+impl SetupRole {
+    pub fn parse(line: &str) -> Result<Self, SrtpError> {
+        let value = line.trim();
+        let value = value.strip_prefix("a=").unwrap_or(value);
+        let value = value.strip_prefix("setup:").unwrap_or(value);
+        match value.trim() {
+            "active" => Ok(SetupRole::Active),
+            "passive" => Ok(SetupRole::Passive),
+            "actpass" => Ok(SetupRole::ActPass),
+            "holdconn" => Ok(SetupRole::HoldConn),
+            other => Err(SrtpError::Parse(format!("invalid setup role: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SetupRole::Active => "active",
+            SetupRole::Passive => "passive",
+            SetupRole::ActPass => "actpass",
+            SetupRole::HoldConn => "holdconn",
+        }
+    }
+
+    /// RFC 5763 Bölüm 5: Cevap veren taraf `actpass` teklifine `active` ile yanıt verir.
+    pub fn answer_to(offer: SetupRole) -> SetupRole {
+        match offer {
+            SetupRole::Active => SetupRole::Passive,
+            SetupRole::Passive | SetupRole::ActPass => SetupRole::Active,
+            SetupRole::HoldConn => SetupRole::HoldConn,
+        }
+    }
+
+    /// Kesinleşmiş `setup` değerinin DTLS rolüne karşılığı (active = istemci).
+    /// `actpass` ve `holdconn` henüz rol belirtmez.
+    pub fn dtls_role(&self) -> Option<DtlsRole> {
+        match self {
+            SetupRole::Active => Some(DtlsRole::Client),
+            SetupRole::Passive => Some(DtlsRole::Server),
+            SetupRole::ActPass | SetupRole::HoldConn => None,
+        }
+    }
+
+    pub fn to_sdp_line(&self) -> String {
+        format!("a=setup:{}", self.as_str())
+    }
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtp::{RtpHeader, RtpPacket};
+
+    #[test]
+    fn test_keying_material_split_and_contexts() {
+        let material: Vec<u8> = (0..60u8).collect();
+        let keys = DtlsSrtpKeys::from_keying_material(&material, 0x0001).unwrap();
+        assert_eq!(keys.client_write_key, (0..16u8).collect::<Vec<_>>());
+        assert_eq!(keys.server_write_key, (16..32u8).collect::<Vec<_>>());
+        assert_eq!(keys.client_write_salt, (32..46u8).collect::<Vec<_>>());
+        assert_eq!(keys.server_write_salt, (46..60u8).collect::<Vec<_>>());
+
+        // İstemcinin gönderdiğini sunucu çözebilmeli
+        let (mut client_tx, _) = keys.clone().into_contexts(DtlsRole::Client).unwrap();
+        let (_, mut server_rx) = keys.into_contexts(DtlsRole::Server).unwrap();
+        let plain = RtpPacket {
+            header: RtpHeader::new(111, 1, 960, 42),
+            payload: vec![7; 40],
+        }
+        .to_bytes();
+        let mut packet = plain.clone();
+        client_tx.protect_rtp(&mut packet).unwrap();
+        server_rx.unprotect_rtp(&mut packet).unwrap();
+        assert_eq!(packet, plain);
+
+        assert!(matches!(
+            DtlsSrtpKeys::from_keying_material(&material, 0x0007),
+            Err(SrtpError::UnsupportedProfile(_))
+        ));
+        assert_eq!(
+            DtlsSrtpKeys::from_keying_material(&material[..59], 0x0001),
+            Err(SrtpError::InvalidKeyLength)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_and_setup_attributes() {
+        let cert = b"dummy certificate der";
+        let fp = Fingerprint::from_certificate_der(FingerprintHash::Sha256, cert);
+        let parsed = Fingerprint::parse(&fp.to_sdp_line()).unwrap();
+        assert_eq!(parsed, fp);
+        assert!(parsed.verify(cert));
+        assert!(!parsed.verify(b"other certificate"));
+        assert!(Fingerprint::parse("a=fingerprint:sha-256 AB:CD").is_err());
+
+        let offer = SetupRole::parse("a=setup:actpass").unwrap();
+        assert_eq!(offer.dtls_role(), None);
+        let answer = SetupRole::answer_to(offer);
+        assert_eq!(answer.to_sdp_line(), "a=setup:active");
+        assert_eq!(answer.dtls_role(), Some(DtlsRole::Client));
+    }
+}
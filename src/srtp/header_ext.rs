@@ -0,0 +1,284 @@
+// sentiric-rtp-core/src/srtp/header_ext.rs
+
+//! Şifreli RTP başlık uzantıları.
+//! - RFC 6904: `a=extmap` ile seçilen uzantı elemanlarının verisi ayrı bir anahtarla şifrelenir.
+//! - RFC 9335 (cryptex): CSRC listesi ve tüm uzantı bloğu payload anahtar akışıyla şifrelenir.
+
+use super::SrtpError;
+use std::ops::Range;
+
+/// RFC 6904 Bölüm 4: Şifreli uzantıyı işaretleyen SDP URI'si.
+pub const ENCRYPT_URI: &str = "urn:ietf:params:rtp-hdrext:encrypt";
+/// RFC 9335 Bölüm 6: Cryptex desteğini ilan eden SDP özniteliği.
+pub const CRYPTEX_SDP_ATTRIBUTE: &str = "a=cryptex";
+
+const PROFILE_ONE_BYTE: u16 = 0xBEDE;
+const PROFILE_TWO_BYTE: u16 = 0x1000;
+const PROFILE_TWO_BYTE_MASK: u16 = 0xFFF0;
+const PROFILE_CRYPTEX_ONE_BYTE: u16 = 0xC0DE;
+const PROFILE_CRYPTEX_TWO_BYTE: u16 = 0xC2DE;
+
+/// SDP `a=extmap:<id>[/<direction>] [urn:ietf:params:rtp-hdrext:encrypt] <uri> [<attributes>]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtMap {
+    pub id: u8,
+    pub direction: Option<String>,
+    pub uri: String,
+    /// RFC 6904 ile şifrelenmesi müzakere edildi mi?
+    pub encrypted: bool,
+    pub attributes: Option<String>,
+}
+
+impl ExtMap {
+    pub fn parse(line: &str) -> Result<Self, SrtpError> {
+        let value = line.trim();
+        let value = value.strip_prefix("a=").unwrap_or(value);
+        let value = value.strip_prefix("extmap:").unwrap_or(value);
+
+        let mut fields = value.splitn(2, char::is_whitespace);
+        let id_field = fields.next().unwrap_or_default();
+        let rest = fields
+            .next()
+            .ok_or_else(|| SrtpError::Parse(format!("missing extmap uri: {}", line)))?
+            .trim_start();
+
+        let (id, direction) = match id_field.split_once('/') {
+            Some((id, dir)) => (id, Some(dir.to_string())),
+            None => (id_field, None),
+        };
+        let id = id
+            .parse::<u8>()
+            .ok()
+            .filter(|id| (1..=255).contains(id) && *id != 15)
+            .ok_or_else(|| SrtpError::Parse(format!("invalid extmap id: {}", line)))?;
+
+        let (encrypted, rest) = match rest.strip_prefix(ENCRYPT_URI) {
+            Some(r) => (true, r.trim_start()),
+            None => (false, rest),
+        };
+        let mut fields = rest.splitn(2, char::is_whitespace);
+        let uri = fields
+            .next()
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| SrtpError::Parse(format!("missing extmap uri: {}", line)))?;
+        let attributes = fields
+            .next()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+
+        Ok(Self {
+            id,
+            direction,
+            uri: uri.to_string(),
+            encrypted,
+            attributes,
+        })
+    }
+
+    pub fn to_sdp_line(&self) -> String {
+        let mut line = format!("a=extmap:{}", self.id);
+        if let Some(dir) = &self.direction {
+            line.push('/');
+            line.push_str(dir);
+        }
+        if self.encrypted {
+            line.push(' ');
+            line.push_str(ENCRYPT_URI);
+        }
+        line.push(' ');
+        line.push_str(&self.uri);
+        if let Some(attrs) = &self.attributes {
+            line.push(' ');
+            line.push_str(attrs);
+        }
+        line
+    }
+
+    /// `SrtpPolicy::encrypted_header_extensions` için şifreli uzantı kimliklerini toplar.
+    pub fn encrypted_ids(maps: &[ExtMap]) -> Vec<u8> {
+        maps.iter().filter(|m| m.encrypted).map(|m| m.id).collect()
+    }
+}
+
+/// RTP başlığının bölgeleri (byte aralıkları).
+#[derive(Debug, Clone)]
+pub(crate) struct RtpLayout {
+    pub csrc: Range<usize>,
+    pub ext: Option<ExtLayout>,
+    pub header_len: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExtLayout {
+    /// "defined by profile" alanı (0xBEDE, 0x100X, 0xC0DE, 0xC2DE ...).
+    pub profile: u16,
+    /// Profil alanının paketteki konumu.
+    pub header_pos: usize,
+    /// Uzantı verisi (4 byte'lık uzantı başlığı hariç).
+    pub data: Range<usize>,
+}
+
+impl ExtLayout {
+    pub fn is_cryptex(&self) -> bool {
+        self.profile == PROFILE_CRYPTEX_ONE_BYTE || self.profile == PROFILE_CRYPTEX_TWO_BYTE
+    }
+}
+
+impl RtpLayout {
+    pub fn parse(packet: &[u8]) -> Result<Self, SrtpError> {
+        if packet.len() < 12 {
+            return Err(SrtpError::PacketTooShort);
+        }
+        if packet[0] >> 6 != 2 {
+            return Err(SrtpError::InvalidPacket);
+        }
+        let csrc_count = (packet[0] & 0x0F) as usize;
+        let csrc = 12..12 + csrc_count * 4;
+        let mut header_len = csrc.end;
+        let mut ext = None;
+        if packet[0] & 0x10 != 0 {
+            if packet.len() < header_len + 4 {
+                return Err(SrtpError::PacketTooShort);
+            }
+            let profile = u16::from_be_bytes([packet[header_len], packet[header_len + 1]]);
+            let words = u16::from_be_bytes([packet[header_len + 2], packet[header_len + 3]]);
+            let data_start = header_len + 4;
+            let data_end = data_start + words as usize * 4;
+            ext = Some(ExtLayout {
+                profile,
+                header_pos: header_len,
+                data: data_start..data_end,
+            });
+            header_len = data_end;
+        }
+        if packet.len() < header_len {
+            return Err(SrtpError::PacketTooShort);
+        }
+        Ok(Self {
+            csrc,
+            ext,
+            header_len,
+        })
+    }
+
+    pub fn is_cryptex(&self) -> bool {
+        self.ext.as_ref().is_some_and(|e| e.is_cryptex())
+    }
+}
+
+/// Uzantı bloğundaki her eleman için (id, uzantı verisine göre ofset, eleman verisi) ile
+/// `f`'yi çağırır (RFC 8285 one-byte / two-byte formatları).
+pub(crate) fn for_each_element(
+    profile: u16,
+    data: &mut [u8],
+    mut f: impl FnMut(u8, usize, &mut [u8]),
+) {
+    let one_byte = profile == PROFILE_ONE_BYTE;
+    let two_byte = profile & PROFILE_TWO_BYTE_MASK == PROFILE_TWO_BYTE;
+    let mut i = 0;
+    while i < data.len() {
+        // Padding byte'ları atlanır
+        if data[i] == 0 {
+            i += 1;
+            continue;
+        }
+        let (id, start, len) = if one_byte {
+            let id = data[i] >> 4;
+            if id == 15 {
+                break;
+            }
+            (id, i + 1, (data[i] & 0x0F) as usize + 1)
+        } else if two_byte {
+            if i + 1 >= data.len() {
+                break;
+            }
+            (data[i], i + 2, data[i + 1] as usize)
+        } else {
+            return;
+        };
+        let end = start + len;
+        if end > data.len() {
+            break;
+        }
+        f(id, start, &mut data[start..end]);
+        i = end;
+    }
+}
+
+/// Cryptex uygulanmadan önce paketi işaretler (RFC 9335 Bölüm 5.1).
+/// One/two-byte profili C0DE/C2DE'ye çevrilir; CSRC var ama uzantı yoksa boş bir
+/// uzantı bloğu eklenir.
+pub(crate) fn cryptex_mark(packet: &mut Vec<u8>) -> Result<(), SrtpError> {
+    let layout = RtpLayout::parse(packet)?;
+    match &layout.ext {
+        Some(ext) => {
+            let marked = if ext.profile == PROFILE_ONE_BYTE {
+                PROFILE_CRYPTEX_ONE_BYTE
+            } else if ext.profile & PROFILE_TWO_BYTE_MASK == PROFILE_TWO_BYTE {
+                PROFILE_CRYPTEX_TWO_BYTE
+            } else if ext.is_cryptex() {
+                ext.profile
+            } else {
+                // RFC 9335: Diğer uzantı profilleri cryptex ile taşınamaz
+                return Err(SrtpError::InvalidPacket);
+            };
+            packet[ext.header_pos..ext.header_pos + 2].copy_from_slice(&marked.to_be_bytes());
+            Ok(())
+        }
+        None if !layout.csrc.is_empty() => {
+            let pos = layout.csrc.end;
+            packet.splice(
+                pos..pos,
+                PROFILE_CRYPTEX_ONE_BYTE
+                    .to_be_bytes()
+                    .into_iter()
+                    .chain([0, 0]),
+            );
+            packet[0] |= 0x10;
+            Ok(())
+        }
+        None => Ok(()),
+    }
+}
+
+/// Çözülmüş cryptex paketini standart forma döndürür. `cryptex_mark` tarafından
+/// eklenen boş uzantı bloğu kaldırılır.
+pub(crate) fn cryptex_unmark(packet: &mut Vec<u8>, layout: &RtpLayout) {
+    let Some(ext) = &layout.ext else {
+        return;
+    };
+    if ext.data.is_empty() && ext.profile == PROFILE_CRYPTEX_ONE_BYTE {
+        packet.drain(ext.header_pos..ext.data.start);
+        packet[0] &= !0x10;
+        return;
+    }
+    let restored = if ext.profile == PROFILE_CRYPTEX_ONE_BYTE {
+        PROFILE_ONE_BYTE
+    } else {
+        PROFILE_TWO_BYTE
+    };
+    packet[ext.header_pos..ext.header_pos + 2].copy_from_slice(&restored.to_be_bytes());
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extmap_parse_roundtrip() {
+        let line = "a=extmap:1/sendonly urn:ietf:params:rtp-hdrext:encrypt urn:ietf:params:rtp-hdrext:ssrc-audio-level vad=on";
+        let map = ExtMap::parse(line).unwrap();
+        assert_eq!(map.id, 1);
+        assert_eq!(map.direction.as_deref(), Some("sendonly"));
+        assert!(map.encrypted);
+        assert_eq!(map.uri, "urn:ietf:params:rtp-hdrext:ssrc-audio-level");
+        assert_eq!(map.attributes.as_deref(), Some("vad=on"));
+        assert_eq!(map.to_sdp_line(), line);
+
+        let plain = ExtMap::parse("a=extmap:3 urn:ietf:params:rtp-hdrext:sdes:mid").unwrap();
+        assert!(!plain.encrypted);
+        assert_eq!(ExtMap::encrypted_ids(&[map, plain]), vec![1]);
+        assert!(ExtMap::parse("a=extmap:15 urn:x").is_err());
+    }
+}
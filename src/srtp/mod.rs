@@ -0,0 +1,1033 @@
+// sentiric-rtp-core/src/srtp/mod.rs
+
+//! SRTP/SRTCP (RFC 3711) paket koruma motoru.
+//! Anahtar değişimi (SDES, DTLS) burada yapılmaz; bu modül sadece kendisine
+//! verilen master key/salt ile paketleri şifreler ve doğrular (Sans-IO).
+
+pub mod dtls;
+pub mod header_ext;
+pub mod sdes;
+
+pub use dtls::{DtlsRole, DtlsSrtpKeys, Fingerprint, FingerprintHash, SetupRole};
+pub use header_ext::ExtMap;
+pub use sdes::{CryptoAttribute, CryptoKeyParams, CryptoSessionParams};
+
+use aes::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use aes::{Aes128, Aes192, Aes256};
+use header_ext::RtpLayout;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::collections::HashMap;
+use std::fmt;
+
+type HmacSha1 = Hmac<Sha1>;
+
+// RFC 3711 Bölüm 4.3.1: Anahtar türetme etiketleri
+const LABEL_RTP_ENCRYPTION: u8 = 0x00;
+const LABEL_RTP_AUTH: u8 = 0x01;
+const LABEL_RTP_SALT: u8 = 0x02;
+const LABEL_RTCP_ENCRYPTION: u8 = 0x03;
+const LABEL_RTCP_AUTH: u8 = 0x04;
+const LABEL_RTCP_SALT: u8 = 0x05;
+// RFC 6904 Bölüm 4.3: Başlık uzantısı şifreleme etiketleri
+const LABEL_HEADER_ENCRYPTION: u8 = 0x06;
+const LABEL_HEADER_SALT: u8 = 0x07;
+
+const AUTH_KEY_LEN: usize = 20;
+const SALT_LEN: usize = 14;
+const SRTCP_INDEX_LEN: usize = 4;
+const SRTCP_AUTH_TAG_LEN: usize = 10;
+const REPLAY_WINDOW_SIZE: u64 = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SrtpError {
+    /// Master key veya salt uzunluğu süit ile uyuşmuyor.
+    InvalidKeyLength,
+    /// Paket, başlık + trailer için gereken minimum boyuttan kısa.
+    PacketTooShort,
+    /// RTP/RTCP başlığı çözümlenemedi (versiyon veya uzunluk hatası).
+    InvalidPacket,
+    /// HMAC doğrulaması başarısız (paket değiştirilmiş veya yanlış anahtar).
+    AuthenticationFailed,
+    /// Paket daha önce işlendi veya replay penceresinin dışında kaldı.
+    ReplayDetected,
+    /// Master key ömrü (lifetime) doldu, yeniden anahtarlama gerekli.
+    KeyExpired,
+    /// Paketteki MKI, context'e tanımlı MKI ile eşleşmiyor.
+    UnknownMki,
+    /// Tanınmayan veya desteklenmeyen kripto süiti.
+    UnsupportedProfile(String),
+    /// SDP kripto özniteliği (a=crypto) çözümleme hatası.
+    Parse(String),
+}
+
+impl fmt::Display for SrtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SrtpError::InvalidKeyLength => write!(f, "invalid SRTP master key/salt length"),
+            SrtpError::PacketTooShort => write!(f, "packet too short"),
+            SrtpError::InvalidPacket => write!(f, "malformed RTP/RTCP packet"),
+            SrtpError::AuthenticationFailed => write!(f, "SRTP authentication failed"),
+            SrtpError::ReplayDetected => write!(f, "SRTP replay detected"),
+            SrtpError::KeyExpired => write!(f, "SRTP master key lifetime exceeded"),
+            SrtpError::UnknownMki => write!(f, "unknown SRTP MKI"),
+            SrtpError::UnsupportedProfile(name) => write!(f, "unsupported SRTP profile: {}", name),
+            SrtpError::Parse(msg) => write!(f, "crypto attribute parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SrtpError {}
+
+/// SRTP Kripto Süitleri (RFC 4568 ve RFC 6188 isimlendirmesi).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SrtpProfile {
+    AesCm128HmacSha1_80,
+    AesCm128HmacSha1_32,
+    Aes192CmHmacSha1_80,
+    Aes192CmHmacSha1_32,
+    Aes256CmHmacSha1_80,
+    Aes256CmHmacSha1_32,
+}
+
+impl SrtpProfile {
+    /// SDP `a=crypto` satırında kullanılan süit adı.
+    pub fn sdes_name(&self) -> &'static str {
+        match self {
+            SrtpProfile::AesCm128HmacSha1_80 => "AES_CM_128_HMAC_SHA1_80",
+            SrtpProfile::AesCm128HmacSha1_32 => "AES_CM_128_HMAC_SHA1_32",
+            SrtpProfile::Aes192CmHmacSha1_80 => "AES_192_CM_HMAC_SHA1_80",
+            SrtpProfile::Aes192CmHmacSha1_32 => "AES_192_CM_HMAC_SHA1_32",
+            SrtpProfile::Aes256CmHmacSha1_80 => "AES_256_CM_HMAC_SHA1_80",
+            SrtpProfile::Aes256CmHmacSha1_32 => "AES_256_CM_HMAC_SHA1_32",
+        }
+    }
+
+    pub fn from_sdes_name(name: &str) -> Option<Self> {
+        match name {
+            "AES_CM_128_HMAC_SHA1_80" => Some(SrtpProfile::AesCm128HmacSha1_80),
+            "AES_CM_128_HMAC_SHA1_32" => Some(SrtpProfile::AesCm128HmacSha1_32),
+            "AES_192_CM_HMAC_SHA1_80" => Some(SrtpProfile::Aes192CmHmacSha1_80),
+            "AES_192_CM_HMAC_SHA1_32" => Some(SrtpProfile::Aes192CmHmacSha1_32),
+            "AES_256_CM_HMAC_SHA1_80" => Some(SrtpProfile::Aes256CmHmacSha1_80),
+            "AES_256_CM_HMAC_SHA1_32" => Some(SrtpProfile::Aes256CmHmacSha1_32),
+            _ => None,
+        }
+    }
+
+    /// Master key uzunluğu (byte). Oturum şifreleme anahtarı da aynı uzunluktadır.
+    pub fn master_key_len(&self) -> usize {
+        match self {
+            SrtpProfile::AesCm128HmacSha1_80 | SrtpProfile::AesCm128HmacSha1_32 => 16,
+            SrtpProfile::Aes192CmHmacSha1_80 | SrtpProfile::Aes192CmHmacSha1_32 => 24,
+            SrtpProfile::Aes256CmHmacSha1_80 | SrtpProfile::Aes256CmHmacSha1_32 => 32,
+        }
+    }
+
+    /// Master salt uzunluğu (byte). Tüm AES-CM süitlerinde 112 bit.
+    pub fn master_salt_len(&self) -> usize {
+        SALT_LEN
+    }
+
+    /// SRTP (RTP) kimlik doğrulama etiketi uzunluğu (byte).
+    pub fn rtp_auth_tag_len(&self) -> usize {
+        match self {
+            SrtpProfile::AesCm128HmacSha1_80
+            | SrtpProfile::Aes192CmHmacSha1_80
+            | SrtpProfile::Aes256CmHmacSha1_80 => 10,
+            SrtpProfile::AesCm128HmacSha1_32
+            | SrtpProfile::Aes192CmHmacSha1_32
+            | SrtpProfile::Aes256CmHmacSha1_32 => 4,
+        }
+    }
+
+    /// SRTCP etiket uzunluğu. RFC 4568: `_32` süitlerinde bile SRTCP 80 bit kullanır.
+    pub fn rtcp_auth_tag_len(&self) -> usize {
+        SRTCP_AUTH_TAG_LEN
+    }
+}
+
+/// Bir SRTP context'inin kurulması için gereken tüm parametreler.
+/// SDES (`a=crypto`) veya DTLS-SRTP çıktısı bu yapıya dönüştürülür.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SrtpPolicy {
+    pub profile: SrtpProfile,
+    pub master_key: Vec<u8>,
+    pub master_salt: Vec<u8>,
+    /// Master Key Identifier (varsa her pakete eklenir).
+    pub mki: Option<Vec<u8>>,
+    /// Bu master key ile korunabilecek maksimum paket sayısı.
+    pub lifetime: Option<u64>,
+    /// Anahtar türetme oranı (Key Derivation Rate). 0 = tek seferlik türetme.
+    pub key_derivation_rate: u64,
+    /// false ise RTP payload şifrelenmez (UNENCRYPTED_SRTP).
+    pub encrypt_rtp: bool,
+    /// false ise SRTCP payload şifrelenmez (UNENCRYPTED_SRTCP).
+    pub encrypt_rtcp: bool,
+    /// false ise RTP paketlerine etiket eklenmez (UNAUTHENTICATED_SRTP).
+    pub authenticate_rtp: bool,
+    /// RFC 6904 ile şifrelenecek başlık uzantısı kimlikleri (`a=extmap ... encrypt`).
+    pub encrypted_header_extensions: Vec<u8>,
+    /// RFC 9335 cryptex: CSRC listesi ve tüm uzantı bloğu şifrelenir (`a=cryptex`).
+    pub cryptex: bool,
+}
+
+impl SrtpPolicy {
+    pub fn new(profile: SrtpProfile, master_key: &[u8], master_salt: &[u8]) -> Self {
+        Self {
+            profile,
+            master_key: master_key.to_vec(),
+            master_salt: master_salt.to_vec(),
+            mki: None,
+            lifetime: None,
+            key_derivation_rate: 0,
+            encrypt_rtp: true,
+            encrypt_rtcp: true,
+            authenticate_rtp: true,
+            encrypted_header_extensions: Vec::new(),
+            cryptex: false,
+        }
+    }
+}
+
+/// Süite göre seçilen AES blok şifresi (Counter Mode için).
+#[derive(Clone)]
+enum AesCipher {
+    Aes128(Box<Aes128>),
+    Aes192(Box<Aes192>),
+    Aes256(Box<Aes256>),
+}
+
+impl AesCipher {
+    fn new(key: &[u8]) -> Result<Self, SrtpError> {
+        match key.len() {
+            16 => Ok(AesCipher::Aes128(Box::new(Aes128::new(
+                GenericArray::from_slice(key),
+            )))),
+            24 => Ok(AesCipher::Aes192(Box::new(Aes192::new(
+                GenericArray::from_slice(key),
+            )))),
+            32 => Ok(AesCipher::Aes256(Box::new(Aes256::new(
+                GenericArray::from_slice(key),
+            )))),
+            _ => Err(SrtpError::InvalidKeyLength),
+        }
+    }
+
+    fn encrypt_block(&self, block: &mut [u8; 16]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            AesCipher::Aes128(c) => c.encrypt_block(block),
+            AesCipher::Aes192(c) => c.encrypt_block(block),
+            AesCipher::Aes256(c) => c.encrypt_block(block),
+        }
+    }
+
+    /// AES Counter Mode (RFC 3711 Bölüm 4.1.1). IV'nin son 16 biti blok sayacıdır.
+    fn apply_keystream(&self, iv: &[u8; 16], data: &mut [u8]) {
+        self.apply_keystream_at(iv, 0, data);
+    }
+
+    /// Anahtar akışını `offset` byte'ından başlayarak uygular. Paketin ardışık olmayan
+    /// bölgeleri (cryptex: CSRC + uzantı + payload) tek bir akış gibi şifrelenebilir.
+    fn apply_keystream_at(&self, iv: &[u8; 16], offset: usize, data: &mut [u8]) {
+        let mut pos = 0;
+        while pos < data.len() {
+            let stream_pos = offset + pos;
+            let mut block = *iv;
+            let ctr = u16::from_be_bytes([iv[14], iv[15]]).wrapping_add((stream_pos / 16) as u16);
+            block[14..16].copy_from_slice(&ctr.to_be_bytes());
+            self.encrypt_block(&mut block);
+
+            let skip = stream_pos % 16;
+            let take = (16 - skip).min(data.len() - pos);
+            for (d, k) in data[pos..pos + take].iter_mut().zip(&block[skip..]) {
+                *d ^= k;
+            }
+            pos += take;
+        }
+    }
+}
+
+/// Tek bir yön (RTP veya RTCP) için türetilmiş oturum anahtarları.
+#[derive(Clone)]
+struct SessionKeys {
+    cipher: AesCipher,
+    salt: [u8; SALT_LEN],
+    auth: HmacSha1,
+    /// Türetmede kullanılan r = index DIV kdr değeri.
+    derivation_index: u64,
+}
+
+impl SessionKeys {
+    fn derive(
+        master: &AesCipher,
+        master_salt: &[u8],
+        key_len: usize,
+        labels: [u8; 3],
+        r: u64,
+    ) -> Result<Self, SrtpError> {
+        let mut enc_key = vec![0u8; key_len];
+        let mut auth_key = [0u8; AUTH_KEY_LEN];
+        let mut salt = [0u8; SALT_LEN];
+        derive_key(master, master_salt, labels[0], r, &mut enc_key);
+        derive_key(master, master_salt, labels[1], r, &mut auth_key);
+        derive_key(master, master_salt, labels[2], r, &mut salt);
+
+        let auth = <HmacSha1 as Mac>::new_from_slice(&auth_key)
+            .map_err(|_| SrtpError::InvalidKeyLength)?;
+        Ok(Self {
+            cipher: AesCipher::new(&enc_key)?,
+            salt,
+            auth,
+            derivation_index: r,
+        })
+    }
+
+    /// RFC 3711 Bölüm 4.1.1: IV = (k_s * 2^16) XOR (SSRC * 2^64) XOR (i * 2^16)
+    fn iv(&self, ssrc: u32, index: u64) -> [u8; 16] {
+        let mut iv = [0u8; 16];
+        iv[..SALT_LEN].copy_from_slice(&self.salt);
+        for (b, s) in iv[4..8].iter_mut().zip(ssrc.to_be_bytes()) {
+            *b ^= s;
+        }
+        for (b, s) in iv[8..14].iter_mut().zip(&index.to_be_bytes()[2..]) {
+            *b ^= s;
+        }
+        iv
+    }
+
+    fn tag(&self, parts: &[&[u8]], out: &mut [u8]) {
+        let mut mac = self.auth.clone();
+        for part in parts {
+            mac.update(part);
+        }
+        let digest = mac.finalize().into_bytes();
+        out.copy_from_slice(&digest[..out.len()]);
+    }
+}
+
+/// RFC 3711 Bölüm 4.3.3: AES-CM tabanlı anahtar türetme fonksiyonu (PRF).
+/// key_id = label || r, master salt'ın en düşük 56 bitiyle XOR'lanır.
+fn derive_key(master: &AesCipher, master_salt: &[u8], label: u8, r: u64, out: &mut [u8]) {
+    let mut iv = [0u8; 16];
+    iv[..SALT_LEN].copy_from_slice(&master_salt[..SALT_LEN]);
+    iv[7] ^= label;
+    for (b, s) in iv[8..14].iter_mut().zip(&r.to_be_bytes()[2..]) {
+        *b ^= s;
+    }
+    out.iter_mut().for_each(|b| *b = 0);
+    master.apply_keystream(&iv, out);
+}
+
+/// 64 paketlik kayan replay penceresi (RFC 3711 Bölüm 3.3.2).
+#[derive(Clone, Debug, Default)]
+struct ReplayWindow {
+    max_index: u64,
+    bitmap: u64,
+    initialized: bool,
+}
+
+impl ReplayWindow {
+    fn check(&self, index: u64) -> bool {
+        if !self.initialized || index > self.max_index {
+            return true;
+        }
+        let delta = self.max_index - index;
+        if delta >= REPLAY_WINDOW_SIZE {
+            return false;
+        }
+        self.bitmap & (1u64 << delta) == 0
+    }
+
+    fn update(&mut self, index: u64) {
+        if !self.initialized {
+            self.initialized = true;
+            self.max_index = index;
+            self.bitmap = 1;
+        } else if index > self.max_index {
+            let shift = index - self.max_index;
+            self.bitmap = if shift >= REPLAY_WINDOW_SIZE {
+                0
+            } else {
+                self.bitmap << shift
+            };
+            self.bitmap |= 1;
+            self.max_index = index;
+        } else {
+            self.bitmap |= 1u64 << (self.max_index - index);
+        }
+    }
+}
+
+/// SSRC başına tutulan kriptografik durum (ROC, son sekans, replay penceresi).
+#[derive(Clone, Debug, Default)]
+struct StreamState {
+    roc: u32,
+    s_l: u16,
+    initialized: bool,
+    rtp_replay: ReplayWindow,
+    srtcp_index: u32,
+    rtcp_replay: ReplayWindow,
+}
+
+impl StreamState {
+    /// RFC 3711 Appendix A: ROC tahmini. Paketin 48-bit indeksini döndürür.
+    fn estimate_index(&self, seq: u16) -> (u32, u64) {
+        if !self.initialized {
+            return (self.roc, ((self.roc as u64) << 16) | seq as u64);
+        }
+        let s_l = self.s_l as i32;
+        let seq_i = seq as i32;
+        let v = if s_l < 32768 {
+            if seq_i - s_l > 32768 {
+                self.roc.wrapping_sub(1)
+            } else {
+                self.roc
+            }
+        } else if s_l - 32768 > seq_i {
+            self.roc.wrapping_add(1)
+        } else {
+            self.roc
+        };
+        (v, ((v as u64) << 16) | seq as u64)
+    }
+
+    fn commit(&mut self, v: u32, seq: u16) {
+        if !self.initialized {
+            self.initialized = true;
+            self.roc = v;
+            self.s_l = seq;
+        } else if v == self.roc {
+            if seq > self.s_l {
+                self.s_l = seq;
+            }
+        } else if v == self.roc.wrapping_add(1) {
+            self.roc = v;
+            self.s_l = seq;
+        }
+    }
+}
+
+/// SRTP/SRTCP kripto context'i.
+/// Bir context tek bir master key'i temsil eder; SSRC başına durum içeride tutulur.
+/// Genellikle her çağrı için bir gönderim (local key) ve bir alım (remote key) context'i kurulur.
+pub struct SrtpContext {
+    policy: SrtpPolicy,
+    master: AesCipher,
+    rtp_keys: SessionKeys,
+    rtcp_keys: SessionKeys,
+    /// RFC 6904 başlık uzantısı anahtarları (sadece şifreli uzantı müzakere edildiyse).
+    header_keys: Option<SessionKeys>,
+    streams: HashMap<u32, StreamState>,
+    packets_protected: u64,
+}
+
+impl fmt::Debug for SrtpContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Anahtar materyali loglara sızmasın diye sadece profil gösterilir.
+        f.debug_struct("SrtpContext")
+            .field("profile", &self.policy.profile)
+            .field("streams", &self.streams.len())
+            .finish()
+    }
+}
+
+impl SrtpContext {
+    pub fn new(policy: SrtpPolicy) -> Result<Self, SrtpError> {
+        if policy.master_key.len() != policy.profile.master_key_len()
+            || policy.master_salt.len() != policy.profile.master_salt_len()
+        {
+            return Err(SrtpError::InvalidKeyLength);
+        }
+        let master = AesCipher::new(&policy.master_key)?;
+        let key_len = policy.profile.master_key_len();
+        let rtp_keys = SessionKeys::derive(
+            &master,
+            &policy.master_salt,
+            key_len,
+            [LABEL_RTP_ENCRYPTION, LABEL_RTP_AUTH, LABEL_RTP_SALT],
+            0,
+        )?;
+        let rtcp_keys = SessionKeys::derive(
+            &master,
+            &policy.master_salt,
+            key_len,
+            [LABEL_RTCP_ENCRYPTION, LABEL_RTCP_AUTH, LABEL_RTCP_SALT],
+            0,
+        )?;
+
+        let header_keys = if policy.encrypted_header_extensions.is_empty() {
+            None
+        } else {
+            Some(SessionKeys::derive(
+                &master,
+                &policy.master_salt,
+                key_len,
+                [LABEL_HEADER_ENCRYPTION, LABEL_RTP_AUTH, LABEL_HEADER_SALT],
+                0,
+            )?)
+        };
+
+        Ok(Self {
+            policy,
+            master,
+            rtp_keys,
+            rtcp_keys,
+            header_keys,
+            streams: HashMap::new(),
+            packets_protected: 0,
+        })
+    }
+
+    pub fn profile(&self) -> SrtpProfile {
+        self.policy.profile
+    }
+
+    pub fn policy(&self) -> &SrtpPolicy {
+        &self.policy
+    }
+
+    /// KDR > 0 ise indeks değiştikçe oturum anahtarlarını yeniden türetir.
+    fn refresh_keys(&mut self, rtcp: bool, index: u64) -> Result<(), SrtpError> {
+        let fresh = self.keys_for_index(rtcp, index)?;
+        self.install_keys(rtcp, index, fresh)
+    }
+
+    /// `index` için geçerli RTP/RTCP oturum anahtarları mevcut olanlardan farklıysa yeni
+    /// türetilmiş halleri. Context'i değiştirmez: Alınan paketlerde etiket bununla doğrulanır,
+    /// anahtarlar ancak doğrulama başarılı olursa `install_keys` ile yerleştirilir.
+    fn keys_for_index(&self, rtcp: bool, index: u64) -> Result<Option<SessionKeys>, SrtpError> {
+        let kdr = self.policy.key_derivation_rate;
+        if kdr == 0 {
+            return Ok(None);
+        }
+        let r = index / kdr;
+        let (keys, labels) = if rtcp {
+            (
+                &self.rtcp_keys,
+                [LABEL_RTCP_ENCRYPTION, LABEL_RTCP_AUTH, LABEL_RTCP_SALT],
+            )
+        } else {
+            (
+                &self.rtp_keys,
+                [LABEL_RTP_ENCRYPTION, LABEL_RTP_AUTH, LABEL_RTP_SALT],
+            )
+        };
+        if keys.derivation_index == r {
+            return Ok(None);
+        }
+        SessionKeys::derive(
+            &self.master,
+            &self.policy.master_salt,
+            self.policy.profile.master_key_len(),
+            labels,
+            r,
+        )
+        .map(Some)
+    }
+
+    fn install_keys(
+        &mut self,
+        rtcp: bool,
+        index: u64,
+        fresh: Option<SessionKeys>,
+    ) -> Result<(), SrtpError> {
+        if let Some(keys) = fresh {
+            if rtcp {
+                self.rtcp_keys = keys;
+            } else {
+                self.rtp_keys = keys;
+            }
+        }
+        let kdr = self.policy.key_derivation_rate;
+        if rtcp || kdr == 0 {
+            return Ok(());
+        }
+        let r = index / kdr;
+        if let Some(keys) = self
+            .header_keys
+            .as_mut()
+            .filter(|k| k.derivation_index != r)
+        {
+            *keys = SessionKeys::derive(
+                &self.master,
+                &self.policy.master_salt,
+                self.policy.profile.master_key_len(),
+                [LABEL_HEADER_ENCRYPTION, LABEL_RTP_AUTH, LABEL_HEADER_SALT],
+                r,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// RTP şifreleme/çözme (AES-CM simetrik olduğu için iki yönde de aynı işlem).
+    /// Cryptex işaretli paketlerde CSRC + uzantı verisi + payload tek akışla işlenir;
+    /// diğerlerinde payload ve (varsa) RFC 6904 uzantı elemanları ayrı ayrı işlenir.
+    fn crypt_rtp(&self, packet: &mut [u8], layout: &RtpLayout, ssrc: u32, index: u64) {
+        let iv = self.rtp_keys.iv(ssrc, index);
+        let cipher = &self.rtp_keys.cipher;
+        match &layout.ext {
+            Some(ext) if ext.is_cryptex() => {
+                if !self.policy.encrypt_rtp {
+                    return;
+                }
+                let mut offset = 0;
+                for range in [
+                    layout.csrc.clone(),
+                    ext.data.clone(),
+                    layout.header_len..packet.len(),
+                ] {
+                    let len = range.len();
+                    cipher.apply_keystream_at(&iv, offset, &mut packet[range]);
+                    offset += len;
+                }
+            }
+            _ => {
+                if self.policy.encrypt_rtp {
+                    cipher.apply_keystream(&iv, &mut packet[layout.header_len..]);
+                }
+                if let (Some(keys), Some(ext)) = (&self.header_keys, &layout.ext) {
+                    let iv = keys.iv(ssrc, index);
+                    let ids = &self.policy.encrypted_header_extensions;
+                    // RFC 6904 Bölüm 4.1: Akış uzantı verisinin başına hizalanır,
+                    // sadece seçili elemanların veri byte'ları maskelenir.
+                    header_ext::for_each_element(
+                        ext.profile,
+                        &mut packet[ext.data.clone()],
+                        |id, offset, element| {
+                            if ids.contains(&id) {
+                                keys.cipher.apply_keystream_at(&iv, offset, element);
+                            }
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    fn consume_lifetime(&mut self) -> Result<(), SrtpError> {
+        if let Some(limit) = self.policy.lifetime {
+            if self.packets_protected >= limit {
+                return Err(SrtpError::KeyExpired);
+            }
+        }
+        self.packets_protected += 1;
+        Ok(())
+    }
+
+    fn mki_len(&self) -> usize {
+        self.policy.mki.as_ref().map_or(0, |m| m.len())
+    }
+
+    fn rtp_tag_len(&self) -> usize {
+        if self.policy.authenticate_rtp {
+            self.policy.profile.rtp_auth_tag_len()
+        } else {
+            0
+        }
+    }
+
+    /// Düz bir RTP paketini yerinde SRTP paketine dönüştürür (şifreleme + MKI + etiket).
+    pub fn protect_rtp(&mut self, packet: &mut Vec<u8>) -> Result<(), SrtpError> {
+        if self.policy.cryptex && self.policy.encrypt_rtp {
+            header_ext::cryptex_mark(packet)?;
+        }
+        let layout = RtpLayout::parse(packet)?;
+        let ssrc = read_u32(packet, 8);
+        let seq = u16::from_be_bytes([packet[2], packet[3]]);
+
+        let stream = self.streams.entry(ssrc).or_default();
+        let (roc, index) = stream.estimate_index(seq);
+        stream.commit(roc, seq);
+
+        self.consume_lifetime()?;
+        self.refresh_keys(false, index)?;
+        self.crypt_rtp(packet, &layout, ssrc, index);
+
+        let auth_end = packet.len();
+        if let Some(mki) = &self.policy.mki {
+            packet.extend_from_slice(mki);
+        }
+        let tag_len = self.rtp_tag_len();
+        if tag_len > 0 {
+            let mut tag = [0u8; 20];
+            self.rtp_keys.tag(
+                &[&packet[..auth_end], &roc.to_be_bytes()],
+                &mut tag[..tag_len],
+            );
+            packet.extend_from_slice(&tag[..tag_len]);
+        }
+        Ok(())
+    }
+
+    /// SRTP paketini doğrular, replay kontrolü yapar ve yerinde çözer.
+    /// Başarılı olursa `packet` düz RTP paketi olarak kalır (MKI ve etiket kırpılır).
+    pub fn unprotect_rtp(&mut self, packet: &mut Vec<u8>) -> Result<(), SrtpError> {
+        let tag_len = self.rtp_tag_len();
+        let mki_len = self.mki_len();
+        let layout = RtpLayout::parse(packet)?;
+        if packet.len() < layout.header_len + mki_len + tag_len {
+            return Err(SrtpError::PacketTooShort);
+        }
+        let ssrc = read_u32(packet, 8);
+        let seq = u16::from_be_bytes([packet[2], packet[3]]);
+        let auth_end = packet.len() - tag_len - mki_len;
+
+        if let Some(mki) = &self.policy.mki {
+            if &packet[auth_end..auth_end + mki_len] != mki.as_slice() {
+                return Err(SrtpError::UnknownMki);
+            }
+        }
+
+        // Doğrulanmamış paket context'i değiştirmemeli: Yeni SSRC'ler için durum ve
+        // yeniden türetilen anahtarlar ancak etiket doğrulandıktan sonra kaydedilir.
+        let unknown = StreamState::default();
+        let stream = self.streams.get(&ssrc).unwrap_or(&unknown);
+        let (roc, index) = stream.estimate_index(seq);
+        if !stream.rtp_replay.check(index) {
+            return Err(SrtpError::ReplayDetected);
+        }
+
+        let fresh = self.keys_for_index(false, index)?;
+        if tag_len > 0 {
+            let mut expected = [0u8; 20];
+            fresh.as_ref().unwrap_or(&self.rtp_keys).tag(
+                &[&packet[..auth_end], &roc.to_be_bytes()],
+                &mut expected[..tag_len],
+            );
+            if !constant_time_eq(&expected[..tag_len], &packet[packet.len() - tag_len..]) {
+                return Err(SrtpError::AuthenticationFailed);
+            }
+        }
+
+        self.install_keys(false, index, fresh)?;
+        packet.truncate(auth_end);
+        self.crypt_rtp(packet, &layout, ssrc, index);
+        if layout.is_cryptex() {
+            header_ext::cryptex_unmark(packet, &layout);
+        }
+
+        let stream = self.streams.entry(ssrc).or_default();
+        stream.rtp_replay.update(index);
+        stream.commit(roc, seq);
+        Ok(())
+    }
+
+    /// Düz bir RTCP (compound) paketini yerinde SRTCP paketine dönüştürür.
+    pub fn protect_rtcp(&mut self, packet: &mut Vec<u8>) -> Result<(), SrtpError> {
+        if packet.len() < 8 {
+            return Err(SrtpError::PacketTooShort);
+        }
+        let ssrc = read_u32(packet, 4);
+        let stream = self.streams.entry(ssrc).or_default();
+        let index = stream.srtcp_index;
+        stream.srtcp_index = (stream.srtcp_index + 1) & 0x7FFF_FFFF;
+
+        self.consume_lifetime()?;
+        self.refresh_keys(true, index as u64)?;
+
+        let encrypt = self.policy.encrypt_rtcp;
+        if encrypt {
+            let iv = self.rtcp_keys.iv(ssrc, index as u64);
+            self.rtcp_keys.cipher.apply_keystream(&iv, &mut packet[8..]);
+        }
+
+        let e_index = if encrypt { index | 0x8000_0000 } else { index };
+        packet.extend_from_slice(&e_index.to_be_bytes());
+        let auth_end = packet.len();
+        if let Some(mki) = &self.policy.mki {
+            packet.extend_from_slice(mki);
+        }
+        let mut tag = [0u8; SRTCP_AUTH_TAG_LEN];
+        self.rtcp_keys.tag(&[&packet[..auth_end]], &mut tag);
+        packet.extend_from_slice(&tag);
+        Ok(())
+    }
+
+    /// SRTCP paketini doğrular ve yerinde düz RTCP paketine çözer.
+    pub fn unprotect_rtcp(&mut self, packet: &mut Vec<u8>) -> Result<(), SrtpError> {
+        let mki_len = self.mki_len();
+        if packet.len() < 8 + SRTCP_INDEX_LEN + mki_len + SRTCP_AUTH_TAG_LEN {
+            return Err(SrtpError::PacketTooShort);
+        }
+        let ssrc = read_u32(packet, 4);
+        let auth_end = packet.len() - SRTCP_AUTH_TAG_LEN - mki_len;
+        let index_pos = auth_end - SRTCP_INDEX_LEN;
+        let e_index = read_u32(packet, index_pos);
+        let encrypted = e_index & 0x8000_0000 != 0;
+        let index = e_index & 0x7FFF_FFFF;
+
+        if let Some(mki) = &self.policy.mki {
+            if &packet[auth_end..auth_end + mki_len] != mki.as_slice() {
+                return Err(SrtpError::UnknownMki);
+            }
+        }
+
+        let replayed = self
+            .streams
+            .get(&ssrc)
+            .is_some_and(|s| !s.rtcp_replay.check(index as u64));
+        if replayed {
+            return Err(SrtpError::ReplayDetected);
+        }
+
+        let fresh = self.keys_for_index(true, index as u64)?;
+        let mut expected = [0u8; SRTCP_AUTH_TAG_LEN];
+        fresh
+            .as_ref()
+            .unwrap_or(&self.rtcp_keys)
+            .tag(&[&packet[..auth_end]], &mut expected);
+        if !constant_time_eq(&expected, &packet[packet.len() - SRTCP_AUTH_TAG_LEN..]) {
+            return Err(SrtpError::AuthenticationFailed);
+        }
+        self.install_keys(true, index as u64, fresh)?;
+
+        packet.truncate(index_pos);
+        if encrypted {
+            let iv = self.rtcp_keys.iv(ssrc, index as u64);
+            self.rtcp_keys.cipher.apply_keystream(&iv, &mut packet[8..]);
+        }
+
+        self.streams
+            .entry(ssrc)
+            .or_default()
+            .rtcp_replay
+            .update(index as u64);
+        Ok(())
+    }
+}
+
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]])
+}
+
+/// Zamanlama saldırılarına karşı sabit süreli karşılaştırma.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rtp::{RtcpPacket, RtpHeader, RtpPacket};
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn test_policy() -> SrtpPolicy {
+        SrtpPolicy::new(
+            SrtpProfile::AesCm128HmacSha1_80,
+            &hex("E1F97A0D3E018BE0D64FA32C06DE4139"),
+            &hex("0EC675AD498AFEEBB6960B3AABE6"),
+        )
+    }
+
+    fn rtp_packet(seq: u16) -> Vec<u8> {
+        RtpPacket {
+            header: RtpHeader::new(0, seq, seq as u32 * 160, 0xCAFEBABE),
+            payload: vec![0xAB; 160],
+        }
+        .to_bytes()
+    }
+
+    #[test]
+    fn test_key_derivation_rfc3711_vectors() {
+        // RFC 3711 Appendix B.3
+        let policy = test_policy();
+        let master = AesCipher::new(&policy.master_key).unwrap();
+
+        let mut enc = [0u8; 16];
+        derive_key(
+            &master,
+            &policy.master_salt,
+            LABEL_RTP_ENCRYPTION,
+            0,
+            &mut enc,
+        );
+        assert_eq!(enc.to_vec(), hex("C61E7A93744F39EE10734AFE3FF7A087"));
+
+        let mut salt = [0u8; 14];
+        derive_key(&master, &policy.master_salt, LABEL_RTP_SALT, 0, &mut salt);
+        assert_eq!(salt.to_vec(), hex("30CBBC08863D8C85D49DB34A9AE1"));
+
+        let mut auth = [0u8; 20];
+        derive_key(&master, &policy.master_salt, LABEL_RTP_AUTH, 0, &mut auth);
+        assert_eq!(
+            auth.to_vec(),
+            hex("CEBE321F6FF7716B6FD4AB49AF256A156D38BAA4")
+        );
+    }
+
+    #[test]
+    fn test_rtp_roundtrip_and_tamper() {
+        let mut tx = SrtpContext::new(test_policy()).unwrap();
+        let mut rx = SrtpContext::new(test_policy()).unwrap();
+
+        let plain = rtp_packet(65534);
+        let mut packet = plain.clone();
+        tx.protect_rtp(&mut packet).unwrap();
+        assert_eq!(packet.len(), plain.len() + 10);
+        assert_ne!(&packet[12..plain.len()], &plain[12..]);
+
+        let mut received = packet.clone();
+        rx.unprotect_rtp(&mut received).unwrap();
+        assert_eq!(received, plain);
+
+        // Aynı paket ikinci kez gelirse replay olarak reddedilmeli
+        let mut replayed = packet.clone();
+        assert_eq!(
+            rx.unprotect_rtp(&mut replayed),
+            Err(SrtpError::ReplayDetected)
+        );
+
+        // Sekans wrap-around (ROC artışı) sonrası doğrulama devam etmeli
+        for seq in [65535u16, 0, 1] {
+            let plain = rtp_packet(seq);
+            let mut p = plain.clone();
+            tx.protect_rtp(&mut p).unwrap();
+            rx.unprotect_rtp(&mut p).unwrap();
+            assert_eq!(p, plain);
+        }
+
+        let mut tampered = rtp_packet(2);
+        tx.protect_rtp(&mut tampered).unwrap();
+        tampered[20] ^= 0x01;
+        assert_eq!(
+            rx.unprotect_rtp(&mut tampered),
+            Err(SrtpError::AuthenticationFailed)
+        );
+    }
+
+    #[test]
+    fn test_rtcp_roundtrip_with_mki() {
+        let mut policy = test_policy();
+        policy.mki = Some(vec![0, 0, 0, 1]);
+        let mut tx = SrtpContext::new(policy.clone()).unwrap();
+        let mut rx = SrtpContext::new(policy).unwrap();
+
+        let mut plain = RtcpPacket::new_sender_report(0xCAFEBABE).to_bytes();
+        plain.extend_from_slice(&[0x11; 20]);
+        let mut packet = plain.clone();
+        tx.protect_rtcp(&mut packet).unwrap();
+        assert_eq!(packet.len(), plain.len() + 4 + 4 + 10);
+
+        rx.unprotect_rtcp(&mut packet).unwrap();
+        assert_eq!(packet, plain);
+    }
+
+    #[test]
+    fn test_forged_packets_do_not_create_streams() {
+        let mut policy = test_policy();
+        policy.key_derivation_rate = 1 << 10;
+        let mut rx = SrtpContext::new(policy).unwrap();
+
+        for ssrc in 0..64u32 {
+            // Rastgele SSRC ve geçersiz etiketli sahte SRTP paketi
+            let mut rtp = RtpPacket {
+                header: RtpHeader::new(0, ssrc as u16 * 977, 0, ssrc.wrapping_mul(0x9E37_79B9)),
+                payload: vec![0x42; 170],
+            }
+            .to_bytes();
+            assert_eq!(
+                rx.unprotect_rtp(&mut rtp),
+                Err(SrtpError::AuthenticationFailed)
+            );
+
+            let mut rtcp = RtcpPacket::new_sender_report(ssrc.wrapping_add(0x1000)).to_bytes();
+            rtcp.extend_from_slice(&[0x80, 0, 0, ssrc as u8]);
+            rtcp.extend_from_slice(&[0x42; 10]);
+            assert_eq!(
+                rx.unprotect_rtcp(&mut rtcp),
+                Err(SrtpError::AuthenticationFailed)
+            );
+        }
+        assert!(rx.streams.is_empty());
+        assert_eq!(rx.rtp_keys.derivation_index, 0);
+
+        // Geçerli paket akışı başlatır
+        let mut tx = SrtpContext::new(rx.policy.clone()).unwrap();
+        let mut packet = rtp_packet(7);
+        tx.protect_rtp(&mut packet).unwrap();
+        rx.unprotect_rtp(&mut packet).unwrap();
+        assert_eq!(rx.streams.len(), 1);
+    }
+
+    fn rtp_with_extension(seq: u16, csrc: &[u32], ext: &[u8]) -> Vec<u8> {
+        let mut header = RtpHeader::new(0, seq, 0, 0xCAFEBABE);
+        header.csrc_count = csrc.len() as u8;
+        header.extension = !ext.is_empty();
+        let mut packet = header.to_bytes();
+        for c in csrc {
+            packet.extend_from_slice(&c.to_be_bytes());
+        }
+        if !ext.is_empty() {
+            packet.extend_from_slice(&[0xBE, 0xDE]);
+            packet.extend_from_slice(&((ext.len() / 4) as u16).to_be_bytes());
+            packet.extend_from_slice(ext);
+        }
+        packet.extend_from_slice(&[0x55; 40]);
+        packet
+    }
+
+    #[test]
+    fn test_rfc6904_encrypts_selected_extensions_only() {
+        let mut policy = test_policy();
+        policy.encrypted_header_extensions = vec![1];
+        let mut tx = SrtpContext::new(policy.clone()).unwrap();
+        let mut rx = SrtpContext::new(policy).unwrap();
+
+        // id=1 (audio level, 1 byte), id=2 (2 byte), padding
+        let ext = [0x10, 0x7F, 0x21, 0xAA, 0xBB, 0x00, 0x00, 0x00];
+        let plain = rtp_with_extension(10, &[], &ext);
+        let mut packet = plain.clone();
+        tx.protect_rtp(&mut packet).unwrap();
+
+        assert_eq!(packet[16], 0x10, "eleman başlığı açık kalmalı");
+        assert_ne!(packet[17], 0x7F, "seçili uzantı verisi şifrelenmeli");
+        assert_eq!(
+            &packet[18..21],
+            &[0x21, 0xAA, 0xBB],
+            "diğer uzantı açık kalmalı"
+        );
+
+        rx.unprotect_rtp(&mut packet).unwrap();
+        assert_eq!(packet, plain);
+    }
+
+    #[test]
+    fn test_cryptex_roundtrip() {
+        let mut policy = test_policy();
+        policy.cryptex = true;
+        let mut tx = SrtpContext::new(policy.clone()).unwrap();
+        let mut rx = SrtpContext::new(policy).unwrap();
+
+        // Uzantılı paket: profil C0DE olarak işaretlenir, tüm blok şifrelenir
+        let ext = [0x10, 0x7F, 0x21, 0xAA, 0xBB, 0x00, 0x00, 0x00];
+        let plain = rtp_with_extension(20, &[0x11223344], &ext);
+        let mut packet = plain.clone();
+        tx.protect_rtp(&mut packet).unwrap();
+        assert_eq!(&packet[16..18], &[0xC0, 0xDE]);
+        assert_ne!(&packet[12..16], &plain[12..16], "CSRC şifrelenmeli");
+        assert_ne!(&packet[20..28], &ext[..], "uzantı bloğu şifrelenmeli");
+        rx.unprotect_rtp(&mut packet).unwrap();
+        assert_eq!(packet, plain);
+
+        // Sadece CSRC içeren paket: boş uzantı bloğu eklenir ve alımda kaldırılır
+        let plain = rtp_with_extension(21, &[0x11223344], &[]);
+        let mut packet = plain.clone();
+        tx.protect_rtp(&mut packet).unwrap();
+        assert_eq!(packet[0] & 0x10, 0x10);
+        assert_eq!(&packet[16..20], &[0xC0, 0xDE, 0, 0]);
+        rx.unprotect_rtp(&mut packet).unwrap();
+        assert_eq!(packet, plain);
+    }
+}
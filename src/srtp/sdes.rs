@@ -0,0 +1,328 @@
+// sentiric-rtp-core/src/srtp/sdes.rs
+
+//! SDES (RFC 4568) `a=crypto` özniteliği çözümleme ve üretme.
+//! Örnek: `a=crypto:1 AES_CM_128_HMAC_SHA1_80 inline:<base64>|2^31|1:4 UNENCRYPTED_SRTCP`
+
+use super::{SrtpContext, SrtpError, SrtpPolicy, SrtpProfile};
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use std::fmt;
+
+/// `CryptoKeyParams::mki` değerinin taşıyabileceği en uzun MKI (byte).
+const MAX_MKI_LEN: u8 = 8;
+
+/// Tek bir `inline:` anahtar parametresi (key||salt, lifetime, MKI).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CryptoKeyParams {
+    pub master_key: Vec<u8>,
+    pub master_salt: Vec<u8>,
+    /// Master key ömrü (paket sayısı). SDP'de `2^31` veya ondalık yazılabilir.
+    pub lifetime: Option<u64>,
+    /// (MKI değeri, MKI uzunluğu byte cinsinden). Örn: `1:4`
+    /// Değer `u64` olarak tutulduğundan en fazla 8 byte'lık MKI desteklenir.
+    pub mki: Option<(u64, u8)>,
+}
+
+impl CryptoKeyParams {
+    /// MKI'ı pakete eklenecek big-endian byte dizisine çevirir.
+    pub fn mki_bytes(&self) -> Option<Vec<u8>> {
+        self.mki.map(|(value, len)| {
+            let bytes = value.to_be_bytes();
+            bytes[bytes.len() - len as usize..].to_vec()
+        })
+    }
+
+    fn parse(input: &str, profile: SrtpProfile) -> Result<Self, SrtpError> {
+        let body = input
+            .strip_prefix("inline:")
+            .ok_or_else(|| SrtpError::Parse(format!("unsupported key method: {}", input)))?;
+        let mut parts = body.split('|');
+        let encoded = parts.next().unwrap_or_default();
+        let key_salt = STANDARD
+            .decode(encoded)
+            .or_else(|_| STANDARD_NO_PAD.decode(encoded))
+            .map_err(|_| SrtpError::Parse(format!("invalid base64 key: {}", encoded)))?;
+
+        let key_len = profile.master_key_len();
+        if key_salt.len() != key_len + profile.master_salt_len() {
+            return Err(SrtpError::InvalidKeyLength);
+        }
+
+        let mut params = Self {
+            master_key: key_salt[..key_len].to_vec(),
+            master_salt: key_salt[key_len..].to_vec(),
+            lifetime: None,
+            mki: None,
+        };
+
+        for part in parts {
+            if let Some((value, len)) = part.split_once(':') {
+                let value = value
+                    .parse::<u64>()
+                    .map_err(|_| SrtpError::Parse(format!("invalid MKI: {}", part)))?;
+                // RFC 4568 1-128 byte'a izin verir; MKI'ın paketlere eksiksiz yazılabilmesi
+                // için değer ve uzunluk 8 byte'a sığmalıdır.
+                let len = len
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|l| (1..=MAX_MKI_LEN).contains(l))
+                    .filter(|l| *l == MAX_MKI_LEN || value >> (*l as u32 * 8) == 0)
+                    .ok_or_else(|| SrtpError::Parse(format!("invalid MKI length: {}", part)))?;
+                params.mki = Some((value, len));
+            } else {
+                params.lifetime = Some(parse_lifetime(part)?);
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl fmt::Display for CryptoKeyParams {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut key_salt = self.master_key.clone();
+        key_salt.extend_from_slice(&self.master_salt);
+        write!(f, "inline:{}", STANDARD.encode(key_salt))?;
+        if let Some(lifetime) = self.lifetime {
+            if lifetime.is_power_of_two() {
+                write!(f, "|2^{}", lifetime.trailing_zeros())?;
+            } else {
+                write!(f, "|{}", lifetime)?;
+            }
+        }
+        if let Some((value, len)) = self.mki {
+            write!(f, "|{}:{}", value, len)?;
+        }
+        Ok(())
+    }
+}
+
+/// RFC 4568 Bölüm 6.3 oturum parametreleri.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CryptoSessionParams {
+    /// KDR=n: Anahtar türetme oranı 2^n.
+    pub kdr: Option<u8>,
+    pub unencrypted_srtp: bool,
+    pub unencrypted_srtcp: bool,
+    pub unauthenticated_srtp: bool,
+    /// WSH=n: Replay penceresi boyutu (bilgi amaçlı saklanır).
+    pub window_size_hint: Option<u32>,
+    /// Tanınmayan parametreler (FEC_ORDER, FEC_KEY vb.) olduğu gibi korunur.
+    pub extensions: Vec<String>,
+}
+
+/// Çözümlenmiş bir `a=crypto` satırı.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CryptoAttribute {
+    pub tag: u32,
+    pub profile: SrtpProfile,
+    /// En az bir anahtar parametresi bulunur. Birden fazlası `;` ile ayrılır.
+    pub key_params: Vec<CryptoKeyParams>,
+    pub session_params: CryptoSessionParams,
+}
+
+impl CryptoAttribute {
+    /// Dışarıdan üretilmiş (rastgele) key ve salt ile yeni bir teklif satırı oluşturur.
+    /// Kütüphane rastgele sayı üretmez; anahtar materyali çağırandan gelir.
+    pub fn new(
+        tag: u32,
+        profile: SrtpProfile,
+        master_key: &[u8],
+        master_salt: &[u8],
+    ) -> Result<Self, SrtpError> {
+        if master_key.len() != profile.master_key_len()
+            || master_salt.len() != profile.master_salt_len()
+        {
+            return Err(SrtpError::InvalidKeyLength);
+        }
+        Ok(Self {
+            tag,
+            profile,
+            key_params: vec![CryptoKeyParams {
+                master_key: master_key.to_vec(),
+                master_salt: master_salt.to_vec(),
+                lifetime: None,
+                mki: None,
+            }],
+            session_params: CryptoSessionParams::default(),
+        })
+    }
+
+    /// `a=crypto:...`, `crypto:...` veya sadece değer kısmını kabul eder.
+    pub fn parse(line: &str) -> Result<Self, SrtpError> {
+        let value = line.trim();
+        let value = value.strip_prefix("a=").unwrap_or(value);
+        let value = value.strip_prefix("crypto:").unwrap_or(value);
+
+        let mut fields = value.split_whitespace();
+        let tag = fields
+            .next()
+            .and_then(|t| t.parse::<u32>().ok())
+            .ok_or_else(|| SrtpError::Parse(format!("missing or invalid tag: {}", line)))?;
+        let suite = fields
+            .next()
+            .ok_or_else(|| SrtpError::Parse(format!("missing crypto suite: {}", line)))?;
+        let profile = SrtpProfile::from_sdes_name(suite)
+            .ok_or_else(|| SrtpError::UnsupportedProfile(suite.to_string()))?;
+        let keys = fields
+            .next()
+            .ok_or_else(|| SrtpError::Parse(format!("missing key params: {}", line)))?;
+
+        let key_params = keys
+            .split(';')
+            .map(|k| CryptoKeyParams::parse(k, profile))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut session_params = CryptoSessionParams::default();
+        for param in fields {
+            match param {
+                "UNENCRYPTED_SRTP" => session_params.unencrypted_srtp = true,
+                "UNENCRYPTED_SRTCP" => session_params.unencrypted_srtcp = true,
+                "UNAUTHENTICATED_SRTP" => session_params.unauthenticated_srtp = true,
+                _ => {
+                    if let Some(n) = param.strip_prefix("KDR=") {
+                        let n =
+                            n.parse::<u8>().ok().filter(|n| *n <= 24).ok_or_else(|| {
+                                SrtpError::Parse(format!("invalid KDR: {}", param))
+                            })?;
+                        session_params.kdr = Some(n);
+                    } else if let Some(n) = param.strip_prefix("WSH=") {
+                        let n = n
+                            .parse::<u32>()
+                            .map_err(|_| SrtpError::Parse(format!("invalid WSH: {}", param)))?;
+                        session_params.window_size_hint = Some(n);
+                    } else {
+                        session_params.extensions.push(param.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            tag,
+            profile,
+            key_params,
+            session_params,
+        })
+    }
+
+    /// SDP'ye yazılacak tam satır: `a=crypto:<tag> <suite> <key-params> [<session-params>]`
+    pub fn to_sdp_line(&self) -> String {
+        format!("a=crypto:{}", self)
+    }
+
+    /// İlk anahtar parametresinden SRTP politikası üretir.
+    pub fn to_srtp_policy(&self) -> Result<SrtpPolicy, SrtpError> {
+        let key = self
+            .key_params
+            .first()
+            .ok_or_else(|| SrtpError::Parse("crypto attribute without key".to_string()))?;
+        let mut policy = SrtpPolicy::new(self.profile, &key.master_key, &key.master_salt);
+        policy.mki = key.mki_bytes();
+        policy.lifetime = key.lifetime;
+        policy.key_derivation_rate = self.session_params.kdr.map_or(0, |n| 1u64 << n);
+        policy.encrypt_rtp = !self.session_params.unencrypted_srtp;
+        policy.encrypt_rtcp = !self.session_params.unencrypted_srtcp;
+        policy.authenticate_rtp = !self.session_params.unauthenticated_srtp;
+        Ok(policy)
+    }
+
+    /// Doğrudan kullanılabilir bir SRTP context'i kurar.
+    /// Yerel satır gönderim, karşı tarafın satırı alım context'i için kullanılır.
+    pub fn to_srtp_context(&self) -> Result<SrtpContext, SrtpError> {
+        SrtpContext::new(self.to_srtp_policy()?)
+    }
+}
+
+impl fmt::Display for CryptoAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.tag, self.profile.sdes_name())?;
+        for (i, key) in self.key_params.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        let params = &self.session_params;
+        if let Some(kdr) = params.kdr {
+            write!(f, " KDR={}", kdr)?;
+        }
+        if params.unencrypted_srtp {
+            write!(f, " UNENCRYPTED_SRTP")?;
+        }
+        if params.unencrypted_srtcp {
+            write!(f, " UNENCRYPTED_SRTCP")?;
+        }
+        if params.unauthenticated_srtp {
+            write!(f, " UNAUTHENTICATED_SRTP")?;
+        }
+        if let Some(wsh) = params.window_size_hint {
+            write!(f, " WSH={}", wsh)?;
+        }
+        for ext in &params.extensions {
+            write!(f, " {}", ext)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_lifetime(value: &str) -> Result<u64, SrtpError> {
+    let parsed = match value.strip_prefix("2^") {
+        Some(exp) => exp
+            .parse::<u32>()
+            .ok()
+            .filter(|e| *e < 64)
+            .map(|e| 1u64 << e),
+        None => value.parse::<u64>().ok(),
+    };
+    parsed.ok_or_else(|| SrtpError::Parse(format!("invalid lifetime: {}", value)))
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc4568_example() {
+        let line = "a=crypto:1 AES_CM_128_HMAC_SHA1_80 \
+                    inline:PS1uQCVeeCFCanVmcjkpPywjNWhcYD0mXXtxaVBR|2^20|1:4 \
+                    UNENCRYPTED_SRTCP FEC_ORDER=FEC_SRTP";
+        let attr = CryptoAttribute::parse(line).unwrap();
+
+        assert_eq!(attr.tag, 1);
+        assert_eq!(attr.profile, SrtpProfile::AesCm128HmacSha1_80);
+        let key = &attr.key_params[0];
+        assert_eq!(key.master_key.len(), 16);
+        assert_eq!(key.master_salt.len(), 14);
+        assert_eq!(key.lifetime, Some(1 << 20));
+        assert_eq!(key.mki, Some((1, 4)));
+        assert_eq!(key.mki_bytes(), Some(vec![0, 0, 0, 1]));
+        let long_mki = line.replace("|1:4", "|1:16");
+        assert!(CryptoAttribute::parse(&long_mki).is_err());
+        let overflowing_mki = line.replace("|1:4", "|256:1");
+        assert!(CryptoAttribute::parse(&overflowing_mki).is_err());
+        assert!(attr.session_params.unencrypted_srtcp);
+        assert_eq!(attr.session_params.extensions, vec!["FEC_ORDER=FEC_SRTP"]);
+
+        // Üretilen satır tekrar çözümlendiğinde aynı sonucu vermeli
+        assert_eq!(CryptoAttribute::parse(&attr.to_sdp_line()).unwrap(), attr);
+
+        let policy = attr.to_srtp_policy().unwrap();
+        assert!(!policy.encrypt_rtcp);
+        assert!(policy.encrypt_rtp);
+        assert!(attr.to_srtp_context().is_ok());
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(matches!(
+            CryptoAttribute::parse("a=crypto:1 F8_128_HMAC_SHA1_80 inline:AAAA"),
+            Err(SrtpError::UnsupportedProfile(_))
+        ));
+        assert_eq!(
+            CryptoAttribute::parse("a=crypto:1 AES_CM_128_HMAC_SHA1_32 inline:AAAA"),
+            Err(SrtpError::InvalidKeyLength)
+        );
+        assert!(CryptoAttribute::parse("a=crypto:x AES_CM_128_HMAC_SHA1_80").is_err());
+    }
+}
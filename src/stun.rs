@@ -0,0 +1,825 @@
+// sentiric-rtp-core/src/stun.rs
+
+//! STUN (RFC 5389 / RFC 8489) mesaj kodlayıcı/çözücü ve ICE-lite yanıtlayıcı.
+//! Sadece byte dizileri işlenir (Sans-IO). RTP portuna gelen STUN paketleri
+//! `is_stun_message` ile ayrıştırılır (RFC 7983 demultiplexing).
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+type HmacSha1 = Hmac<Sha1>;
+
+pub const MAGIC_COOKIE: u32 = 0x2112_A442;
+pub const HEADER_SIZE: usize = 20;
+const FINGERPRINT_XOR: u32 = 0x5354_554E;
+const INTEGRITY_SIZE: usize = 20;
+
+/// STUN metotları.
+pub const METHOD_BINDING: u16 = 0x0001;
+// TURN metotları (RFC 8656 Bölüm 17)
+pub const METHOD_SEND: u16 = 0x0006;
+pub const METHOD_DATA: u16 = 0x0007;
+
+// Öznitelik tipleri (RFC 8489 Bölüm 18.3, RFC 8445 Bölüm 16.1)
+pub const ATTR_MAPPED_ADDRESS: u16 = 0x0001;
+pub const ATTR_USERNAME: u16 = 0x0006;
+pub const ATTR_MESSAGE_INTEGRITY: u16 = 0x0008;
+pub const ATTR_ERROR_CODE: u16 = 0x0009;
+pub const ATTR_UNKNOWN_ATTRIBUTES: u16 = 0x000A;
+pub const ATTR_CHANNEL_NUMBER: u16 = 0x000C;
+pub const ATTR_LIFETIME: u16 = 0x000D;
+pub const ATTR_XOR_PEER_ADDRESS: u16 = 0x0012;
+pub const ATTR_DATA: u16 = 0x0013;
+pub const ATTR_XOR_RELAYED_ADDRESS: u16 = 0x0016;
+pub const ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+pub const ATTR_PRIORITY: u16 = 0x0024;
+pub const ATTR_USE_CANDIDATE: u16 = 0x0025;
+pub const ATTR_SOFTWARE: u16 = 0x8022;
+pub const ATTR_FINGERPRINT: u16 = 0x8028;
+pub const ATTR_ICE_CONTROLLED: u16 = 0x8029;
+pub const ATTR_ICE_CONTROLLING: u16 = 0x802A;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StunError {
+    /// Başlık veya öznitelik için yeterli veri yok.
+    TooShort,
+    /// İlk iki bit, magic cookie veya uzunluk STUN ile uyumsuz.
+    NotStun,
+    /// Öznitelik içeriği geçersiz (tip kodu ile).
+    InvalidAttribute(u16),
+    /// Zorunlu bir öznitelik eksik.
+    MissingAttribute(u16),
+    /// MESSAGE-INTEGRITY doğrulanamadı veya kullanıcı adı eşleşmedi (401).
+    Unauthorized,
+    /// FINGERPRINT (CRC32) tutmuyor.
+    FingerprintMismatch,
+    /// Mesaj beklenen sınıf/metotta değil.
+    UnexpectedMessage,
+    /// ICE rol çakışması (ICE-lite daima controlled rolündedir) (487).
+    RoleConflict,
+    /// Öznitelik veya mesaj uzunluğu 16-bit uzunluk alanına sığmıyor (byte cinsinden).
+    TooLarge(usize),
+}
+
+impl fmt::Display for StunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StunError::TooShort => write!(f, "STUN message too short"),
+            StunError::NotStun => write!(f, "not a STUN message"),
+            StunError::InvalidAttribute(t) => write!(f, "invalid STUN attribute 0x{:04X}", t),
+            StunError::MissingAttribute(t) => write!(f, "missing STUN attribute 0x{:04X}", t),
+            StunError::Unauthorized => write!(f, "STUN message integrity check failed"),
+            StunError::FingerprintMismatch => write!(f, "STUN fingerprint mismatch"),
+            StunError::UnexpectedMessage => write!(f, "unexpected STUN message"),
+            StunError::RoleConflict => write!(f, "ICE role conflict"),
+            StunError::TooLarge(len) => write!(f, "STUN length too large: {} bytes", len),
+        }
+    }
+}
+
+impl std::error::Error for StunError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StunClass {
+    Request,
+    Indication,
+    SuccessResponse,
+    ErrorResponse,
+}
+
+impl StunClass {
+    fn bits(&self) -> u16 {
+        match self {
+            StunClass::Request => 0b00,
+            StunClass::Indication => 0b01,
+            StunClass::SuccessResponse => 0b10,
+            StunClass::ErrorResponse => 0b11,
+        }
+    }
+
+    fn from_bits(bits: u16) -> Self {
+        match bits & 0b11 {
+            0b00 => StunClass::Request,
+            0b01 => StunClass::Indication,
+            0b10 => StunClass::SuccessResponse,
+            _ => StunClass::ErrorResponse,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StunAttribute {
+    MappedAddress(SocketAddr),
+    XorMappedAddress(SocketAddr),
+    Username(String),
+    /// Çözümlemede okunan HMAC değeri. Kodlamada yok sayılır; `encode` anahtarla yeniden hesaplar.
+    MessageIntegrity([u8; INTEGRITY_SIZE]),
+    ErrorCode {
+        code: u16,
+        reason: String,
+    },
+    UnknownAttributes(Vec<u16>),
+    Priority(u32),
+    UseCandidate,
+    Software(String),
+    /// Çözümlemede okunan CRC değeri. Kodlamada yok sayılır.
+    Fingerprint(u32),
+    IceControlled(u64),
+    IceControlling(u64),
+    /// TURN kanal numarası (0x4000-0x4FFF).
+    ChannelNumber(u16),
+    /// TURN allocation/permission ömrü (saniye).
+    Lifetime(u32),
+    XorPeerAddress(SocketAddr),
+    XorRelayedAddress(SocketAddr),
+    /// Send/Data indication içinde taşınan uygulama verisi (RTP/RTCP paketi).
+    Data(Vec<u8>),
+    /// Tanınmayan öznitelikler olduğu gibi taşınır.
+    Other {
+        kind: u16,
+        value: Vec<u8>,
+    },
+}
+
+impl StunAttribute {
+    pub fn kind(&self) -> u16 {
+        match self {
+            StunAttribute::MappedAddress(_) => ATTR_MAPPED_ADDRESS,
+            StunAttribute::XorMappedAddress(_) => ATTR_XOR_MAPPED_ADDRESS,
+            StunAttribute::Username(_) => ATTR_USERNAME,
+            StunAttribute::MessageIntegrity(_) => ATTR_MESSAGE_INTEGRITY,
+            StunAttribute::ErrorCode { .. } => ATTR_ERROR_CODE,
+            StunAttribute::UnknownAttributes(_) => ATTR_UNKNOWN_ATTRIBUTES,
+            StunAttribute::Priority(_) => ATTR_PRIORITY,
+            StunAttribute::UseCandidate => ATTR_USE_CANDIDATE,
+            StunAttribute::Software(_) => ATTR_SOFTWARE,
+            StunAttribute::Fingerprint(_) => ATTR_FINGERPRINT,
+            StunAttribute::IceControlled(_) => ATTR_ICE_CONTROLLED,
+            StunAttribute::IceControlling(_) => ATTR_ICE_CONTROLLING,
+            StunAttribute::ChannelNumber(_) => ATTR_CHANNEL_NUMBER,
+            StunAttribute::Lifetime(_) => ATTR_LIFETIME,
+            StunAttribute::XorPeerAddress(_) => ATTR_XOR_PEER_ADDRESS,
+            StunAttribute::XorRelayedAddress(_) => ATTR_XOR_RELAYED_ADDRESS,
+            StunAttribute::Data(_) => ATTR_DATA,
+            StunAttribute::Other { kind, .. } => *kind,
+        }
+    }
+
+    fn encode_value(&self, transaction_id: &[u8; 12], out: &mut Vec<u8>) {
+        match self {
+            StunAttribute::MappedAddress(addr) => encode_address(*addr, None, out),
+            StunAttribute::XorMappedAddress(addr)
+            | StunAttribute::XorPeerAddress(addr)
+            | StunAttribute::XorRelayedAddress(addr) => {
+                encode_address(*addr, Some(transaction_id), out)
+            }
+            StunAttribute::Username(s) | StunAttribute::Software(s) => {
+                out.extend_from_slice(s.as_bytes())
+            }
+            StunAttribute::MessageIntegrity(mac) => out.extend_from_slice(mac),
+            StunAttribute::ErrorCode { code, reason } => {
+                out.extend_from_slice(&[0, 0, (code / 100) as u8 & 0x07, (code % 100) as u8]);
+                out.extend_from_slice(reason.as_bytes());
+            }
+            StunAttribute::UnknownAttributes(kinds) => {
+                for k in kinds {
+                    out.extend_from_slice(&k.to_be_bytes());
+                }
+            }
+            StunAttribute::Priority(p) => out.extend_from_slice(&p.to_be_bytes()),
+            StunAttribute::UseCandidate => {}
+            StunAttribute::Fingerprint(crc) => out.extend_from_slice(&crc.to_be_bytes()),
+            StunAttribute::IceControlled(t) | StunAttribute::IceControlling(t) => {
+                out.extend_from_slice(&t.to_be_bytes())
+            }
+            // RFC 8656 Bölüm 18.1: Kanal numarasını 16 bit RFFU (sıfır) takip eder
+            StunAttribute::ChannelNumber(c) => {
+                out.extend_from_slice(&c.to_be_bytes());
+                out.extend_from_slice(&[0, 0]);
+            }
+            StunAttribute::Lifetime(l) => out.extend_from_slice(&l.to_be_bytes()),
+            StunAttribute::Data(d) => out.extend_from_slice(d),
+            StunAttribute::Other { value, .. } => out.extend_from_slice(value),
+        }
+    }
+
+    fn decode(kind: u16, value: &[u8], transaction_id: &[u8; 12]) -> Result<Self, StunError> {
+        let invalid = || StunError::InvalidAttribute(kind);
+        let text = || String::from_utf8(value.to_vec()).map_err(|_| invalid());
+        Ok(match kind {
+            ATTR_MAPPED_ADDRESS => StunAttribute::MappedAddress(decode_address(value, None, kind)?),
+            ATTR_XOR_MAPPED_ADDRESS => {
+                StunAttribute::XorMappedAddress(decode_address(value, Some(transaction_id), kind)?)
+            }
+            ATTR_USERNAME => StunAttribute::Username(text()?),
+            ATTR_SOFTWARE => StunAttribute::Software(text()?),
+            ATTR_MESSAGE_INTEGRITY => {
+                StunAttribute::MessageIntegrity(value.try_into().map_err(|_| invalid())?)
+            }
+            ATTR_ERROR_CODE => {
+                if value.len() < 4 {
+                    return Err(invalid());
+                }
+                StunAttribute::ErrorCode {
+                    code: (value[2] & 0x07) as u16 * 100 + value[3] as u16,
+                    reason: String::from_utf8_lossy(&value[4..]).into_owned(),
+                }
+            }
+            ATTR_UNKNOWN_ATTRIBUTES => StunAttribute::UnknownAttributes(
+                value
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect(),
+            ),
+            ATTR_PRIORITY => StunAttribute::Priority(u32::from_be_bytes(
+                value.try_into().map_err(|_| invalid())?,
+            )),
+            ATTR_USE_CANDIDATE => StunAttribute::UseCandidate,
+            ATTR_FINGERPRINT => StunAttribute::Fingerprint(u32::from_be_bytes(
+                value.try_into().map_err(|_| invalid())?,
+            )),
+            ATTR_ICE_CONTROLLED => StunAttribute::IceControlled(u64::from_be_bytes(
+                value.try_into().map_err(|_| invalid())?,
+            )),
+            ATTR_ICE_CONTROLLING => StunAttribute::IceControlling(u64::from_be_bytes(
+                value.try_into().map_err(|_| invalid())?,
+            )),
+            ATTR_CHANNEL_NUMBER => {
+                if value.len() != 4 {
+                    return Err(invalid());
+                }
+                StunAttribute::ChannelNumber(u16::from_be_bytes([value[0], value[1]]))
+            }
+            ATTR_LIFETIME => StunAttribute::Lifetime(u32::from_be_bytes(
+                value.try_into().map_err(|_| invalid())?,
+            )),
+            ATTR_XOR_PEER_ADDRESS => {
+                StunAttribute::XorPeerAddress(decode_address(value, Some(transaction_id), kind)?)
+            }
+            ATTR_XOR_RELAYED_ADDRESS => {
+                StunAttribute::XorRelayedAddress(decode_address(value, Some(transaction_id), kind)?)
+            }
+            ATTR_DATA => StunAttribute::Data(value.to_vec()),
+            _ => StunAttribute::Other {
+                kind,
+                value: value.to_vec(),
+            },
+        })
+    }
+}
+
+/// (X)OR-MAPPED-ADDRESS kodlaması. `xor` verilirse XOR-MAPPED-ADDRESS üretilir.
+pub(crate) fn encode_address(addr: SocketAddr, xor: Option<&[u8; 12]>, out: &mut Vec<u8>) {
+    let cookie = MAGIC_COOKIE.to_be_bytes();
+    let port = if xor.is_some() {
+        addr.port() ^ (MAGIC_COOKIE >> 16) as u16
+    } else {
+        addr.port()
+    };
+    match addr.ip() {
+        IpAddr::V4(ip) => {
+            out.extend_from_slice(&[0, 0x01]);
+            out.extend_from_slice(&port.to_be_bytes());
+            let mut octets = ip.octets();
+            if xor.is_some() {
+                octets.iter_mut().zip(cookie).for_each(|(o, c)| *o ^= c);
+            }
+            out.extend_from_slice(&octets);
+        }
+        IpAddr::V6(ip) => {
+            out.extend_from_slice(&[0, 0x02]);
+            out.extend_from_slice(&port.to_be_bytes());
+            let mut octets = ip.octets();
+            if let Some(tid) = xor {
+                let mask = cookie.iter().chain(tid.iter());
+                octets.iter_mut().zip(mask).for_each(|(o, m)| *o ^= m);
+            }
+            out.extend_from_slice(&octets);
+        }
+    }
+}
+
+pub(crate) fn decode_address(
+    value: &[u8],
+    xor: Option<&[u8; 12]>,
+    kind: u16,
+) -> Result<SocketAddr, StunError> {
+    let invalid = StunError::InvalidAttribute(kind);
+    if value.len() < 4 {
+        return Err(invalid);
+    }
+    let cookie = MAGIC_COOKIE.to_be_bytes();
+    let mut port = u16::from_be_bytes([value[2], value[3]]);
+    if xor.is_some() {
+        port ^= (MAGIC_COOKIE >> 16) as u16;
+    }
+    match value[1] {
+        0x01 if value.len() == 8 => {
+            let mut octets: [u8; 4] = value[4..8].try_into().map_err(|_| invalid.clone())?;
+            if xor.is_some() {
+                octets.iter_mut().zip(cookie).for_each(|(o, c)| *o ^= c);
+            }
+            Ok(SocketAddr::new(IpAddr::V4(Ipv4Addr::from(octets)), port))
+        }
+        0x02 if value.len() == 20 => {
+            let mut octets: [u8; 16] = value[4..20].try_into().map_err(|_| invalid.clone())?;
+            if let Some(tid) = xor {
+                let mask = cookie.iter().chain(tid.iter());
+                octets.iter_mut().zip(mask).for_each(|(o, m)| *o ^= m);
+            }
+            Ok(SocketAddr::new(IpAddr::V6(Ipv6Addr::from(octets)), port))
+        }
+        _ => Err(invalid),
+    }
+}
+
+/// RFC 7983: İlk byte 0-3 aralığında, magic cookie doğru ve uzunluk 4'ün katıysa STUN'dur.
+/// RTP (128-191) ve DTLS (20-63) paketlerinden ayırmak için kullanılır.
+pub fn is_stun_message(data: &[u8]) -> bool {
+    data.len() >= HEADER_SIZE
+        && data[0] < 4
+        && u32::from_be_bytes([data[4], data[5], data[6], data[7]]) == MAGIC_COOKIE
+        && u16::from_be_bytes([data[2], data[3]]).is_multiple_of(4)
+}
+
+/// Çözümlenmiş veya kodlanacak STUN mesajı.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StunMessage {
+    pub class: StunClass,
+    pub method: u16,
+    pub transaction_id: [u8; 12],
+    pub attributes: Vec<StunAttribute>,
+}
+
+impl StunMessage {
+    pub fn new(class: StunClass, method: u16, transaction_id: [u8; 12]) -> Self {
+        Self {
+            class,
+            method,
+            transaction_id,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// transaction_id çağıran tarafından (rastgele) üretilmelidir.
+    pub fn binding_request(transaction_id: [u8; 12]) -> Self {
+        Self::new(StunClass::Request, METHOD_BINDING, transaction_id)
+    }
+
+    /// İsteği gönderen adresi XOR-MAPPED-ADDRESS olarak içeren başarı yanıtı.
+    pub fn binding_success(transaction_id: [u8; 12], mapped: SocketAddr) -> Self {
+        let mut msg = Self::new(StunClass::SuccessResponse, METHOD_BINDING, transaction_id);
+        msg.attributes.push(StunAttribute::XorMappedAddress(mapped));
+        msg
+    }
+
+    pub fn error_response(request: &StunMessage, code: u16, reason: &str) -> Self {
+        let mut msg = Self::new(
+            StunClass::ErrorResponse,
+            request.method,
+            request.transaction_id,
+        );
+        msg.attributes.push(StunAttribute::ErrorCode {
+            code,
+            reason: reason.to_string(),
+        });
+        msg
+    }
+
+    pub fn with_attribute(mut self, attr: StunAttribute) -> Self {
+        self.attributes.push(attr);
+        self
+    }
+
+    pub fn get(&self, kind: u16) -> Option<&StunAttribute> {
+        self.attributes.iter().find(|a| a.kind() == kind)
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|a| match a {
+            StunAttribute::Username(u) => Some(u.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn priority(&self) -> Option<u32> {
+        self.attributes.iter().find_map(|a| match a {
+            StunAttribute::Priority(p) => Some(*p),
+            _ => None,
+        })
+    }
+
+    pub fn use_candidate(&self) -> bool {
+        self.get(ATTR_USE_CANDIDATE).is_some()
+    }
+
+    pub fn xor_mapped_address(&self) -> Option<SocketAddr> {
+        self.attributes.iter().find_map(|a| match a {
+            StunAttribute::XorMappedAddress(addr) => Some(*addr),
+            _ => None,
+        })
+    }
+
+    fn message_type(&self) -> u16 {
+        let m = self.method & 0x0FFF;
+        let c = self.class.bits();
+        (m & 0x000F) | ((m & 0x0070) << 1) | ((m & 0x0F80) << 2) | ((c & 1) << 4) | ((c & 2) << 7)
+    }
+
+    /// Mesajı byte dizisine çevirir. `integrity_key` verilirse MESSAGE-INTEGRITY,
+    /// `fingerprint` true ise FINGERPRINT sona eklenir (listedeki eski değerler yok sayılır).
+    /// Bir öznitelik veya mesajın toplam uzunluğu 65535 byte'ı aşarsa `StunError::TooLarge`.
+    pub fn encode(
+        &self,
+        integrity_key: Option<&[u8]>,
+        fingerprint: bool,
+    ) -> Result<Vec<u8>, StunError> {
+        let mut out = Vec::with_capacity(HEADER_SIZE + 64);
+        out.extend_from_slice(&self.message_type().to_be_bytes());
+        out.extend_from_slice(&[0, 0]);
+        out.extend_from_slice(&MAGIC_COOKIE.to_be_bytes());
+        out.extend_from_slice(&self.transaction_id);
+
+        for attr in &self.attributes {
+            if matches!(
+                attr,
+                StunAttribute::MessageIntegrity(_) | StunAttribute::Fingerprint(_)
+            ) {
+                continue;
+            }
+            let start = out.len();
+            out.extend_from_slice(&attr.kind().to_be_bytes());
+            out.extend_from_slice(&[0, 0]);
+            attr.encode_value(&self.transaction_id, &mut out);
+            let len = out.len() - start - 4;
+            let len = u16::try_from(len).map_err(|_| StunError::TooLarge(len))?;
+            out[start + 2..start + 4].copy_from_slice(&len.to_be_bytes());
+            pad_to_word(&mut out);
+        }
+
+        // MESSAGE-INTEGRITY ve FINGERPRINT dahil toplam uzunluk 16 bite sığmalı
+        let total = out.len() - HEADER_SIZE
+            + integrity_key.map_or(0, |_| 4 + INTEGRITY_SIZE)
+            + if fingerprint { 8 } else { 0 };
+        if total > u16::MAX as usize {
+            return Err(StunError::TooLarge(total));
+        }
+        if let Some(key) = integrity_key {
+            // Uzunluk alanı, MESSAGE-INTEGRITY'yi de kapsayacak şekilde ayarlanır
+            let len = out.len() - HEADER_SIZE + 4 + INTEGRITY_SIZE;
+            set_length(&mut out, len);
+            let mac = hmac_sha1(key, &out);
+            out.extend_from_slice(&ATTR_MESSAGE_INTEGRITY.to_be_bytes());
+            out.extend_from_slice(&(INTEGRITY_SIZE as u16).to_be_bytes());
+            out.extend_from_slice(&mac);
+        }
+        if fingerprint {
+            let len = out.len() - HEADER_SIZE + 8;
+            set_length(&mut out, len);
+            let crc = crc32fast::hash(&out) ^ FINGERPRINT_XOR;
+            out.extend_from_slice(&ATTR_FINGERPRINT.to_be_bytes());
+            out.extend_from_slice(&4u16.to_be_bytes());
+            out.extend_from_slice(&crc.to_be_bytes());
+        }
+        let len = out.len() - HEADER_SIZE;
+        set_length(&mut out, len);
+        Ok(out)
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, StunError> {
+        if data.len() < HEADER_SIZE {
+            return Err(StunError::TooShort);
+        }
+        if !is_stun_message(data) {
+            return Err(StunError::NotStun);
+        }
+        let msg_type = u16::from_be_bytes([data[0], data[1]]);
+        let length = u16::from_be_bytes([data[2], data[3]]) as usize;
+        if data.len() < HEADER_SIZE + length {
+            return Err(StunError::TooShort);
+        }
+        let mut transaction_id = [0u8; 12];
+        transaction_id.copy_from_slice(&data[8..20]);
+
+        let method = (msg_type & 0x000F) | ((msg_type >> 1) & 0x0070) | ((msg_type >> 2) & 0x0F80);
+        let class = StunClass::from_bits(((msg_type >> 4) & 1) | ((msg_type >> 7) & 2));
+
+        let mut attributes = Vec::new();
+        for attribute in AttributeIter::new(&data[..HEADER_SIZE + length]) {
+            let (kind, _, value) = attribute?;
+            attributes.push(StunAttribute::decode(kind, value, &transaction_id)?);
+        }
+
+        Ok(Self {
+            class,
+            method,
+            transaction_id,
+            attributes,
+        })
+    }
+}
+
+/// Ham mesajdaki (tip, öznitelik başlangıcı, değer) üçlülerini gezer.
+/// Öznitelik başlığı veya bildirilen değer uzunluğu mesaj sonunu aşarsa bir kez
+/// `StunError::TooShort` döner ve gezinme biter.
+pub(crate) struct AttributeIter<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> AttributeIter<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: HEADER_SIZE,
+        }
+    }
+}
+
+impl<'a> Iterator for AttributeIter<'a> {
+    type Item = Result<(u16, usize, &'a [u8]), StunError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.data.len() {
+            return None;
+        }
+        let start = self.pos;
+        let truncated = |iter: &mut Self| {
+            iter.pos = iter.data.len();
+            Some(Err(StunError::TooShort))
+        };
+        if start + 4 > self.data.len() {
+            return truncated(self);
+        }
+        let kind = u16::from_be_bytes([self.data[start], self.data[start + 1]]);
+        let len = u16::from_be_bytes([self.data[start + 2], self.data[start + 3]]) as usize;
+        let value_end = start + 4 + len;
+        if value_end > self.data.len() {
+            return truncated(self);
+        }
+        self.pos = start + 4 + len.div_ceil(4) * 4;
+        Some(Ok((kind, start, &self.data[start + 4..value_end])))
+    }
+}
+
+/// İlk `kind` özniteliğinin başlangıcı ve değeri. Öncesinde kesik bir öznitelik varsa
+/// `TooShort`, hiç yoksa `MissingAttribute` döner.
+fn find_attribute(data: &[u8], kind: u16) -> Result<(usize, &[u8]), StunError> {
+    for attribute in AttributeIter::new(data) {
+        let (found, start, value) = attribute?;
+        if found == kind {
+            return Ok((start, value));
+        }
+    }
+    Err(StunError::MissingAttribute(kind))
+}
+
+/// Ham mesajın MESSAGE-INTEGRITY değerini `key` ile doğrular (RFC 8489 Bölüm 14.5).
+pub fn verify_message_integrity(data: &[u8], key: &[u8]) -> Result<(), StunError> {
+    let length = stun_length(data)?;
+    let (start, value) = find_attribute(&data[..HEADER_SIZE + length], ATTR_MESSAGE_INTEGRITY)?;
+
+    let mut signed = data[..start].to_vec();
+    set_length(&mut signed, start - HEADER_SIZE + 4 + INTEGRITY_SIZE);
+    let expected = hmac_sha1(key, &signed);
+    if value.len() != INTEGRITY_SIZE
+        || expected
+            .iter()
+            .zip(value)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            != 0
+    {
+        return Err(StunError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Ham mesajın FINGERPRINT değerini doğrular. Öznitelik yoksa `MissingAttribute` döner.
+pub fn verify_fingerprint(data: &[u8]) -> Result<(), StunError> {
+    let length = stun_length(data)?;
+    let (start, value) = find_attribute(&data[..HEADER_SIZE + length], ATTR_FINGERPRINT)?;
+    let received: [u8; 4] = value
+        .try_into()
+        .map_err(|_| StunError::InvalidAttribute(ATTR_FINGERPRINT))?;
+
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&data[..2]);
+    hasher.update(&((start - HEADER_SIZE + 8) as u16).to_be_bytes());
+    hasher.update(&data[4..start]);
+    if hasher.finalize() ^ FINGERPRINT_XOR != u32::from_be_bytes(received) {
+        return Err(StunError::FingerprintMismatch);
+    }
+    Ok(())
+}
+
+pub(crate) fn stun_length(data: &[u8]) -> Result<usize, StunError> {
+    if !is_stun_message(data) {
+        return Err(StunError::NotStun);
+    }
+    let length = u16::from_be_bytes([data[2], data[3]]) as usize;
+    if data.len() < HEADER_SIZE + length {
+        return Err(StunError::TooShort);
+    }
+    Ok(length)
+}
+
+fn set_length(msg: &mut [u8], len: usize) {
+    msg[2..4].copy_from_slice(&(len as u16).to_be_bytes());
+}
+
+fn pad_to_word(out: &mut Vec<u8>) {
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+}
+
+fn hmac_sha1(key: &[u8], data: &[u8]) -> [u8; INTEGRITY_SIZE] {
+    // HMAC her uzunlukta anahtarı kabul eder; hata dalı pratikte oluşmaz.
+    let mut mac = match <HmacSha1 as Mac>::new_from_slice(key) {
+        Ok(mac) => mac,
+        Err(_) => return [0; INTEGRITY_SIZE],
+    };
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// SDP `a=ice-ufrag` / `a=ice-pwd` değerlerinden oluşan kısa süreli kimlik bilgileri.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IceCredentials {
+    pub local_ufrag: String,
+    pub local_pwd: String,
+    /// Karşı tarafın ufrag'i biliniyorsa USERNAME'in ikinci kısmı da doğrulanır.
+    pub remote_ufrag: Option<String>,
+}
+
+/// ICE-lite tarafından doğrulanmış bir bağlantı kontrolü (connectivity check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IceCheck {
+    /// Kaynağa geri gönderilecek, imzalı Binding başarı yanıtı.
+    pub response: Vec<u8>,
+    /// Kontrolü yapan (controlling) taraf bu çifti aday olarak seçti mi? (USE-CANDIDATE)
+    pub use_candidate: bool,
+    pub priority: Option<u32>,
+    pub source: SocketAddr,
+}
+
+/// ICE-lite yanıtlayıcı (RFC 8445 Bölüm 7.3). Lite ajan daima controlled roldedir.
+/// Geçerli bir Binding isteği için imzalı yanıt üretir; hatalı istekler için `Err` döner
+/// (çağıran isterse `StunMessage::error_response` ile 400/401/487 gönderebilir).
+pub fn handle_ice_binding_request(
+    data: &[u8],
+    source: SocketAddr,
+    credentials: &IceCredentials,
+) -> Result<IceCheck, StunError> {
+    let request = StunMessage::decode(data)?;
+    if request.class != StunClass::Request || request.method != METHOD_BINDING {
+        return Err(StunError::UnexpectedMessage);
+    }
+    match verify_fingerprint(data) {
+        Ok(()) | Err(StunError::MissingAttribute(_)) => {}
+        Err(e) => return Err(e),
+    }
+
+    // USERNAME = "<bizim ufrag>:<karşı taraf ufrag>"
+    let username = request
+        .username()
+        .ok_or(StunError::MissingAttribute(ATTR_USERNAME))?;
+    let (local, remote) = username.split_once(':').ok_or(StunError::Unauthorized)?;
+    if local != credentials.local_ufrag {
+        return Err(StunError::Unauthorized);
+    }
+    if let Some(expected) = &credentials.remote_ufrag {
+        if remote != expected {
+            return Err(StunError::Unauthorized);
+        }
+    }
+    verify_message_integrity(data, credentials.local_pwd.as_bytes())?;
+
+    if request.get(ATTR_ICE_CONTROLLED).is_some() {
+        return Err(StunError::RoleConflict);
+    }
+
+    let response = StunMessage::binding_success(request.transaction_id, source)
+        .encode(Some(credentials.local_pwd.as_bytes()), true)?;
+
+    Ok(IceCheck {
+        response,
+        use_candidate: request.use_candidate(),
+        priority: request.priority(),
+        source,
+    })
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        let s: String = s.split_whitespace().collect();
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    const RFC5769_PASSWORD: &str = "VOkJxbRl1RmTxUk/WvJxBt";
+
+    #[test]
+    fn test_rfc5769_sample_request() {
+        let data = hex("000100582112a442b7e7a701bc34d686fa87dfae
+             802200105354554e207465737420636c69656e74
+             002400046e0001ff
+             80290008932ff9b151263b36
+             000600096576746a3a68367659202020
+             000800149aeaa70cbfd8cb56781ef2b5b2d3f249c1b571a2
+             80280004e57a3bcf");
+        assert!(is_stun_message(&data));
+        let msg = StunMessage::decode(&data).unwrap();
+        assert_eq!(msg.class, StunClass::Request);
+        assert_eq!(msg.method, METHOD_BINDING);
+        assert_eq!(msg.username(), Some("evtj:h6vY"));
+        assert_eq!(msg.priority(), Some(0x6e0001ff));
+        assert!(msg
+            .attributes
+            .contains(&StunAttribute::IceControlled(0x932ff9b151263b36)));
+
+        verify_fingerprint(&data).unwrap();
+        verify_message_integrity(&data, RFC5769_PASSWORD.as_bytes()).unwrap();
+        assert_eq!(
+            verify_message_integrity(&data, b"wrong"),
+            Err(StunError::Unauthorized)
+        );
+
+        // Aynı içerik yeniden kodlandığında (padding hariç) aynı imza üretilmeli
+        let reencoded = msg.encode(Some(RFC5769_PASSWORD.as_bytes()), true).unwrap();
+        verify_message_integrity(&reencoded, RFC5769_PASSWORD.as_bytes()).unwrap();
+        verify_fingerprint(&reencoded).unwrap();
+    }
+
+    #[test]
+    fn test_truncated_attribute_is_rejected() {
+        let mut data = StunMessage::binding_request([9; 12])
+            .with_attribute(StunAttribute::Priority(7))
+            .encode(None, false)
+            .unwrap();
+        assert!(StunMessage::decode(&data).is_ok());
+
+        // Değer uzunluğu mesaj sonunu aşıyor
+        data[HEADER_SIZE + 3] = 8;
+        assert_eq!(StunMessage::decode(&data), Err(StunError::TooShort));
+        assert_eq!(verify_fingerprint(&data), Err(StunError::TooShort));
+    }
+
+    #[test]
+    fn test_xor_mapped_address_roundtrip() {
+        let tid = [7u8; 12];
+        for addr in [
+            "192.0.2.1:32853",
+            "[2001:db8:1234:5678:11:2233:4455:6677]:32853",
+        ] {
+            let addr: SocketAddr = addr.parse().unwrap();
+            let encoded = StunMessage::binding_success(tid, addr)
+                .encode(None, true)
+                .unwrap();
+            let decoded = StunMessage::decode(&encoded).unwrap();
+            assert_eq!(decoded.xor_mapped_address(), Some(addr));
+            assert_eq!(decoded.class, StunClass::SuccessResponse);
+        }
+    }
+
+    #[test]
+    fn test_ice_lite_responder() {
+        let creds = IceCredentials {
+            local_ufrag: "lite".to_string(),
+            local_pwd: "litepassword1234567890".to_string(),
+            remote_ufrag: Some("full".to_string()),
+        };
+        let source: SocketAddr = "203.0.113.7:40000".parse().unwrap();
+        let request = StunMessage::binding_request([1; 12])
+            .with_attribute(StunAttribute::Username("lite:full".to_string()))
+            .with_attribute(StunAttribute::Priority(1845501695))
+            .with_attribute(StunAttribute::IceControlling(42))
+            .with_attribute(StunAttribute::UseCandidate);
+
+        let raw = request
+            .encode(Some(creds.local_pwd.as_bytes()), true)
+            .unwrap();
+        let check = handle_ice_binding_request(&raw, source, &creds).unwrap();
+        assert!(check.use_candidate);
+        assert_eq!(check.priority, Some(1845501695));
+
+        verify_message_integrity(&check.response, creds.local_pwd.as_bytes()).unwrap();
+        let response = StunMessage::decode(&check.response).unwrap();
+        assert_eq!(response.transaction_id, [1; 12]);
+        assert_eq!(response.xor_mapped_address(), Some(source));
+
+        let forged = request.encode(Some(b"guess"), true).unwrap();
+        assert_eq!(
+            handle_ice_binding_request(&forged, source, &creds),
+            Err(StunError::Unauthorized)
+        );
+    }
+}
@@ -0,0 +1,324 @@
+// sentiric-rtp-core/src/turn.rs
+
+//! TURN (RFC 8656) medya çerçeveleri: ChannelData ve Send/Data indication.
+//! Relay üzerinden gelen medya bu katmanda açılır, ardından RTP/SRTP çözücüye verilir.
+//! Allocation/permission yönetimi (istek-yanıt akışı) uygulamanın sorumluluğundadır.
+
+use crate::stun::{
+    self, AttributeIter, StunAttribute, StunClass, StunError, StunMessage, ATTR_DATA,
+    ATTR_XOR_PEER_ADDRESS, HEADER_SIZE, METHOD_DATA, METHOD_SEND,
+};
+use std::fmt;
+use std::net::SocketAddr;
+use std::ops::RangeInclusive;
+
+/// RFC 8656 Bölüm 12: İstemcinin kullanabileceği kanal numaraları.
+pub const CHANNEL_NUMBER_RANGE: RangeInclusive<u16> = 0x4000..=0x4FFF;
+pub const CHANNEL_DATA_HEADER_SIZE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TurnError {
+    /// Başlık veya bildirilen uzunluk için yeterli veri yok.
+    TooShort,
+    /// ChannelData verisi 16-bit uzunluk alanına sığmıyor.
+    PayloadTooLarge(usize),
+    /// Kanal numarası 0x4000-0x4FFF aralığı dışında.
+    InvalidChannel(u16),
+    /// Paket ChannelData veya Send/Data indication değil.
+    NotTurn,
+    /// Indication içinde zorunlu öznitelik (XOR-PEER-ADDRESS, DATA) eksik.
+    MissingAttribute(u16),
+    Stun(StunError),
+}
+
+impl fmt::Display for TurnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TurnError::TooShort => write!(f, "TURN frame too short"),
+            TurnError::PayloadTooLarge(len) => {
+                write!(f, "TURN ChannelData payload too large: {} bytes", len)
+            }
+            TurnError::InvalidChannel(c) => write!(f, "invalid TURN channel 0x{:04X}", c),
+            TurnError::NotTurn => write!(f, "not a TURN media frame"),
+            TurnError::MissingAttribute(t) => write!(f, "missing TURN attribute 0x{:04X}", t),
+            TurnError::Stun(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for TurnError {}
+
+impl From<StunError> for TurnError {
+    fn from(e: StunError) -> Self {
+        TurnError::Stun(e)
+    }
+}
+
+/// RFC 7983: İlk byte 64-79 ise paket ChannelData'dır.
+pub fn is_channel_data(data: &[u8]) -> bool {
+    data.len() >= CHANNEL_DATA_HEADER_SIZE && (0x40..=0x4F).contains(&data[0])
+}
+
+/// Çözümlenmiş ChannelData çerçevesi (veri kopyalanmaz).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelData<'a> {
+    pub channel: u16,
+    pub data: &'a [u8],
+}
+
+impl<'a> ChannelData<'a> {
+    /// Tamponun başındaki ChannelData çerçevesini çözer.
+    /// Dönen ikinci değer tüketilen byte sayısıdır (varsa 4 byte hizalama dolgusu dahil);
+    /// TCP akışında bir sonraki çerçeve bu ofsetten başlar.
+    pub fn decode(buf: &'a [u8]) -> Result<(Self, usize), TurnError> {
+        if buf.len() < CHANNEL_DATA_HEADER_SIZE {
+            return Err(TurnError::TooShort);
+        }
+        let channel = u16::from_be_bytes([buf[0], buf[1]]);
+        if !CHANNEL_NUMBER_RANGE.contains(&channel) {
+            return Err(TurnError::InvalidChannel(channel));
+        }
+        let len = u16::from_be_bytes([buf[2], buf[3]]) as usize;
+        let end = CHANNEL_DATA_HEADER_SIZE + len;
+        if buf.len() < end {
+            return Err(TurnError::TooShort);
+        }
+        // UDP'de dolgu opsiyoneldir; tampon bittiyse dolgu yok sayılır.
+        let consumed = (CHANNEL_DATA_HEADER_SIZE + len.div_ceil(4) * 4).min(buf.len());
+        Ok((
+            Self {
+                channel,
+                data: &buf[CHANNEL_DATA_HEADER_SIZE..end],
+            },
+            consumed,
+        ))
+    }
+
+    /// Çerçeveyi `out` sonuna ekler. `pad` true ise (TCP/TLS üzerinde zorunlu)
+    /// uzunluk 4'ün katına tamamlanır; uzunluk alanı dolguyu içermez.
+    pub fn encode(&self, pad: bool, out: &mut Vec<u8>) -> Result<(), TurnError> {
+        if !CHANNEL_NUMBER_RANGE.contains(&self.channel) {
+            return Err(TurnError::InvalidChannel(self.channel));
+        }
+        if self.data.len() > u16::MAX as usize {
+            return Err(TurnError::PayloadTooLarge(self.data.len()));
+        }
+        out.extend_from_slice(&self.channel.to_be_bytes());
+        out.extend_from_slice(&(self.data.len() as u16).to_be_bytes());
+        out.extend_from_slice(self.data);
+        if pad {
+            out.resize(out.len() + (4 - self.data.len() % 4) % 4, 0);
+        }
+        Ok(())
+    }
+}
+
+/// Send/Data indication içindeki eş adresi ve veri (veri kopyalanmaz).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerData<'a> {
+    pub peer: SocketAddr,
+    pub data: &'a [u8],
+}
+
+/// İstemciden relay'e: XOR-PEER-ADDRESS ve DATA içeren Send indication üretir.
+/// Veri STUN uzunluk alanlarına sığmazsa `TurnError::PayloadTooLarge`.
+pub fn encode_send_indication(
+    transaction_id: [u8; 12],
+    peer: SocketAddr,
+    data: &[u8],
+) -> Result<Vec<u8>, TurnError> {
+    indication(METHOD_SEND, transaction_id, peer, data)
+}
+
+/// Relay'den istemciye giden Data indication üretir (test ve relay simülasyonu için).
+/// Veri STUN uzunluk alanlarına sığmazsa `TurnError::PayloadTooLarge`.
+pub fn encode_data_indication(
+    transaction_id: [u8; 12],
+    peer: SocketAddr,
+    data: &[u8],
+) -> Result<Vec<u8>, TurnError> {
+    indication(METHOD_DATA, transaction_id, peer, data)
+}
+
+fn indication(
+    method: u16,
+    transaction_id: [u8; 12],
+    peer: SocketAddr,
+    data: &[u8],
+) -> Result<Vec<u8>, TurnError> {
+    if data.len() > u16::MAX as usize {
+        return Err(TurnError::PayloadTooLarge(data.len()));
+    }
+    StunMessage::new(StunClass::Indication, method, transaction_id)
+        .with_attribute(StunAttribute::XorPeerAddress(peer))
+        .with_attribute(StunAttribute::Data(data.to_vec()))
+        .encode(None, false)
+        .map_err(|e| match e {
+            StunError::TooLarge(_) => TurnError::PayloadTooLarge(data.len()),
+            e => TurnError::Stun(e),
+        })
+}
+
+/// Data (veya Send) indication'ı çözer. DATA özniteliği kopyalanmadan döndürülür.
+pub fn decode_peer_indication(buf: &[u8]) -> Result<(u16, PeerData<'_>), TurnError> {
+    let length = stun::stun_length(buf)?;
+    let msg_type = u16::from_be_bytes([buf[0], buf[1]]);
+    // Indication sınıfı: C1=0, C0=1 (0x0010 biti)
+    let method = (msg_type & 0x000F) | ((msg_type >> 1) & 0x0070) | ((msg_type >> 2) & 0x0F80);
+    if msg_type & 0x0110 != 0x0010 || (method != METHOD_DATA && method != METHOD_SEND) {
+        return Err(TurnError::NotTurn);
+    }
+    let transaction_id: [u8; 12] = buf[8..HEADER_SIZE]
+        .try_into()
+        .map_err(|_| TurnError::TooShort)?;
+
+    let mut peer = None;
+    let mut data = None;
+    for attribute in AttributeIter::new(&buf[..HEADER_SIZE + length]) {
+        let (kind, _, value) = attribute?;
+        match kind {
+            ATTR_XOR_PEER_ADDRESS if peer.is_none() => {
+                peer = Some(stun::decode_address(value, Some(&transaction_id), kind)?)
+            }
+            ATTR_DATA if data.is_none() => data = Some(value),
+            _ => {}
+        }
+    }
+    Ok((
+        method,
+        PeerData {
+            peer: peer.ok_or(TurnError::MissingAttribute(ATTR_XOR_PEER_ADDRESS))?,
+            data: data.ok_or(TurnError::MissingAttribute(ATTR_DATA))?,
+        },
+    ))
+}
+
+/// Relay'den gelen bir datagramın açılmış hali.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayedMedia<'a> {
+    Channel(ChannelData<'a>),
+    Indication(PeerData<'a>),
+}
+
+impl<'a> RelayedMedia<'a> {
+    /// İçerideki medya paketi (RTP, RTCP veya DTLS).
+    pub fn payload(&self) -> &'a [u8] {
+        match self {
+            RelayedMedia::Channel(c) => c.data,
+            RelayedMedia::Indication(p) => p.data,
+        }
+    }
+}
+
+/// Relay sunucusundan gelen datagramı açar. ChannelData veya Data indication değilse
+/// `Ok(None)` döner (örn. Allocate/Refresh yanıtları uygulamaya bırakılır).
+pub fn unwrap_relayed(buf: &[u8]) -> Result<Option<RelayedMedia<'_>>, TurnError> {
+    if is_channel_data(buf) {
+        let (frame, _) = ChannelData::decode(buf)?;
+        return Ok(Some(RelayedMedia::Channel(frame)));
+    }
+    if stun::is_stun_message(buf) {
+        return match decode_peer_indication(buf) {
+            Ok((METHOD_DATA, peer)) => Ok(Some(RelayedMedia::Indication(peer))),
+            Ok(_) | Err(TurnError::NotTurn) => Ok(None),
+            Err(e) => Err(e),
+        };
+    }
+    Ok(None)
+}
+
+// --- UNIT TESTS ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_data_roundtrip_with_padding() {
+        let rtp = [0x80u8, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0, 0, 1, 0xAA];
+        let mut out = Vec::new();
+        ChannelData {
+            channel: 0x4001,
+            data: &rtp,
+        }
+        .encode(true, &mut out)
+        .unwrap();
+        assert_eq!(out.len(), 4 + 16);
+        assert_eq!(&out[2..4], &[0, 13]);
+
+        // TCP akışında iki çerçeve art arda
+        let mut stream = out.clone();
+        stream.extend_from_slice(&out);
+        let (first, consumed) = ChannelData::decode(&stream).unwrap();
+        assert_eq!(first.channel, 0x4001);
+        assert_eq!(first.data, &rtp);
+        assert_eq!(consumed, 20);
+        let (second, _) = ChannelData::decode(&stream[consumed..]).unwrap();
+        assert_eq!(second, first);
+
+        assert_eq!(
+            ChannelData::decode(&[0x50, 0x00, 0x00, 0x00]),
+            Err(TurnError::InvalidChannel(0x5000))
+        );
+        let invalid = ChannelData {
+            channel: 0x3FFF,
+            data: &rtp,
+        };
+        assert!(invalid.encode(false, &mut Vec::new()).is_err());
+
+        let jumbo = vec![0u8; u16::MAX as usize + 1];
+        let oversized = ChannelData {
+            channel: 0x4001,
+            data: &jumbo,
+        };
+        let mut out = Vec::new();
+        assert_eq!(
+            oversized.encode(false, &mut out),
+            Err(TurnError::PayloadTooLarge(65536))
+        );
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_send_and_data_indications() {
+        let peer: SocketAddr = "198.51.100.20:5004".parse().unwrap();
+        let rtp = vec![0x80u8; 33];
+
+        let send = encode_send_indication([3; 12], peer, &rtp).unwrap();
+        let (method, decoded) = decode_peer_indication(&send).unwrap();
+        assert_eq!(method, METHOD_SEND);
+        assert_eq!(decoded.peer, peer);
+        assert_eq!(decoded.data, &rtp[..]);
+        // Relay'den gelen Send indication medya olarak kabul edilmez
+        assert_eq!(unwrap_relayed(&send), Ok(None));
+
+        let data = encode_data_indication([4; 12], peer, &rtp).unwrap();
+        let relayed = unwrap_relayed(&data).unwrap().unwrap();
+        assert_eq!(relayed.payload(), &rtp[..]);
+        assert!(matches!(relayed, RelayedMedia::Indication(p) if p.peer == peer));
+
+        let mut chan = Vec::new();
+        ChannelData {
+            channel: 0x4ABC,
+            data: &rtp,
+        }
+        .encode(false, &mut chan)
+        .unwrap();
+        assert_eq!(unwrap_relayed(&chan).unwrap().unwrap().payload(), &rtp[..]);
+        assert_eq!(unwrap_relayed(&rtp), Ok(None));
+
+        // Mesaj uzunluğu 16 bite sığmalı: 12 (XOR-PEER-ADDRESS) + 4 + veri (4 byte hizalı)
+        let fits = vec![0u8; 65_516];
+        let send = encode_send_indication([5; 12], peer, &fits).unwrap();
+        let (_, decoded) = decode_peer_indication(&send).unwrap();
+        assert_eq!(decoded.data.len(), fits.len());
+        let jumbo = vec![0u8; fits.len() + 4];
+        assert_eq!(
+            encode_send_indication([5; 12], peer, &jumbo),
+            Err(TurnError::PayloadTooLarge(jumbo.len()))
+        );
+        assert_eq!(
+            encode_data_indication([5; 12], peer, &[0; 70_000]),
+            Err(TurnError::PayloadTooLarge(70_000))
+        );
+    }
+}
@@ -3,6 +3,8 @@
 use std::fs::File;
 use std::io::{self, Read};
 
+use crate::codecs::gsm::{GsmDecoder, WAVE_FORMAT_GSM610};
+
 pub struct WavAudio {
     pub samples: Vec<i16>,
     pub sample_rate: u32,
@@ -32,6 +34,7 @@ impl WavAudio {
         let mut pos = 12;
         let mut audio_data_start = 0;
         let mut audio_data_len = 0;
+        let mut format_tag = 1u16; // PCM
 
         while pos + 8 < buffer.len() {
             let chunk_id = &buffer[pos..pos + 4];
@@ -47,6 +50,10 @@ impl WavAudio {
                 break;
             }
 
+            if chunk_id == b"fmt " && chunk_size >= 2 {
+                format_tag = u16::from_le_bytes([buffer[pos + 8], buffer[pos + 9]]);
+            }
+
             if chunk_id == b"data" {
                 audio_data_start = pos + 8;
                 audio_data_len = chunk_size;
@@ -75,11 +82,16 @@ impl WavAudio {
 
         let data_part = &buffer[audio_data_start..audio_data_start + audio_data_len];
 
-        // i16 Dönüşümü
-        let samples: Vec<i16> = data_part
-            .chunks_exact(2)
-            .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
-            .collect();
+        let samples: Vec<i16> = if format_tag == WAVE_FORMAT_GSM610 {
+            // Microsoft GSM 6.10 (WAV49): 65 byte'lık bloklar, her biri 320 örnek
+            GsmDecoder::new().decode_wav49(data_part)
+        } else {
+            // i16 Dönüşümü
+            data_part
+                .chunks_exact(2)
+                .map(|chunk| i16::from_le_bytes([chunk[0], chunk[1]]))
+                .collect()
+        };
 
         Ok(WavAudio {
             samples,
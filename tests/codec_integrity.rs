@@ -5,7 +5,7 @@ use std::f64::consts::PI;
 
 // --- Test Yardımcı Fonksiyonları ---
 fn generate_sine_wave(freq: f64, duration_ms: u32, sample_rate: u32) -> Vec<i16> {
-    let num_samples = (sample_rate as u32 * duration_ms / 1000) as usize;
+    let num_samples = (sample_rate * duration_ms / 1000) as usize;
     let mut samples = Vec::with_capacity(num_samples);
     let amplitude = 28000.0;
     for i in 0..num_samples {
@@ -44,8 +44,8 @@ fn test_pcma_quality() {
     println!("\n--- [TEST BAŞLADI] Kodek: {} ---", name);
 
     let original_pcm = generate_sine_wave(440.0, 1000, 8000);
-    let mut encoder = CodecFactory::create_encoder(codec_type);
-    let mut decoder = CodecFactory::create_decoder(codec_type);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
 
     let encoded = encoder.encode(&original_pcm);
     let decoded = decoder.decode(&encoded);
@@ -71,8 +71,8 @@ fn test_pcmu_quality() {
     println!("\n--- [TEST BAŞLADI] Kodek: {} ---", name);
 
     let original_pcm = generate_sine_wave(440.0, 1000, 8000);
-    let mut encoder = CodecFactory::create_encoder(codec_type);
-    let mut decoder = CodecFactory::create_decoder(codec_type);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
 
     let encoded = encoder.encode(&original_pcm);
     let decoded = decoder.decode(&encoded);
@@ -91,6 +91,40 @@ fn test_pcmu_quality() {
 }
 
 #[test]
+fn test_g711_into_buffers_without_allocation() {
+    use sentiric_rtp_core::codecs::CodecError;
+
+    let original_pcm = generate_sine_wave(1000.0, 100, 8000);
+    for codec_type in [CodecType::PCMU, CodecType::PCMA] {
+        let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+        let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+        let mut reference = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+
+        // Medya döngüsü: Tamponlar bir kez ayrılır, her çerçevede yeniden kullanılır
+        let frame = codec_type.samples_per_frame(20);
+        let mut payload = vec![0u8; encoder.max_encoded_size(frame)];
+        let mut pcm = vec![0i16; decoder.decoded_size(payload.len())];
+        for chunk in original_pcm.chunks(frame) {
+            let written = encoder.encode_into(chunk, &mut payload).unwrap();
+            let decoded = decoder.decode_into(&payload[..written], &mut pcm).unwrap();
+            assert_eq!(
+                &pcm[..decoded],
+                reference.decode(&payload[..written]).as_slice()
+            );
+        }
+
+        assert_eq!(
+            encoder.encode_into(&original_pcm[..frame], &mut payload[..10]),
+            Err(CodecError::BufferTooSmall {
+                needed: frame,
+                available: 10
+            })
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "g729")]
 fn test_g729_quality() {
     let codec_type = CodecType::G729;
     let name = "G729";
@@ -101,8 +135,8 @@ fn test_g729_quality() {
     let g729_pcm_len = (original_pcm.len() / 80) * 80;
     let g729_pcm_slice = &original_pcm[0..g729_pcm_len];
 
-    let mut encoder = CodecFactory::create_encoder(codec_type);
-    let mut decoder = CodecFactory::create_decoder(codec_type);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
 
     let encoded = encoder.encode(g729_pcm_slice);
     let decoded = decoder.decode(&encoded);
@@ -120,7 +154,315 @@ fn test_g729_quality() {
     );
 }
 
-// G.722 testi kaldırıldı.
+#[test]
+fn test_g722_quality() {
+    let codec_type = CodecType::G722;
+    let name = "G722 (SB-ADPCM)";
+    let psnr_threshold = 30.0;
+    println!("\n--- [TEST BAŞLADI] Kodek: {} ---", name);
+
+    // G.722 16 kHz ses ister; 20ms paketler (320 sample -> 160 byte)
+    let original_pcm = generate_sine_wave(1000.0, 1000, codec_type.sample_rate());
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+
+    let frame = codec_type.samples_per_frame(20);
+    let mut decoded = Vec::with_capacity(original_pcm.len());
+    for chunk in original_pcm.chunks(frame) {
+        let encoded = encoder.encode(chunk);
+        assert_eq!(encoded.len(), codec_type.payload_size_bytes(20));
+        decoded.extend(decoder.decode(&encoded));
+    }
+    assert_eq!(codec_type.rtp_timestamp_increment(20), 160);
+
+    // QMF filtre çifti sabit bir gecikme ekler; en iyi hizalama aranır
+    let psnr = (0..48)
+        .map(|delay| {
+            calculate_psnr(
+                &original_pcm[..original_pcm.len() - delay],
+                &decoded[delay..],
+            )
+        })
+        .fold(f64::MIN, f64::max);
+
+    println!(
+        "  └─ Hesaplanan PSNR: {:.2} dB (Eşik: >{:.1} dB)",
+        psnr, psnr_threshold
+    );
+    assert!(
+        psnr > psnr_threshold,
+        "{} PSNR değeri ({:.2} dB) eşiğin altında kaldı!",
+        name,
+        psnr
+    );
+}
+
+#[test]
+fn test_g726_quality() {
+    let codec_type = CodecType::G726_32;
+    let name = "G726-32 (ADPCM)";
+    let psnr_threshold = 25.0;
+    println!("\n--- [TEST BAŞLADI] Kodek: {} ---", name);
+
+    let original_pcm = generate_sine_wave(1000.0, 1000, 8000);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+
+    let frame = codec_type.samples_per_frame(20);
+    let mut decoded = Vec::with_capacity(original_pcm.len());
+    for chunk in original_pcm.chunks(frame) {
+        let encoded = encoder.encode(chunk);
+        assert_eq!(encoded.len(), codec_type.payload_size_bytes(20));
+        decoded.extend(decoder.decode(&encoded));
+    }
+    // Adaptasyon süresi (ilk 20ms) hariç tutulur
+    let psnr = calculate_psnr(&original_pcm[frame..], &decoded[frame..]);
+
+    println!(
+        "  └─ Hesaplanan PSNR: {:.2} dB (Eşik: >{:.1} dB)",
+        psnr, psnr_threshold
+    );
+    assert!(
+        psnr > psnr_threshold,
+        "{} PSNR değeri ({:.2} dB) eşiğin altında kaldı!",
+        name,
+        psnr
+    );
+}
+
+/// Depodaki G.726 referans vektörleri (`tests/data/g726`): Sun g72x referansının C dökümüyle
+/// üretilmiş 1024 örneklik G.711 girdisi (sessizlik, tarama, 2100 Hz ton, aşırı seviye,
+/// gürültü), her hız ve kural için kod (`.i`) ve tandem düzeltmeli çıkış (`.o`) dosyaları.
+#[test]
+fn test_g726_reference_vectors() {
+    use sentiric_rtp_core::codecs::g726::{G711Law, G726Packing, G726Rate};
+    use sentiric_rtp_core::codecs::{G726Decoder, G726Encoder};
+
+    macro_rules! vector {
+        ($name:literal) => {
+            &include_bytes!(concat!("data/g726/", $name))[..]
+        };
+    }
+    let inputs = [
+        (G711Law::ALaw, vector!("input.a")),
+        (G711Law::MuLaw, vector!("input.u")),
+    ];
+    let cases = [
+        (
+            G726Rate::Kbps16,
+            G711Law::ALaw,
+            vector!("g726_16.a.i"),
+            vector!("g726_16.a.o"),
+        ),
+        (
+            G726Rate::Kbps16,
+            G711Law::MuLaw,
+            vector!("g726_16.u.i"),
+            vector!("g726_16.u.o"),
+        ),
+        (
+            G726Rate::Kbps24,
+            G711Law::ALaw,
+            vector!("g726_24.a.i"),
+            vector!("g726_24.a.o"),
+        ),
+        (
+            G726Rate::Kbps24,
+            G711Law::MuLaw,
+            vector!("g726_24.u.i"),
+            vector!("g726_24.u.o"),
+        ),
+        (
+            G726Rate::Kbps32,
+            G711Law::ALaw,
+            vector!("g726_32.a.i"),
+            vector!("g726_32.a.o"),
+        ),
+        (
+            G726Rate::Kbps32,
+            G711Law::MuLaw,
+            vector!("g726_32.u.i"),
+            vector!("g726_32.u.o"),
+        ),
+        (
+            G726Rate::Kbps40,
+            G711Law::ALaw,
+            vector!("g726_40.a.i"),
+            vector!("g726_40.a.o"),
+        ),
+        (
+            G726Rate::Kbps40,
+            G711Law::MuLaw,
+            vector!("g726_40.u.i"),
+            vector!("g726_40.u.o"),
+        ),
+    ];
+
+    for (rate, law, codes, output) in cases {
+        let (_, pcm) = inputs.iter().find(|(l, _)| *l == law).unwrap();
+        assert_eq!(codes.len(), pcm.len());
+        assert_eq!(output.len(), pcm.len());
+
+        let mut encoder = G726Encoder::new(rate, G726Packing::Rfc3551);
+        for (n, (&sample, &expected)) in pcm.iter().zip(codes).enumerate() {
+            let code = encoder.encode_law_sample(law, sample);
+            assert_eq!(code, expected, "{:?}/{:?} kodlama, örnek {}", rate, law, n);
+        }
+
+        let mut decoder = G726Decoder::new(rate, G726Packing::Rfc3551);
+        for (n, (&code, &expected)) in codes.iter().zip(output).enumerate() {
+            let sample = decoder.decode_sample_to_law(law, code);
+            assert_eq!(sample, expected, "{:?}/{:?} çözme, örnek {}", rate, law, n);
+        }
+        println!("  ✅ G.726 {:?} {:?} bit-exact", rate, law);
+    }
+}
+
+/// ITU-T G.726 Appendix II test vektörleri (dağıtılmaz, lisans gereği repo dışındadır).
+/// `G726_TEST_VECTORS` dizini ITU paketinin `DISK1` yapısını içermeli; her dosyada örnek
+/// başına bir byte bulunur. Vektörler repoda olmadığından test varsayılan olarak çalışmaz:
+/// `G726_TEST_VECTORS=/yol/DISK1 cargo test --test codec_integrity -- --ignored test_g726_itu_vectors`
+#[test]
+#[ignore = "G726_TEST_VECTORS ile ITU Appendix II vektörleri gerekir"]
+fn test_g726_itu_vectors() {
+    use sentiric_rtp_core::codecs::g726::{G711Law, G726Packing, G726Rate};
+    use sentiric_rtp_core::codecs::{G726Decoder, G726Encoder};
+
+    let root = std::env::var("G726_TEST_VECTORS")
+        .expect("G726_TEST_VECTORS, ITU paketinin DISK1 dizinini göstermeli");
+    let root = std::path::Path::new(&root);
+    let mut checked = 0;
+
+    for (rate, kbps) in [
+        (G726Rate::Kbps16, 16),
+        (G726Rate::Kbps24, 24),
+        (G726Rate::Kbps32, 32),
+        (G726Rate::Kbps40, 40),
+    ] {
+        for (law, suffix) in [(G711Law::ALaw, 'A'), (G711Law::MuLaw, 'M')] {
+            for input_name in ["NRM", "OVR"] {
+                let prefix = if input_name == "NRM" { "RN" } else { "RV" };
+                let input = root.join(format!("INPUT/{}.{}", input_name, suffix));
+                let base = root.join(format!("RESET/{}/{}{}F{}", kbps, prefix, kbps, suffix));
+                let (Ok(pcm), Ok(codes), Ok(output)) = (
+                    std::fs::read(&input),
+                    std::fs::read(base.with_extension("I")),
+                    std::fs::read(base.with_extension("O")),
+                ) else {
+                    println!("  └─ Vektör bulunamadı: {}", base.display());
+                    continue;
+                };
+                assert!(
+                    !codes.is_empty() && codes.len() == pcm.len() && output.len() == pcm.len(),
+                    "{} vektör uzunlukları tutarsız",
+                    base.display()
+                );
+
+                let mut encoder = G726Encoder::new(rate, G726Packing::Rfc3551);
+                for (n, (&sample, &expected)) in pcm.iter().zip(&codes).enumerate() {
+                    let code = encoder.encode_law_sample(law, sample);
+                    assert_eq!(code, expected, "{} kodlama, örnek {}", base.display(), n);
+                }
+
+                let mut decoder = G726Decoder::new(rate, G726Packing::Rfc3551);
+                for (n, (&code, &expected)) in codes.iter().zip(&output).enumerate() {
+                    let sample = decoder.decode_sample_to_law(law, code);
+                    assert_eq!(sample, expected, "{} çözme, örnek {}", base.display(), n);
+                }
+                println!("  ✅ {} bit-exact", base.display());
+                checked += 1;
+            }
+        }
+    }
+    assert!(
+        checked > 0,
+        "{} altında hiç ITU vektörü bulunamadı",
+        root.display()
+    );
+}
+
+#[test]
+fn test_gsm_quality() {
+    let codec_type = CodecType::GSM;
+    let name = "GSM 06.10 (RPE-LTP)";
+    // RPE-LTP dalga biçimini ADPCM kadar yakından izlemez, eşik daha düşüktür
+    let psnr_threshold = 25.0;
+    println!("\n--- [TEST BAŞLADI] Kodek: {} ---", name);
+
+    let original_pcm = generate_sine_wave(1000.0, 1000, 8000);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+
+    let frame = codec_type.samples_per_frame(20);
+    let mut decoded = Vec::with_capacity(original_pcm.len());
+    for chunk in original_pcm.chunks(frame) {
+        let encoded = encoder.encode(chunk);
+        assert_eq!(encoded.len(), codec_type.payload_size_bytes(20));
+        decoded.extend(decoder.decode(&encoded));
+    }
+    // Adaptasyon süresi (ilk 20ms) hariç tutulur
+    let psnr = calculate_psnr(&original_pcm[frame..], &decoded[frame..]);
+
+    println!(
+        "  └─ Hesaplanan PSNR: {:.2} dB (Eşik: >{:.1} dB)",
+        psnr, psnr_threshold
+    );
+    assert!(
+        psnr > psnr_threshold,
+        "{} PSNR değeri ({:.2} dB) eşiğin altında kaldı!",
+        name,
+        psnr
+    );
+}
+
+#[test]
+fn test_l16_lossless() {
+    let codec_type = CodecType::L16 {
+        rate: 16000,
+        channels: 1,
+    };
+    println!("\n--- [TEST BAŞLADI] Kodek: L16/16000 (kayıpsız) ---");
+
+    let original_pcm = generate_sine_wave(1000.0, 1000, 16000);
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin()
+        .create_decoder(CodecType::from_u8(116).unwrap())
+        .unwrap();
+
+    let frame = codec_type.samples_per_frame(20);
+    assert_eq!(frame, 320);
+    let mut decoded = Vec::with_capacity(original_pcm.len());
+    for chunk in original_pcm.chunks(frame) {
+        let encoded = encoder.encode(chunk);
+        assert_eq!(encoded.len(), codec_type.payload_size_bytes(20));
+        decoded.extend(decoder.decode(&encoded));
+    }
+    assert_eq!(decoded, original_pcm);
+    println!("  └─ Çözülen ses orijinaliyle birebir aynı.");
+}
+
+#[test]
+fn test_clearmode_bypasses_dsp() {
+    use sentiric_rtp_core::dsp::AudioResampler;
+
+    let codec_type = CodecType::Clearmode;
+    println!("\n--- [TEST BAŞLADI] Kodek: CLEARMODE/8000 (şeffaf veri) ---");
+
+    // Noise gate eşiğinin (30) altındaki ve DC içeren oktetler dahil tüm değerler
+    let octets: Vec<u8> = (0..1600).map(|i| (i * 7 % 256) as u8).collect();
+    let mut encoder = CodecFactory::builtin().create_encoder(codec_type).unwrap();
+    let mut decoder = CodecFactory::builtin().create_decoder(codec_type).unwrap();
+    let mut resampler = AudioResampler::new(8000, 16000, 0);
+    resampler.set_bypass(codec_type.is_transparent());
+
+    let mut received = Vec::with_capacity(octets.len());
+    for chunk in octets.chunks(codec_type.payload_size_bytes(20)) {
+        let samples = resampler.process(&decoder.decode(chunk));
+        received.extend(encoder.encode(&samples));
+    }
+    assert_eq!(received, octets);
+    println!("  └─ Oktetler DSP'den değişmeden geçti.");
+}
 
 #[test]
 fn test_dsp_phase_continuity_no_crackle() {
@@ -171,3 +513,97 @@ fn test_dsp_phase_continuity_no_crackle() {
 
     println!("  ✅ DSP Motoru paket geçişlerinde faz kaybetmiyor (Cızırtı yok).");
 }
+
+#[test]
+fn test_factory_reports_unknown_payload() {
+    use sentiric_rtp_core::codecs::{
+        CodecDescriptor, CodecRegistry, Decoder, Encoder, L16Decoder, L16Encoder, PayloadTypeMap,
+    };
+    use sentiric_rtp_core::config::CodecConfig;
+    use sentiric_rtp_core::Error;
+
+    println!("\n--- [TEST BAŞLADI] Fabrika: müzakere edilmemiş PT ---");
+
+    let mut map = PayloadTypeMap::with_static();
+    assert!(CodecFactory::builtin()
+        .create_decoder_for_payload(&map, 0)
+        .is_ok());
+    assert_eq!(
+        CodecFactory::builtin()
+            .create_decoder_for_payload(&map, 96)
+            .err(),
+        Some(Error::UnsupportedPayload(96))
+    );
+    assert!(CodecFactory::builtin()
+        .create_encoder_for_payload(&map, 96)
+        .is_err());
+
+    assert_eq!(
+        map.insert_rtpmap("a=rtpmap:96 VP8/90000"),
+        Err(Error::UnsupportedEncoding("VP8".to_string()))
+    );
+    assert!(CodecFactory::builtin()
+        .create_decoder_for_payload(&map, 96)
+        .is_err());
+
+    // Yerleşik defterde olmayan uygulama kodeği kimliğiyle raporlanır
+    let mut registry = CodecRegistry::with_builtin();
+    let custom = registry.register(
+        CodecDescriptor::new(String::from("X-LINEAR"), 16000, 1),
+        |_: &CodecConfig| Ok(Box::new(L16Encoder::new(16000, 1)) as Box<dyn Encoder>),
+        |_: &CodecConfig| Ok(Box::new(L16Decoder::new(16000, 1)) as Box<dyn Decoder>),
+    );
+    let CodecType::Custom { id, .. } = custom else {
+        panic!("özel kodek bekleniyordu");
+    };
+    assert_eq!(
+        CodecFactory::builtin().create_encoder(custom).err(),
+        Some(Error::UnsupportedEncoding(id.to_string()))
+    );
+    assert_eq!(
+        CodecFactory::builtin().create_decoder(custom).err(),
+        Some(Error::UnsupportedEncoding(id.to_string()))
+    );
+    assert!(CodecFactory::new(&registry).create_decoder(custom).is_ok());
+    println!("  └─ Bilinmeyen PT hata olarak döndü, süreç çökmedi.");
+}
+
+#[test]
+fn test_aal2_g726_survives_payload_map() {
+    use sentiric_rtp_core::codecs::g726::{G726Packing, G726Rate};
+    use sentiric_rtp_core::codecs::{Decoder, Encoder, G726Decoder, G726Encoder, PayloadTypeMap};
+    use sentiric_rtp_core::config::AudioProfile;
+
+    println!("\n--- [TEST BAŞLADI] G.726 AAL2: PT haritası üzerinden gidiş-dönüş ---");
+
+    let mut map = PayloadTypeMap::with_static();
+    map.insert_rtpmap("a=rtpmap:98 AAL2-G726-32/8000").unwrap();
+    let profile = AudioProfile::default();
+    let config = profile.get_by_payload_in(&map, 98).unwrap();
+    assert_eq!(config.rtpmap_line(), "a=rtpmap:98 AAL2-G726-32/8000");
+
+    let pcm = generate_sine_wave(1000.0, 20, 8000);
+    let reference = G726Encoder::new(G726Rate::Kbps32, G726Packing::Aal2).encode(&pcm);
+    let encoded = profile.create_encoder_in(&map, 98).unwrap().encode(&pcm);
+    assert_eq!(encoded, reference);
+    assert_ne!(
+        encoded,
+        G726Encoder::new(G726Rate::Kbps32, G726Packing::Rfc3551).encode(&pcm)
+    );
+
+    let expected = G726Decoder::new(G726Rate::Kbps32, G726Packing::Aal2).decode(&encoded);
+    let mut from_profile = profile.create_decoder_in(&map, 98).unwrap();
+    let mut from_factory = CodecFactory::builtin()
+        .create_decoder_for_payload(&map, 98)
+        .unwrap();
+    assert_eq!(from_profile.decode(&encoded), expected);
+    assert_eq!(from_factory.decode(&encoded), expected);
+    assert_eq!(
+        CodecFactory::builtin()
+            .create_encoder_for_payload(&map, 98)
+            .unwrap()
+            .encode(&pcm),
+        reference
+    );
+    println!("  └─ AAL2 paketleme düzeni haritadan kodeğe taşındı.");
+}
@@ -0,0 +1,90 @@
+
+
+
+		
+			
+
+
+	
+
+
+
+					
+
+
+
+
+	
+
+
+
+
+
+
+
+
+
+	
+	
+		
+		
+
+			
+		
+
+
+
+
+
+
+
+
+	
+
+	
+	
+
+
+	
+	
+
+
+	
+	
+
+
+
+
+
+
+
+	
+	
+
+
+
+				
+
+	
+
+
+	
+	
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+		
+
+
+	
\ No newline at end of file
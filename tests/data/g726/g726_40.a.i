@@ -0,0 +1,35 @@
+			
+	
+	
+				
+
+
+	
+
+	
+	
+
+	
+
+						
+
+		
+
+
+
+		
+
+
+	
+	
+				
+				
+
+				
+		
+
+		
+	
+		
+	
+	
\ No newline at end of file
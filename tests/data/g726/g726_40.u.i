@@ -0,0 +1,46 @@
+
+
+			
+
+
+
+	
+	
+
+		
+	
+		
+		
+
+	
+		
+		
+			
+
+	
+	
+		
+	
+	
+		
+		
+
+	
+			
+
+	
+
+
+
+
+
+
+
+
+				
+					
+	
+
+			
+	
+	
\ No newline at end of file